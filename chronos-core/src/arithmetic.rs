@@ -0,0 +1,183 @@
+//! Natural number arithmetic for the virtual machine
+//!
+//! `Nat` arithmetic is checked: a result that does not fit in a `u64` raises
+//! [`VmError::ArithmeticOverflow`] instead of panicking or wrapping. With the
+//! `bigint` feature enabled such results are promoted to `Value::BigNat`
+//! instead. Values that fit in a `u64` are always kept as `Value::Nat`, so
+//! equal numbers always compare equal.
+
+use crate::types::Value;
+use crate::vm::VmError;
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+use std::cmp::Ordering;
+
+/// A binary operation on natural numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NatOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl NatOp {
+    /// The word that performs this operation
+    #[cfg(not(feature = "bigint"))]
+    fn word(self) -> &'static str {
+        match self {
+            NatOp::Add => "+",
+            NatOp::Sub => "-",
+            NatOp::Mul => "*",
+            NatOp::Div => "/",
+            NatOp::Mod => "mod",
+        }
+    }
+}
+
+/// Apply `op` to two natural numbers, `a op b`
+pub(crate) fn apply(op: NatOp, a: Value, b: Value) -> Result<Value, VmError> {
+    match (a, b) {
+        (Value::Nat(x), Value::Nat(y)) => checked(op, x, y),
+        #[cfg(feature = "bigint")]
+        (a, b) => match (to_big(&a), to_big(&b)) {
+            (Some(x), Some(y)) => big(op, x, y),
+            _ => Err(nat_mismatch()),
+        },
+        #[cfg(not(feature = "bigint"))]
+        _ => Err(nat_mismatch()),
+    }
+}
+
+/// Compare two natural numbers
+pub(crate) fn compare(a: &Value, b: &Value) -> Result<Ordering, VmError> {
+    match (a, b) {
+        (Value::Nat(x), Value::Nat(y)) => Ok(x.cmp(y)),
+        #[cfg(feature = "bigint")]
+        (a, b) => match (to_big(a), to_big(b)) {
+            (Some(x), Some(y)) => Ok(x.cmp(&y)),
+            _ => Err(nat_mismatch()),
+        },
+        #[cfg(not(feature = "bigint"))]
+        _ => Err(nat_mismatch()),
+    }
+}
+
+/// Convert a big natural number back to a `Value`, preferring `Value::Nat`
+#[cfg(feature = "bigint")]
+pub(crate) fn from_big(n: BigUint) -> Value {
+    match u64::try_from(&n) {
+        Ok(small) => Value::Nat(small),
+        Err(_) => Value::BigNat(n),
+    }
+}
+
+fn checked(op: NatOp, x: u64, y: u64) -> Result<Value, VmError> {
+    check_domain(op, x < y, y == 0)?;
+
+    let result = match op {
+        NatOp::Add => x.checked_add(y),
+        NatOp::Sub => x.checked_sub(y),
+        NatOp::Mul => x.checked_mul(y),
+        NatOp::Div => x.checked_div(y),
+        NatOp::Mod => x.checked_rem(y),
+    };
+
+    match result {
+        Some(n) => Ok(Value::Nat(n)),
+        #[cfg(feature = "bigint")]
+        None => big(op, BigUint::from(x), BigUint::from(y)),
+        #[cfg(not(feature = "bigint"))]
+        None => Err(VmError::ArithmeticOverflow {
+            operation: op.word().to_string(),
+            operands: vec![x.to_string(), y.to_string()],
+        }),
+    }
+}
+
+#[cfg(feature = "bigint")]
+fn big(op: NatOp, x: BigUint, y: BigUint) -> Result<Value, VmError> {
+    check_domain(op, x < y, y == BigUint::ZERO)?;
+
+    let result = match op {
+        NatOp::Add => x + y,
+        NatOp::Sub => x - y,
+        NatOp::Mul => x * y,
+        NatOp::Div => x / y,
+        NatOp::Mod => x % y,
+    };
+    Ok(from_big(result))
+}
+
+#[cfg(feature = "bigint")]
+fn to_big(value: &Value) -> Option<BigUint> {
+    match value {
+        Value::Nat(n) => Some(BigUint::from(*n)),
+        Value::BigNat(n) => Some(n.clone()),
+        _ => None,
+    }
+}
+
+/// Reject operations whose result is not a natural number
+fn check_domain(op: NatOp, less: bool, zero_divisor: bool) -> Result<(), VmError> {
+    match op {
+        NatOp::Sub if less => Err(VmError::InvalidOperation(
+            "Cannot subtract to negative result".to_string(),
+        )),
+        NatOp::Div if zero_divisor => Err(VmError::RuntimeError("Division by zero".to_string())),
+        NatOp::Mod if zero_divisor => Err(VmError::RuntimeError("Modulo by zero".to_string())),
+        _ => Ok(()),
+    }
+}
+
+fn nat_mismatch() -> VmError {
+    VmError::TypeMismatch {
+        expected: "Nat Nat".to_string(),
+        found: "other types".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let nat = Value::Nat;
+        assert_eq!(apply(NatOp::Add, nat(2), nat(3)).unwrap(), nat(5));
+        assert_eq!(apply(NatOp::Mod, nat(7), nat(3)).unwrap(), nat(1));
+        assert!(apply(NatOp::Sub, nat(2), nat(3)).is_err());
+        assert!(apply(NatOp::Div, nat(2), nat(0)).is_err());
+        assert!(apply(NatOp::Add, nat(1), Value::Bool(true)).is_err());
+        assert_eq!(compare(&nat(2), &nat(3)).unwrap(), Ordering::Less);
+    }
+
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn test_overflow_is_an_error() {
+        match apply(NatOp::Mul, Value::Nat(u64::MAX), Value::Nat(2)) {
+            Err(VmError::ArithmeticOverflow {
+                operation,
+                operands,
+            }) => {
+                assert_eq!(operation, "*");
+                assert_eq!(operands, vec![u64::MAX.to_string(), "2".to_string()]);
+            }
+            other => panic!("Expected an overflow error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_overflow_promotes_to_bignat() {
+        let big = apply(NatOp::Add, Value::Nat(u64::MAX), Value::Nat(1)).unwrap();
+        assert_eq!(big.to_string(), "18446744073709551616");
+        assert!(matches!(big, Value::BigNat(_)));
+
+        // Results that fit again are demoted back to `Nat`
+        let small = apply(NatOp::Sub, big.clone(), Value::Nat(2)).unwrap();
+        assert_eq!(small, Value::Nat(u64::MAX - 1));
+        assert_eq!(compare(&big, &small).unwrap(), Ordering::Greater);
+    }
+}
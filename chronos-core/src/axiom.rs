@@ -0,0 +1,181 @@
+//! Axioms: words that are assumed rather than defined
+//!
+//! `axiom name ( inputs -> outputs ) ;` declares a word with a stack effect
+//! but no body. The type checker takes the signature on trust, so later
+//! definitions may build on the axiom, but the axiom itself can never run:
+//!
+//! ```text
+//! axiom excluded-middle ( Bool -> Bool ) ;
+//! : decide excluded-middle not ;
+//! ```
+//!
+//! [`AxiomRegistry::dependencies`] reports which words rest on which axioms.
+
+use crate::types::{Token, TypeSignature, Value, WordDefinition};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The axioms declared so far, with their signatures
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AxiomRegistry {
+    axioms: BTreeMap<String, TypeSignature>,
+}
+
+impl AxiomRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare an axiom, replacing any earlier axiom of the same name
+    pub fn declare(&mut self, name: String, signature: TypeSignature) {
+        self.axioms.insert(name, signature);
+    }
+
+    /// Forget an axiom, for example because the word was given a definition
+    pub fn remove(&mut self, name: &str) -> Option<TypeSignature> {
+        self.axioms.remove(name)
+    }
+
+    /// The signature assumed for an axiom
+    pub fn get(&self, name: &str) -> Option<&TypeSignature> {
+        self.axioms.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.axioms.contains_key(name)
+    }
+
+    /// Every axiom with its signature, in name order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &TypeSignature)> {
+        self.axioms.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.axioms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axioms.is_empty()
+    }
+
+    /// The axioms each word in `dictionary` relies on, directly or through
+    /// the words it calls
+    ///
+    /// Words that rely on no axiom, and the axioms themselves, are left out.
+    pub fn dependencies(
+        &self,
+        dictionary: &HashMap<String, WordDefinition>,
+    ) -> BTreeMap<String, BTreeSet<String>> {
+        let mut dependencies = BTreeMap::new();
+        for word_def in dictionary.values().filter(|def| !def.is_axiom) {
+            let mut seen = BTreeSet::new();
+            let mut pending = vec![word_def.name.as_str()];
+            let mut axioms = BTreeSet::new();
+            while let Some(name) = pending.pop() {
+                if !seen.insert(name) {
+                    continue;
+                }
+                if self.contains(name) {
+                    axioms.insert(name.to_string());
+                } else if let Some(callee) = dictionary.get(name) {
+                    collect_words(&callee.body, &mut pending);
+                }
+            }
+            if !axioms.is_empty() {
+                dependencies.insert(word_def.name.clone(), axioms);
+            }
+        }
+        dependencies
+    }
+}
+
+/// Push every word named in `tokens`, including inside quotes and match arms
+pub(crate) fn collect_words<'a>(tokens: &'a [Token], words: &mut Vec<&'a str>) {
+    for token in tokens {
+        match token {
+            Token::Word(word) => words.push(word),
+            Token::Literal(Value::Quote(body)) => collect_words(body, words),
+            Token::MatchExpression { value, arms } => {
+                collect_words(std::slice::from_ref(value.as_ref()), words);
+                for arm in arms {
+                    collect_words(&arm.body, words);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{OrdinalValue, Type};
+    use crate::Lexer;
+
+    fn word(name: &str, source: &str) -> WordDefinition {
+        WordDefinition {
+            name: name.to_string(),
+            signature: TypeSignature {
+                inputs: vec![],
+                outputs: vec![],
+            },
+            body: Lexer::new(source).tokenize().unwrap(),
+            is_axiom: false,
+            ordinal_cost: OrdinalValue::Zero,
+        }
+    }
+
+    fn registry() -> AxiomRegistry {
+        let mut registry = AxiomRegistry::new();
+        registry.declare(
+            "lem".to_string(),
+            TypeSignature {
+                inputs: vec![Type::Bool],
+                outputs: vec![Type::Bool],
+            },
+        );
+        registry.declare(
+            "choice".to_string(),
+            TypeSignature {
+                inputs: vec![],
+                outputs: vec![Type::Nat],
+            },
+        );
+        registry
+    }
+
+    #[test]
+    fn test_dependencies_are_transitive() {
+        let dictionary: HashMap<String, WordDefinition> = [
+            word("direct", "lem not"),
+            word("indirect", "direct"),
+            word("quoted", "[ choice ] call"),
+            word("both", "indirect quoted"),
+            word("plain", "1 2 +"),
+        ]
+        .into_iter()
+        .map(|def| (def.name.clone(), def))
+        .collect();
+
+        let dependencies = registry().dependencies(&dictionary);
+        let axioms =
+            |name: &str| -> Vec<&str> { dependencies[name].iter().map(String::as_str).collect() };
+        assert_eq!(axioms("direct"), vec!["lem"]);
+        assert_eq!(axioms("indirect"), vec!["lem"]);
+        assert_eq!(axioms("quoted"), vec!["choice"]);
+        assert_eq!(axioms("both"), vec!["choice", "lem"]);
+        assert!(!dependencies.contains_key("plain"));
+    }
+
+    #[test]
+    fn test_dependencies_through_recursion() {
+        let dictionary: HashMap<String, WordDefinition> =
+            [word("even", "lem odd"), word("odd", "even")]
+                .into_iter()
+                .map(|def| (def.name.clone(), def))
+                .collect();
+
+        let dependencies = registry().dependencies(&dictionary);
+        assert!(dependencies["even"].contains("lem"));
+        assert!(dependencies["odd"].contains("lem"));
+    }
+}
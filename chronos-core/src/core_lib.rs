@@ -35,6 +35,9 @@ impl CoreLibrary {
         // Arithmetic primitives
         self.define_arithmetic_words();
 
+        // Ordinal arithmetic in Cantor normal form
+        self.define_ordinal_words();
+
         // Logical primitives
         self.define_logical_words();
 
@@ -44,8 +47,17 @@ impl CoreLibrary {
         // Type system primitives
         self.define_type_words();
 
+        // List primitives
+        self.define_list_words();
+
         // System primitives
         self.define_system_words();
+
+        // String primitives
+        self.define_string_words();
+
+        // Assertions for `test:` definitions
+        self.define_testing_words();
     }
 
     /// Define stack manipulation words
@@ -322,6 +334,178 @@ impl CoreLibrary {
             vec![],
             "Execute the quote on top of the stack",
         );
+
+        // while: ( Nat Quote Quote -> ) - Loop while condition holds, bounded by fuel
+        self.define_builtin(
+            "while",
+            vec![Type::Nat, Type::Quote, Type::Quote],
+            vec![],
+            "Run body while condition holds, failing after fuel iterations",
+        );
+
+        let list_of = |name: &str| Type::List(Box::new(Type::Variable(name.to_string())));
+
+        // each: ( List<a> Quote -> ) - Run quote on every element
+        self.define_builtin(
+            "each",
+            vec![list_of("a"), Type::Quote],
+            vec![],
+            "Execute quote once for each list element",
+        );
+
+        // map: ( List<a> Quote -> List<b> ) - Transform every element
+        self.define_builtin(
+            "map",
+            vec![list_of("a"), Type::Quote],
+            vec![list_of("b")],
+            "Apply quote to each list element, collecting the results",
+        );
+
+        // filter: ( List<a> Quote -> List<a> ) - Keep elements satisfying a predicate
+        self.define_builtin(
+            "filter",
+            vec![list_of("a"), Type::Quote],
+            vec![list_of("a")],
+            "Keep the list elements for which quote yields true",
+        );
+
+        // fold: ( List<a> b Quote -> b ) - Left fold with an initial accumulator
+        self.define_builtin(
+            "fold",
+            vec![list_of("a"), Type::Variable("b".to_string()), Type::Quote],
+            vec![Type::Variable("b".to_string())],
+            "Combine list elements into an accumulator from the left",
+        );
+
+        // range: ( Nat Nat -> List<Nat> ) - Half-open range of naturals
+        self.define_builtin(
+            "range",
+            vec![Type::Nat, Type::Nat],
+            vec![Type::List(Box::new(Type::Nat))],
+            "Build the list start, start+1, ..., end-1",
+        );
+    }
+
+    /// Define list words; the `?` variants return `None` where the plain
+    /// word would fail
+    fn define_list_words(&mut self) {
+        let a = || Type::Variable("a".to_string());
+        let list_of = |ty: Type| Type::List(Box::new(ty));
+        let option_of = |ty: Type| Type::Option(Box::new(ty));
+
+        // cons: ( a List<a> -> List<a> ) - Prepend a value
+        self.define_builtin(
+            "cons",
+            vec![a(), list_of(a())],
+            vec![list_of(a())],
+            "Prepend a value to a list",
+        );
+
+        // head: ( List<a> -> a ) - First element
+        self.define_builtin("head", vec![list_of(a())], vec![a()], "First element");
+        self.define_builtin(
+            "head?",
+            vec![list_of(a())],
+            vec![option_of(a())],
+            "First element, or None",
+        );
+
+        // tail: ( List<a> -> List<a> ) - All but the first element
+        self.define_builtin(
+            "tail",
+            vec![list_of(a())],
+            vec![list_of(a())],
+            "All but the first element",
+        );
+        self.define_builtin(
+            "tail?",
+            vec![list_of(a())],
+            vec![option_of(list_of(a()))],
+            "All but the first element, or None",
+        );
+
+        // nth: ( List<a> Nat -> a ) - Element at a zero-based index
+        self.define_builtin(
+            "nth",
+            vec![list_of(a()), Type::Nat],
+            vec![a()],
+            "Element at a zero-based index",
+        );
+        self.define_builtin(
+            "nth?",
+            vec![list_of(a()), Type::Nat],
+            vec![option_of(a())],
+            "Element at a zero-based index, or None",
+        );
+
+        // append: ( List<a> List<a> -> List<a> ) - Concatenate two lists
+        self.define_builtin(
+            "append",
+            vec![list_of(a()), list_of(a())],
+            vec![list_of(a())],
+            "Concatenate two lists",
+        );
+
+        // reverse: ( List<a> -> List<a> ) - Reverse a list
+        self.define_builtin(
+            "reverse",
+            vec![list_of(a())],
+            vec![list_of(a())],
+            "Reverse a list",
+        );
+
+        // sort: ( List<a> -> List<a> ) - Sort in ascending order
+        self.define_builtin(
+            "sort",
+            vec![list_of(a())],
+            vec![list_of(a())],
+            "Sort in ascending order",
+        );
+
+        // zip: ( List<a> List<a> -> List<List<a>> ) - Pair up elements
+        self.define_builtin(
+            "zip",
+            vec![list_of(a()), list_of(a())],
+            vec![list_of(list_of(a()))],
+            "Pair up elements, stopping at the shorter list",
+        );
+    }
+
+    /// Define ordinal arithmetic words
+    fn define_ordinal_words(&mut self) {
+        // omega: ( -> Ordinal ) - The first infinite ordinal
+        self.define_builtin("omega", vec![], vec![Type::Ordinal], "Push the ordinal ω");
+        self.define_builtin("ω", vec![], vec![Type::Ordinal], "Push the ordinal ω");
+
+        // ord: ( Nat -> Ordinal ) - A natural number as an ordinal
+        self.define_builtin(
+            "ord",
+            vec![Type::Nat],
+            vec![Type::Ordinal],
+            "Convert a natural number to a finite ordinal",
+        );
+
+        // ord+ ord* ord^: ( Ordinal Ordinal -> Ordinal ) - Non-commutative arithmetic
+        for (name, description) in [
+            ("ord+", "Ordinal addition in Cantor normal form"),
+            ("ord*", "Ordinal multiplication in Cantor normal form"),
+            ("ord^", "Ordinal exponentiation in Cantor normal form"),
+        ] {
+            self.define_builtin(
+                name,
+                vec![Type::Ordinal, Type::Ordinal],
+                vec![Type::Ordinal],
+                description,
+            );
+        }
+
+        // ord<: ( Ordinal Ordinal -> Bool ) - Ordinal comparison
+        self.define_builtin(
+            "ord<",
+            vec![Type::Ordinal, Type::Ordinal],
+            vec![Type::Bool],
+            "Test whether one ordinal is less than another",
+        );
     }
 
     /// Define type system words
@@ -355,10 +539,49 @@ impl CoreLibrary {
             vec![Type::Variable("b".to_string())],
             "Cast value to specified type (unsafe operation)",
         );
+
+        let var = |name: &str| Type::Variable(name.to_string());
+        let option_of = |name: &str| Type::Option(Box::new(var(name)));
+        let result_of = Type::Result(Box::new(var("a")), Box::new(var("e")));
+
+        // Some: ( a -> Option<a> ) - Wrap a value in an Option
+        self.define_builtin(
+            "Some",
+            vec![var("a")],
+            vec![option_of("a")],
+            "Wrap a value as Some",
+        );
+
+        // None: ( -> Option<a> ) - The empty Option
+        self.define_builtin("None", vec![], vec![option_of("a")], "Push None");
+
+        // Ok: ( a -> Result<a, e> ) - A successful Result
+        self.define_builtin(
+            "Ok",
+            vec![var("a")],
+            vec![result_of.clone()],
+            "Wrap a value as Ok",
+        );
+
+        // Err: ( e -> Result<a, e> ) - A failed Result
+        self.define_builtin(
+            "Err",
+            vec![var("e")],
+            vec![result_of],
+            "Wrap a value as Err",
+        );
     }
 
     /// Define system and meta-programming words
     fn define_system_words(&mut self) {
+        // print-string: ( String -> ) - Print a string without quotes
+        self.define_builtin(
+            "print-string",
+            vec![Type::String],
+            vec![],
+            "Print a string without quotes",
+        );
+
         // .: ( a -> ) - Print value
         self.define_builtin(
             ".",
@@ -378,10 +601,10 @@ impl CoreLibrary {
             "List all defined words in the dictionary",
         );
 
-        // see: ( Word -> ) - Show definition of word
+        // see: ( String -> ) - Show definition of the named word
         self.define_builtin(
             "see",
-            vec![Type::Variable("Word".to_string())],
+            vec![Type::String],
             vec![],
             "Show the definition of a word",
         );
@@ -417,6 +640,155 @@ impl CoreLibrary {
         self.define_builtin("help", vec![], vec![], "Show help information");
     }
 
+    /// Define the string words
+    fn define_string_words(&mut self) {
+        // concat: ( String String -> String ) - Join two strings
+        self.define_builtin(
+            "concat",
+            vec![Type::String, Type::String],
+            vec![Type::String],
+            "Join two strings",
+        );
+
+        // length: ( String -> Nat ) - Number of characters; type inference
+        // also accepts ( List<a> -> Nat )
+        self.define_builtin(
+            "length",
+            vec![Type::String],
+            vec![Type::Nat],
+            "Number of characters in a string or elements in a list",
+        );
+
+        // substring: ( String start end -> String ) - Characters from start up to end
+        self.define_builtin(
+            "substring",
+            vec![Type::String, Type::Nat, Type::Nat],
+            vec![Type::String],
+            "Characters from start up to end",
+        );
+
+        // split: ( String separator -> List<String> ) - Split on a separator
+        self.define_builtin(
+            "split",
+            vec![Type::String, Type::String],
+            vec![Type::List(Box::new(Type::String))],
+            "Split on a separator",
+        );
+
+        // join: ( List<String> separator -> String ) - Join strings with a separator
+        self.define_builtin(
+            "join",
+            vec![Type::List(Box::new(Type::String)), Type::String],
+            vec![Type::String],
+            "Join strings with a separator",
+        );
+
+        // to-upper: ( String -> String ) - Uppercase
+        self.define_builtin(
+            "to-upper",
+            vec![Type::String],
+            vec![Type::String],
+            "Uppercase",
+        );
+
+        // to-lower: ( String -> String ) - Lowercase
+        self.define_builtin(
+            "to-lower",
+            vec![Type::String],
+            vec![Type::String],
+            "Lowercase",
+        );
+
+        // trim: ( String -> String ) - Remove surrounding whitespace
+        self.define_builtin(
+            "trim",
+            vec![Type::String],
+            vec![Type::String],
+            "Remove surrounding whitespace",
+        );
+
+        // contains?: ( String needle -> Bool ) - Whether the string contains the needle
+        self.define_builtin(
+            "contains?",
+            vec![Type::String, Type::String],
+            vec![Type::Bool],
+            "Whether the string contains the needle",
+        );
+
+        // string->nat: ( String -> Option<Nat> ) - Parse a natural number
+        self.define_builtin(
+            "string->nat",
+            vec![Type::String],
+            vec![Type::Option(Box::new(Type::Nat))],
+            "Parse a natural number",
+        );
+
+        // nat->string: ( Nat -> String ) - Decimal digits of a number
+        self.define_builtin(
+            "nat->string",
+            vec![Type::Nat],
+            vec![Type::String],
+            "Decimal digits of a number",
+        );
+
+        // to-string: ( a -> String ) - The value as `.` prints it
+        self.define_builtin(
+            "to-string",
+            vec![Type::Variable("a".to_string())],
+            vec![Type::String],
+            "The value as `.` prints it",
+        );
+
+        // format: ( List template -> String ) - Fill {} placeholders from a list
+        self.define_builtin(
+            "format",
+            vec![
+                Type::List(Box::new(Type::Variable("a".to_string()))),
+                Type::String,
+            ],
+            vec![Type::String],
+            "Fill {} placeholders from a list",
+        );
+    }
+
+    /// Define the assertion words used by `test:` definitions
+    fn define_testing_words(&mut self) {
+        // assert: ( Bool -> ) - Fail unless true
+        self.define_builtin(
+            "assert",
+            vec![Type::Bool],
+            vec![],
+            "Fail unless the value is true",
+        );
+
+        // assert-eq: ( a a -> ) - Fail unless equal
+        self.define_builtin(
+            "assert-eq",
+            vec![
+                Type::Variable("a".to_string()),
+                Type::Variable("a".to_string()),
+            ],
+            vec![],
+            "Fail unless the actual value equals the expected value on top",
+        );
+
+        // assert-stack: ( Quote -> ) - Fail unless the stack matches
+        self.define_builtin(
+            "assert-stack",
+            vec![Type::Quote],
+            vec![],
+            "Fail unless the stack holds what the quote pushes",
+        );
+
+        // equiv?: ( Quote Quote -> Bool ) - Compare two quotes on random inputs
+        self.define_builtin(
+            "equiv?",
+            vec![Type::Quote, Type::Quote],
+            vec![Type::Bool],
+            "Whether two quotes leave the same values on random inputs",
+        );
+    }
+
     /// Helper to define a builtin word with signature
     fn define_builtin(&mut self, name: &str, inputs: Vec<Type>, outputs: Vec<Type>, _doc: &str) {
         let signature = TypeSignature { inputs, outputs };
@@ -524,10 +896,93 @@ impl CoreLibrary {
             "when".to_string(),
             "( Bool Quote -> ) Execute quote if true".to_string(),
         );
+        docs.insert(
+            "unless".to_string(),
+            "( Bool Quote -> ) Execute quote if false".to_string(),
+        );
         docs.insert(
             "times".to_string(),
             "( Nat Quote -> ) Execute quote n times".to_string(),
         );
+        docs.insert(
+            "call".to_string(),
+            "( Quote -> ) Execute the quote on top of the stack".to_string(),
+        );
+        docs.insert(
+            "while".to_string(),
+            "( Nat Quote Quote -> ) Run body while condition holds, at most fuel times".to_string(),
+        );
+
+        // Iteration
+        docs.insert(
+            "each".to_string(),
+            "( List<a> Quote -> ) Execute quote for each element".to_string(),
+        );
+        docs.insert(
+            "map".to_string(),
+            "( List<a> Quote -> List<b> ) Apply quote to each element".to_string(),
+        );
+        docs.insert(
+            "filter".to_string(),
+            "( List<a> Quote -> List<a> ) Keep elements where quote yields true".to_string(),
+        );
+        docs.insert(
+            "fold".to_string(),
+            "( List<a> b Quote -> b ) Left fold with initial accumulator".to_string(),
+        );
+        docs.insert(
+            "range".to_string(),
+            "( Nat Nat -> List<Nat> ) Naturals from start up to, not including, end".to_string(),
+        );
+
+        // Lists
+        docs.insert(
+            "cons".to_string(),
+            "( a List<a> -> List<a> ) Prepend a value to a list".to_string(),
+        );
+        docs.insert(
+            "head".to_string(),
+            "( List<a> -> a ) First element; fails on an empty list".to_string(),
+        );
+        docs.insert(
+            "head?".to_string(),
+            "( List<a> -> Option<a> ) First element, or None for an empty list".to_string(),
+        );
+        docs.insert(
+            "tail".to_string(),
+            "( List<a> -> List<a> ) All but the first element; fails on an empty list".to_string(),
+        );
+        docs.insert(
+            "tail?".to_string(),
+            "( List<a> -> Option<List<a>> ) All but the first element, or None for an empty list"
+                .to_string(),
+        );
+        docs.insert(
+            "nth".to_string(),
+            "( List<a> Nat -> a ) Element at a zero-based index; fails when out of bounds"
+                .to_string(),
+        );
+        docs.insert(
+            "nth?".to_string(),
+            "( List<a> Nat -> Option<a> ) Element at a zero-based index, or None when out of bounds".to_string(),
+        );
+        docs.insert(
+            "append".to_string(),
+            "( List<a> List<a> -> List<a> ) Concatenate two lists".to_string(),
+        );
+        docs.insert(
+            "reverse".to_string(),
+            "( List<a> -> List<a> ) Reverse a list".to_string(),
+        );
+        docs.insert(
+            "sort".to_string(),
+            "( List<a> -> List<a> ) Sort naturals, strings, booleans or ordinals in ascending order".to_string(),
+        );
+        docs.insert(
+            "zip".to_string(),
+            "( List<a> List<a> -> List<List<a>> ) Pair up elements, stopping at the shorter list"
+                .to_string(),
+        );
 
         // System
         docs.insert(
@@ -542,11 +997,126 @@ impl CoreLibrary {
             "words".to_string(),
             "( -> ) List all defined words".to_string(),
         );
+        docs.insert(
+            "see".to_string(),
+            "( Word -> ) Show the definition of a word".to_string(),
+        );
+        docs.insert(
+            "clear".to_string(),
+            "( ... -> ) Clear all values from the stack".to_string(),
+        );
+        docs.insert(
+            "depth".to_string(),
+            "( -> Nat ) Push the current stack depth".to_string(),
+        );
+        docs.insert(
+            "help".to_string(),
+            "( -> ) Show help information".to_string(),
+        );
         docs.insert(
             "--ordinal".to_string(),
             "( Quote -> Ordinal ) Calculate ordinal cost".to_string(),
         );
 
+        // Ordinals
+        docs.insert(
+            "omega".to_string(),
+            "( -> Ordinal ) The first infinite ordinal ω".to_string(),
+        );
+        docs.insert(
+            "ord".to_string(),
+            "( Nat -> Ordinal ) A natural number as a finite ordinal".to_string(),
+        );
+        docs.insert(
+            "ord+".to_string(),
+            "( Ordinal Ordinal -> Ordinal ) Ordinal sum, where 1 + ω = ω".to_string(),
+        );
+        docs.insert(
+            "ord*".to_string(),
+            "( Ordinal Ordinal -> Ordinal ) Ordinal product, where 2 * ω = ω".to_string(),
+        );
+        docs.insert(
+            "ord^".to_string(),
+            "( Ordinal Ordinal -> Ordinal ) Ordinal power, where 2 ^ ω = ω".to_string(),
+        );
+        docs.insert(
+            "ord<".to_string(),
+            "( Ordinal Ordinal -> Bool ) Ordinal less than".to_string(),
+        );
+
+        // Strings
+        docs.insert(
+            "concat".to_string(),
+            "( String String -> String ) Join two strings".to_string(),
+        );
+        docs.insert(
+            "length".to_string(),
+            "( String -> Nat ) Number of characters; also counts list elements".to_string(),
+        );
+        docs.insert(
+            "substring".to_string(),
+            "( String start end -> String ) Characters from start up to end".to_string(),
+        );
+        docs.insert(
+            "split".to_string(),
+            "( String separator -> List<String> ) Split on a separator; \"\" splits into characters".to_string(),
+        );
+        docs.insert(
+            "join".to_string(),
+            "( List<String> separator -> String ) Join strings with a separator".to_string(),
+        );
+        docs.insert(
+            "to-upper".to_string(),
+            "( String -> String ) Uppercase".to_string(),
+        );
+        docs.insert(
+            "to-lower".to_string(),
+            "( String -> String ) Lowercase".to_string(),
+        );
+        docs.insert(
+            "trim".to_string(),
+            "( String -> String ) Remove surrounding whitespace".to_string(),
+        );
+        docs.insert(
+            "contains?".to_string(),
+            "( String needle -> Bool ) Whether the string contains the needle".to_string(),
+        );
+        docs.insert(
+            "string->nat".to_string(),
+            "( String -> Option<Nat> ) Parse a natural number, or None".to_string(),
+        );
+        docs.insert(
+            "nat->string".to_string(),
+            "( Nat -> String ) Decimal digits of a number".to_string(),
+        );
+        docs.insert(
+            "to-string".to_string(),
+            "( a -> String ) The value as `.` prints it".to_string(),
+        );
+        docs.insert(
+            "format".to_string(),
+            "( List template -> String ) Fill each {} in the template with the next value"
+                .to_string(),
+        );
+
+        // Testing
+        docs.insert(
+            "assert".to_string(),
+            "( Bool -> ) Fail unless the value is true".to_string(),
+        );
+        docs.insert(
+            "assert-eq".to_string(),
+            "( actual expected -> ) Fail unless the two values are equal".to_string(),
+        );
+        docs.insert(
+            "assert-stack".to_string(),
+            "( ... Quote -> ... ) Fail unless the stack holds what the quote pushes".to_string(),
+        );
+        docs.insert(
+            "equiv?".to_string(),
+            "( Quote Quote -> Bool ) Whether both quotes leave the same values on random stacks of the first quote's input types".to_string(),
+        );
+
         docs
     }
 
@@ -578,14 +1148,71 @@ impl CoreLibrary {
         help.push_str("Control Flow:\n");
         help.push_str("  if      ( Bool Quote Quote -> ) Conditional\n");
         help.push_str("  when    ( Bool Quote -> )   Execute if true\n");
-        help.push_str("  times   ( Nat Quote -> )    Execute n times\n\n");
+        help.push_str("  unless  ( Bool Quote -> )   Execute if false\n");
+        help.push_str("  times   ( Nat Quote -> )    Execute n times\n");
+        help.push_str("  call    ( Quote -> )        Execute a quote\n");
+        help.push_str("  while   ( Nat Quote Quote -> ) Loop, bounded by fuel\n\n");
+
+        help.push_str("Iteration:\n");
+        help.push_str("  each    ( List Quote -> )   Run quote per element\n");
+        help.push_str("  map     ( List Quote -> List ) Transform elements\n");
+        help.push_str("  filter  ( List Quote -> List ) Keep matching elements\n");
+        help.push_str("  fold    ( List b Quote -> b ) Left fold\n");
+        help.push_str("  range   ( Nat Nat -> List ) Half-open range\n\n");
+
+        help.push_str("Lists:\n");
+        help.push_str("  cons    ( a List -> List ) Prepend a value\n");
+        help.push_str("  head    ( List -> a ) First element\n");
+        help.push_str("  tail    ( List -> List ) Drop the first element\n");
+        help.push_str("  nth     ( List Nat -> a ) Element at an index\n");
+        help.push_str("  append  ( List List -> List ) Concatenate\n");
+        help.push_str("  reverse ( List -> List ) Reverse\n");
+        help.push_str("  sort    ( List -> List ) Sort ascending\n");
+        help.push_str("  zip     ( List List -> List ) Pair up elements\n");
+        help.push_str("  length  ( List -> Nat ) Element count\n");
+        help.push_str("  head? tail? nth?            Return an Option instead of failing\n");
+        help.push_str("  #[1, 2, 3]                  List literal\n\n");
 
         help.push_str("System:\n");
         help.push_str("  .       ( a -> )            Print value\n");
         help.push_str("  .s      ( -> )              Print stack\n");
         help.push_str("  words   ( -> )              List words\n");
+        help.push_str("  see     ( Word -> )         Show a definition\n");
+        help.push_str("  clear   ( ... -> )          Clear the stack\n");
+        help.push_str("  depth   ( -> Nat )          Stack depth\n");
         help.push_str("  help    ( -> )              Show this help\n");
-        help.push_str("  --ordinal ( Quote -> Ordinal ) Ordinal cost\n");
+        help.push_str("  --ordinal ( Quote -> Ordinal ) Ordinal cost\n\n");
+
+        help.push_str("Ordinals:\n");
+        help.push_str("  omega   ( -> Ordinal )      The ordinal ω\n");
+        help.push_str("  ord     ( Nat -> Ordinal )  Finite ordinal\n");
+        help.push_str("  ord+    ( Ordinal Ordinal -> Ordinal ) Sum\n");
+        help.push_str("  ord*    ( Ordinal Ordinal -> Ordinal ) Product\n");
+        help.push_str("  ord^    ( Ordinal Ordinal -> Ordinal ) Power\n");
+        help.push_str("  ord<    ( Ordinal Ordinal -> Bool ) Less than\n\n");
+
+        help.push_str("Strings:\n");
+        help.push_str("  concat  ( String String -> String ) Join two strings\n");
+        help.push_str("  length  ( String -> Nat ) Character count\n");
+        help.push_str("  substring ( String Nat Nat -> String ) Slice start..end\n");
+        help.push_str("  split   ( String String -> List ) Split on a separator\n");
+        help.push_str("  join    ( List String -> String ) Join with a separator\n");
+        help.push_str("  to-upper ( String -> String ) Uppercase\n");
+        help.push_str("  to-lower ( String -> String ) Lowercase\n");
+        help.push_str("  trim    ( String -> String ) Strip whitespace\n");
+        help.push_str("  contains? ( String String -> Bool ) Substring test\n");
+        help.push_str("  string->nat ( String -> Option ) Parse a number\n");
+        help.push_str("  nat->string ( Nat -> String ) Print a number\n");
+        help.push_str("  to-string ( a -> String ) Any value as text\n");
+        help.push_str("  format  ( List String -> String ) Fill {} placeholders\n");
+        help.push_str("  print-string ( String -> ) Print without quotes\n\n");
+
+        help.push_str("Testing:\n");
+        help.push_str("  assert  ( Bool -> )         Fail unless true\n");
+        help.push_str("  assert-eq ( a a -> )        Fail unless equal\n");
+        help.push_str("  assert-stack ( Quote -> )   Fail unless the stack matches\n");
+        help.push_str("  equiv?  ( Quote Quote -> Bool ) Agree on random inputs\n");
+        help.push_str("  test: name ... ;            Define a test for `chronos test`\n");
 
         help
     }
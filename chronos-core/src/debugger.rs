@@ -0,0 +1,448 @@
+//! Step debugger for the virtual machine
+//!
+//! A [`Debugger`] attached to the [`VirtualMachine`](crate::VirtualMachine) is
+//! a [`VmObserver`] consulted, after any other observers, before every token
+//! the machine executes. That includes the bodies of user-defined words, quotes
+//! run by combinators such as `if` and `times`, and match arms. When it decides
+//! to pause, it hands a [`Pause`] to its [`DebugHandler`], which inspects the
+//! machine and chooses how to resume.
+//!
+//! Pauses are decided by call depth, the number of words currently executing:
+//! stepping over a word skips every token run at a deeper level, and finishing
+//! runs until the current word returns.
+
+use crate::observer::{ExecutionContext, VmObserver};
+use crate::types::{Token, Value};
+use crate::vm::VmError;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the debugger resumes after a pause
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugAction {
+    /// Pause at the next token, entering words and quotes
+    StepInto,
+    /// Pause at the next token at this call depth or shallower
+    StepOver,
+    /// Pause once the current word returns
+    Finish,
+    /// Run until a breakpoint is hit
+    Continue,
+    /// Stop execution with an error
+    Abort,
+}
+
+/// A comparison used by depth breakpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessEqual,
+    Equal,
+    GreaterEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, left: usize, right: usize) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::GreaterEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::GreaterEqual => ">=",
+            Comparison::Greater => ">",
+        }
+    }
+}
+
+/// A condition that pauses execution whenever it holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Pause before the named word is executed
+    Word(String),
+    /// Pause when the data stack depth satisfies the comparison
+    StackDepth(Comparison, usize),
+    /// Pause when the call depth satisfies the comparison
+    CallDepth(Comparison, usize),
+}
+
+impl Breakpoint {
+    /// Check whether the breakpoint holds before `token` runs
+    pub fn matches(&self, token: &Token, call_stack: &[String], stack: &[Value]) -> bool {
+        match self {
+            Breakpoint::Word(name) => matches!(token, Token::Word(word) if word == name),
+            Breakpoint::StackDepth(comparison, depth) => comparison.holds(stack.len(), *depth),
+            Breakpoint::CallDepth(comparison, depth) => comparison.holds(call_stack.len(), *depth),
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::Word(name) => write!(f, "{}", name),
+            Breakpoint::StackDepth(comparison, depth) => {
+                write!(f, "stack {} {}", comparison.symbol(), depth)
+            }
+            Breakpoint::CallDepth(comparison, depth) => {
+                write!(f, "calls {} {}", comparison.symbol(), depth)
+            }
+        }
+    }
+}
+
+impl FromStr for Breakpoint {
+    type Err = String;
+
+    /// Parse `word`, `stack > 3` or `calls >= 10`; spaces around the
+    /// comparison are optional
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+        for (kind, depth_breakpoint) in [
+            (
+                "stack",
+                Breakpoint::StackDepth as fn(Comparison, usize) -> Breakpoint,
+            ),
+            ("calls", Breakpoint::CallDepth),
+        ] {
+            let Some(condition) = spec.strip_prefix(kind) else {
+                continue;
+            };
+            let condition = condition.trim_start();
+            let Some((comparison, rest)) = [
+                ("<=", Comparison::LessEqual),
+                (">=", Comparison::GreaterEqual),
+                ("<", Comparison::Less),
+                (">", Comparison::Greater),
+                ("=", Comparison::Equal),
+            ]
+            .into_iter()
+            .find_map(|(symbol, comparison)| {
+                condition
+                    .strip_prefix(symbol)
+                    .map(|rest| (comparison, rest))
+            }) else {
+                continue;
+            };
+            return rest
+                .trim()
+                .parse()
+                .map(|depth| depth_breakpoint(comparison, depth))
+                .map_err(|_| format!("Invalid depth in breakpoint: {}", spec));
+        }
+
+        if spec.is_empty() || spec.contains(char::is_whitespace) {
+            Err(format!("Invalid breakpoint: '{}'", spec))
+        } else {
+            Ok(Breakpoint::Word(spec.to_string()))
+        }
+    }
+}
+
+/// Why execution paused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseReason {
+    /// A step, step-over or finish completed
+    Step,
+    /// A breakpoint held
+    Breakpoint(Breakpoint),
+}
+
+/// The machine state at a pause, just before `token` runs
+#[derive(Debug)]
+pub struct Pause<'a> {
+    /// The token about to be executed
+    pub token: &'a Token,
+    /// Words currently executing, outermost first
+    pub call_stack: &'a [String],
+    /// The data stack, bottom first
+    pub stack: &'a [Value],
+    /// Why execution paused
+    pub reason: PauseReason,
+}
+
+/// Decides how to resume each time the debugger pauses
+pub trait DebugHandler: Send {
+    /// Inspect a pause and choose how to resume
+    ///
+    /// Breakpoints may be added or removed before resuming.
+    fn pause(&mut self, pause: &Pause<'_>, breakpoints: &mut Vec<Breakpoint>) -> DebugAction;
+}
+
+/// When the debugger should next pause, regardless of breakpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepMode {
+    /// At the next token
+    Step,
+    /// At the next token with at most this call depth
+    Over(usize),
+    /// At the next token with less than this call depth
+    Finish(usize),
+    /// Only at breakpoints
+    Run,
+}
+
+/// A step debugger consulted by the VM before each token
+pub struct Debugger {
+    handler: Box<dyn DebugHandler>,
+    breakpoints: Vec<Breakpoint>,
+    mode: StepMode,
+}
+
+impl Debugger {
+    /// Create a debugger that pauses before the first token
+    pub fn new(handler: Box<dyn DebugHandler>) -> Self {
+        Self {
+            handler,
+            breakpoints: Vec::new(),
+            mode: StepMode::Step,
+        }
+    }
+
+    /// Create a debugger that only pauses at breakpoints
+    pub fn running(handler: Box<dyn DebugHandler>, breakpoints: Vec<Breakpoint>) -> Self {
+        Self {
+            handler,
+            breakpoints,
+            mode: StepMode::Run,
+        }
+    }
+
+    /// The active breakpoints
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Add a breakpoint
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+}
+
+/// The debugger watches for tokens, pausing before one when a step or a
+/// breakpoint calls for it
+impl VmObserver for Debugger {
+    fn token_enter(
+        &mut self,
+        token: &Token,
+        context: &ExecutionContext<'_>,
+    ) -> Result<(), VmError> {
+        let ExecutionContext { call_stack, stack } = *context;
+        let depth = call_stack.len();
+        let reason = match self
+            .breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.matches(token, call_stack, stack))
+        {
+            Some(breakpoint) => PauseReason::Breakpoint(breakpoint.clone()),
+            None => {
+                let stepped = match self.mode {
+                    StepMode::Step => true,
+                    StepMode::Over(level) => depth <= level,
+                    StepMode::Finish(level) => depth < level,
+                    StepMode::Run => false,
+                };
+                if !stepped {
+                    return Ok(());
+                }
+                PauseReason::Step
+            }
+        };
+
+        let pause = Pause {
+            token,
+            call_stack,
+            stack,
+            reason,
+        };
+        self.mode = match self.handler.pause(&pause, &mut self.breakpoints) {
+            DebugAction::StepInto => StepMode::Step,
+            DebugAction::StepOver => StepMode::Over(depth),
+            DebugAction::Finish => StepMode::Finish(depth),
+            DebugAction::Continue => StepMode::Run,
+            DebugAction::Abort => {
+                return Err(VmError::RuntimeError(
+                    "Execution aborted by the debugger".to_string(),
+                ))
+            }
+        };
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Debugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChronosCore;
+    use std::sync::{Arc, Mutex};
+
+    /// Records each pause and resumes with scripted actions, then continues
+    struct Script {
+        actions: Vec<DebugAction>,
+        pauses: Arc<Mutex<Vec<(String, usize)>>>,
+    }
+
+    impl DebugHandler for Script {
+        fn pause(&mut self, pause: &Pause<'_>, _: &mut Vec<Breakpoint>) -> DebugAction {
+            self.pauses
+                .lock()
+                .unwrap()
+                .push((pause.token.to_string(), pause.call_stack.len()));
+            if self.actions.is_empty() {
+                DebugAction::Continue
+            } else {
+                self.actions.remove(0)
+            }
+        }
+    }
+
+    fn debug(
+        definitions: &str,
+        code: &str,
+        actions: Vec<DebugAction>,
+        breakpoints: Option<Vec<Breakpoint>>,
+    ) -> (ChronosCore, Vec<(String, usize)>) {
+        let mut core = ChronosCore::new();
+        core.eval_source(definitions).unwrap();
+
+        let pauses = Arc::new(Mutex::new(Vec::new()));
+        let handler = Box::new(Script {
+            actions,
+            pauses: pauses.clone(),
+        });
+        core.set_debugger(Some(match breakpoints {
+            Some(breakpoints) => Debugger::running(handler, breakpoints),
+            None => Debugger::new(handler),
+        }));
+        let _ = core.eval_source(code);
+        core.set_debugger(None);
+
+        let pauses = pauses.lock().unwrap().clone();
+        (core, pauses)
+    }
+
+    fn step(token: &str, depth: usize) -> (String, usize) {
+        (token.to_string(), depth)
+    }
+
+    #[test]
+    fn test_step_into_and_over() {
+        use DebugAction::*;
+        let definitions = ": square dup * ; : quad square square ;";
+
+        let (core, pauses) = debug(definitions, "2 quad", vec![StepInto; 4], None);
+        assert_eq!(
+            pauses,
+            vec![
+                step("2", 0),
+                step("quad", 0),
+                step("square", 1),
+                step("dup", 2),
+                step("*", 2),
+            ]
+        );
+        assert_eq!(core.get_stack(), vec![Value::Nat(16)]);
+
+        let (_, pauses) = debug(definitions, "2 quad", vec![StepOver; 2], None);
+        assert_eq!(pauses, vec![step("2", 0), step("quad", 0)]);
+
+        let (_, pauses) = debug(definitions, "2 quad 1", vec![StepOver; 3], None);
+        assert_eq!(pauses, vec![step("2", 0), step("quad", 0), step("1", 0)]);
+    }
+
+    #[test]
+    fn test_finish_returns_to_caller() {
+        use DebugAction::*;
+        let definitions = ": square dup * ; : quad square square ;";
+
+        let (_, pauses) = debug(
+            definitions,
+            "2 quad",
+            vec![StepInto, StepInto, StepInto, Finish, Abort],
+            None,
+        );
+        assert_eq!(
+            pauses,
+            vec![
+                step("2", 0),
+                step("quad", 0),
+                step("square", 1),
+                step("dup", 2),
+                step("square", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_steps_into_quotes() {
+        use DebugAction::*;
+        let (_, pauses) = debug("", "true [ 1 ] [ 2 ] if", vec![StepInto; 5], None);
+        assert_eq!(pauses.last(), Some(&step("1", 1)));
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let definitions = ": square dup * ; : quad square square ;";
+
+        let (_, pauses) = debug(
+            definitions,
+            "2 quad",
+            vec![],
+            Some(vec![Breakpoint::Word("dup".into())]),
+        );
+        assert_eq!(pauses, vec![step("dup", 2), step("dup", 2)]);
+
+        let (_, pauses) = debug(
+            definitions,
+            "1 2 3 quad",
+            vec![],
+            Some(vec!["stack >= 3".parse().unwrap()]),
+        );
+        assert_eq!(pauses[0], step("quad", 0));
+    }
+
+    #[test]
+    fn test_abort_stops_execution() {
+        let (core, _) = debug("", "1 2 +", vec![DebugAction::Abort], None);
+        assert!(core.get_stack().is_empty());
+    }
+
+    #[test]
+    fn test_parse_breakpoints() {
+        assert_eq!("dup".parse(), Ok(Breakpoint::Word("dup".into())));
+        assert_eq!(
+            "stack>3".parse(),
+            Ok(Breakpoint::StackDepth(Comparison::Greater, 3))
+        );
+        assert_eq!(
+            "calls <= 10".parse(),
+            Ok(Breakpoint::CallDepth(Comparison::LessEqual, 10))
+        );
+        assert!("stack > many".parse::<Breakpoint>().is_err());
+        assert!("".parse::<Breakpoint>().is_err());
+        assert_eq!(
+            Breakpoint::StackDepth(Comparison::Equal, 2).to_string(),
+            "stack = 2"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! C∀O language runtime, covering lexical analysis, parsing, type checking,
 //! and runtime execution errors.
 
+use crate::types::{Span, Value};
 use std::fmt;
 
 /// Main error type for all Chronos Core operations
@@ -14,6 +15,7 @@ pub enum ChronosError {
         message: String,
         position: Option<usize>,
         source: Option<String>,
+        span: Option<Span>,
     },
 
     /// Parsing errors (syntax analysis)
@@ -22,6 +24,7 @@ pub enum ChronosError {
         token_position: Option<usize>,
         expected: Option<String>,
         found: Option<String>,
+        span: Option<Span>,
     },
 
     /// Type checking and inference errors
@@ -30,6 +33,7 @@ pub enum ChronosError {
         expected_type: Option<String>,
         found_type: Option<String>,
         word_name: Option<String>,
+        span: Option<Span>,
     },
 
     /// Runtime execution errors
@@ -37,6 +41,7 @@ pub enum ChronosError {
         message: String,
         word_name: Option<String>,
         stack_trace: Vec<String>,
+        span: Option<Span>,
     },
 
     /// Stack manipulation errors
@@ -71,6 +76,7 @@ pub enum ChronosError {
         message: String,
         operation: String,
         operands: Vec<String>,
+        span: Option<Span>,
     },
 
     /// Definition errors (invalid word definitions, circular dependencies)
@@ -79,6 +85,21 @@ pub enum ChronosError {
         word_name: String,
         context: Option<String>,
     },
+
+    /// Failed `assert`, `assert-eq` or `assert-stack` checks
+    AssertionError {
+        message: String,
+        expected: Vec<Value>,
+        actual: Vec<Value>,
+        span: Option<Span>,
+    },
+
+    /// Module loading errors (missing files, import cycles, failing modules)
+    ModuleError {
+        message: String,
+        path: String,
+        cause: Option<String>,
+    },
 }
 
 impl fmt::Display for ChronosError {
@@ -88,9 +109,12 @@ impl fmt::Display for ChronosError {
                 message,
                 position,
                 source,
+                span,
             } => {
                 write!(f, "Lexical error: {}", message)?;
-                if let Some(pos) = position {
+                if let Some(span) = span {
+                    write!(f, " at {}", span)?;
+                } else if let Some(pos) = position {
                     write!(f, " at position {}", pos)?;
                 }
                 if let Some(src) = source {
@@ -104,9 +128,12 @@ impl fmt::Display for ChronosError {
                 token_position,
                 expected,
                 found,
+                span,
             } => {
                 write!(f, "Parse error: {}", message)?;
-                if let Some(pos) = token_position {
+                if let Some(span) = span {
+                    write!(f, " at {}", span)?;
+                } else if let Some(pos) = token_position {
                     write!(f, " at token {}", pos)?;
                 }
                 if let Some(exp) = expected {
@@ -123,11 +150,15 @@ impl fmt::Display for ChronosError {
                 expected_type,
                 found_type,
                 word_name,
+                span,
             } => {
                 write!(f, "Type error: {}", message)?;
                 if let Some(word) = word_name {
                     write!(f, " in word '{}'", word)?;
                 }
+                if let Some(span) = span {
+                    write!(f, " at {}", span)?;
+                }
                 if let Some(exp) = expected_type {
                     write!(f, " (expected {})", exp)?;
                 }
@@ -141,11 +172,15 @@ impl fmt::Display for ChronosError {
                 message,
                 word_name,
                 stack_trace,
+                span,
             } => {
                 write!(f, "Runtime error: {}", message)?;
                 if let Some(word) = word_name {
                     write!(f, " in word '{}'", word)?;
                 }
+                if let Some(span) = span {
+                    write!(f, " at {}", span)?;
+                }
                 if !stack_trace.is_empty() {
                     write!(f, "\nStack trace:")?;
                     for (i, frame) in stack_trace.iter().enumerate() {
@@ -206,6 +241,7 @@ impl fmt::Display for ChronosError {
                 message,
                 operation,
                 operands,
+                span,
             } => {
                 write!(
                     f,
@@ -213,7 +249,11 @@ impl fmt::Display for ChronosError {
                     message,
                     operation,
                     operands.join(", ")
-                )
+                )?;
+                if let Some(span) = span {
+                    write!(f, " at {}", span)?;
+                }
+                Ok(())
             }
 
             ChronosError::DefinitionError {
@@ -227,6 +267,32 @@ impl fmt::Display for ChronosError {
                 }
                 Ok(())
             }
+
+            ChronosError::AssertionError {
+                message,
+                expected,
+                actual,
+                span,
+            } => {
+                write!(f, "Assertion failed: {}", message)?;
+                if let Some(span) = span {
+                    write!(f, " at {}", span)?;
+                }
+                write!(f, "\n  expected: {}", crate::vm::format_values(expected))?;
+                write!(f, "\n  actual:   {}", crate::vm::format_values(actual))
+            }
+
+            ChronosError::ModuleError {
+                message,
+                path,
+                cause,
+            } => {
+                write!(f, "Module error: {} in '{}'", message, path)?;
+                if let Some(cause) = cause {
+                    write!(f, "\n{}", cause)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -250,6 +316,7 @@ impl ChronosError {
             message: message.into(),
             position,
             source: None,
+            span: None,
         }
     }
 
@@ -264,6 +331,7 @@ impl ChronosError {
             token_position: None,
             expected,
             found,
+            span: None,
         }
     }
 
@@ -278,6 +346,7 @@ impl ChronosError {
             expected_type: expected,
             found_type: found,
             word_name: None,
+            span: None,
         }
     }
 
@@ -287,6 +356,7 @@ impl ChronosError {
             message: message.into(),
             word_name,
             stack_trace: Vec::new(),
+            span: None,
         }
     }
 
@@ -313,6 +383,15 @@ impl ChronosError {
         }
     }
 
+    /// Create a module error for the file at `path`
+    pub fn module_error(message: impl Into<String>, path: impl Into<String>) -> Self {
+        ChronosError::ModuleError {
+            message: message.into(),
+            path: path.into(),
+            cause: None,
+        }
+    }
+
     /// Create an arithmetic error
     pub fn arithmetic_error(
         message: impl Into<String>,
@@ -323,6 +402,7 @@ impl ChronosError {
             message: message.into(),
             operation: operation.into(),
             operands,
+            span: None,
         }
     }
 
@@ -359,6 +439,47 @@ impl ChronosError {
         self
     }
 
+    /// Attach the source span this error was reported at
+    ///
+    /// Only lexical, parse, type, runtime and arithmetic errors carry spans;
+    /// other errors are returned unchanged.
+    pub fn with_span(mut self, at: Span) -> Self {
+        match &mut self {
+            ChronosError::LexError { span, .. }
+            | ChronosError::ParseError { span, .. }
+            | ChronosError::TypeError { span, .. }
+            | ChronosError::RuntimeError { span, .. }
+            | ChronosError::ArithmeticError { span, .. }
+            | ChronosError::AssertionError { span, .. } => *span = Some(at),
+            _ => {}
+        }
+        self
+    }
+
+    /// The source span this error was reported at, if known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ChronosError::LexError { span, .. }
+            | ChronosError::ParseError { span, .. }
+            | ChronosError::TypeError { span, .. }
+            | ChronosError::RuntimeError { span, .. }
+            | ChronosError::ArithmeticError { span, .. }
+            | ChronosError::AssertionError { span, .. } => *span,
+            _ => None,
+        }
+    }
+
+    /// Render this error against the source it came from
+    ///
+    /// Errors with a span show the offending source line with a caret
+    /// underline beneath the spanned text.
+    pub fn render(&self, source: &str) -> String {
+        match self.span() {
+            Some(span) => format!("{}\n{}", self, span.underline(source)),
+            None => self.to_string(),
+        }
+    }
+
     /// Add a suggestion to undefined errors
     pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
         if let ChronosError::UndefinedError {
@@ -380,7 +501,9 @@ impl ChronosError {
             | ChronosError::UndefinedError { .. }
             | ChronosError::StackError { .. }
             | ChronosError::ArithmeticError { .. }
-            | ChronosError::DefinitionError { .. } => true,
+            | ChronosError::DefinitionError { .. }
+            | ChronosError::AssertionError { .. }
+            | ChronosError::ModuleError { .. } => true,
 
             ChronosError::RuntimeError { .. } | ChronosError::OrdinalError { .. } => true, // Usually recoverable
 
@@ -401,6 +524,8 @@ impl ChronosError {
             ChronosError::SystemError { .. } => "system",
             ChronosError::ArithmeticError { .. } => "arithmetic",
             ChronosError::DefinitionError { .. } => "definition",
+            ChronosError::AssertionError { .. } => "assertion",
+            ChronosError::ModuleError { .. } => "module",
         }
     }
 }
@@ -526,4 +651,21 @@ mod tests {
             panic!("Expected RuntimeError");
         }
     }
+
+    #[test]
+    fn test_error_render() {
+        let source = "1 2 +\n3 swap frob";
+        let error = ChronosError::runtime_error("Unknown word: frob", None)
+            .with_span(Span::at_offset(source, 13, 4));
+
+        assert_eq!(error.span().map(|s| (s.line, s.column)), Some((2, 8)));
+        assert_eq!(
+            error.render(source),
+            "Runtime error: Unknown word: frob at line 2, column 8\n  |\n2 | 3 swap frob\n  |        ^^^^"
+        );
+
+        // Errors without a span render as plain messages
+        let error = ChronosError::stack_error("Not enough values", "dup", 0, 1);
+        assert_eq!(error.render(source), error.to_string());
+    }
 }
@@ -3,7 +3,7 @@
 //! This module handles tokenization of C∀O source code, converting text into
 //! a sequence of tokens that can be parsed and executed.
 
-use crate::types::{Token, Value};
+use crate::types::{MatchArm, Pattern, Span, Spanned, SpannedToken, Token, Value};
 use std::fmt;
 
 /// Errors that can occur during lexical analysis
@@ -15,6 +15,10 @@ pub enum LexError {
     UnterminatedComment(usize),
     /// Invalid number format
     InvalidNumber(String, usize),
+    /// Malformed `match` expression or pattern
+    InvalidPattern(String, usize),
+    /// Malformed `#[...]` list literal
+    InvalidList(String, usize),
     /// Unexpected end of input
     UnexpectedEof,
 }
@@ -31,6 +35,12 @@ impl fmt::Display for LexError {
             LexError::InvalidNumber(s, pos) => {
                 write!(f, "Invalid number '{}' at position {}", s, pos)
             }
+            LexError::InvalidPattern(msg, pos) => {
+                write!(f, "Invalid match pattern at position {}: {}", pos, msg)
+            }
+            LexError::InvalidList(msg, pos) => {
+                write!(f, "Invalid list literal at position {}: {}", pos, msg)
+            }
             LexError::UnexpectedEof => {
                 write!(f, "Unexpected end of input")
             }
@@ -40,11 +50,45 @@ impl fmt::Display for LexError {
 
 impl std::error::Error for LexError {}
 
+impl LexError {
+    /// A description of the error without its position
+    pub fn message(&self) -> String {
+        match self {
+            LexError::InvalidCharacter(ch, _) => format!("Invalid character '{}'", ch),
+            LexError::UnterminatedComment(_) => "Unterminated comment".to_string(),
+            LexError::InvalidNumber(s, _) => format!("Invalid number '{}'", s),
+            LexError::InvalidPattern(msg, _) => format!("Invalid match pattern: {}", msg),
+            LexError::InvalidList(msg, _) => format!("Invalid list literal: {}", msg),
+            LexError::UnexpectedEof => "Unexpected end of input".to_string(),
+        }
+    }
+
+    /// The character offset the error was reported at, if known
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            LexError::InvalidCharacter(_, pos)
+            | LexError::UnterminatedComment(pos)
+            | LexError::InvalidNumber(_, pos)
+            | LexError::InvalidPattern(_, pos)
+            | LexError::InvalidList(_, pos) => Some(*pos),
+            LexError::UnexpectedEof => None,
+        }
+    }
+}
+
 /// The lexer for C∀O source code
 pub struct Lexer<'a> {
     input: &'a str,
     position: usize,
+    /// 1-based line of the current character
+    line: usize,
+    /// 1-based column of the current character
+    column: usize,
     current_char: Option<char>,
+    /// The previous token was `::`, so the next word names a signature
+    after_signature_marker: bool,
+    /// The previous tokens were `:: name`, so a `(` opens a type signature
+    after_signature_name: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -53,7 +97,11 @@ impl<'a> Lexer<'a> {
         let mut lexer = Lexer {
             input,
             position: 0,
+            line: 1,
+            column: 1,
             current_char: None,
+            after_signature_marker: false,
+            after_signature_name: false,
         };
         lexer.current_char = lexer.input.chars().next();
         lexer
@@ -61,6 +109,12 @@ impl<'a> Lexer<'a> {
 
     /// Advance to the next character
     fn advance(&mut self) {
+        if self.current_char == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
         self.position += 1;
         if self.position >= self.input.len() {
             self.current_char = None;
@@ -131,6 +185,12 @@ impl<'a> Lexer<'a> {
 
         match number_str.parse::<u64>() {
             Ok(n) => Ok(Token::Literal(Value::Nat(n))),
+            #[cfg(feature = "bigint")]
+            Err(_) => match number_str.parse::<num_bigint::BigUint>() {
+                Ok(n) => Ok(Token::Literal(Value::BigNat(n))),
+                Err(_) => Err(LexError::InvalidNumber(number_str, start_pos)),
+            },
+            #[cfg(not(feature = "bigint"))]
             Err(_) => Err(LexError::InvalidNumber(number_str, start_pos)),
         }
     }
@@ -191,7 +251,7 @@ impl<'a> Lexer<'a> {
         if let Some(ch) = self.current_char {
             if let Some(next_ch) = self.input.chars().nth(self.position + 1) {
                 match (ch, next_ch) {
-                    (':', ':') | ('<', '=') | ('>', '=') | ('<', '>') | ('-', '>') => {
+                    (':', ':') | ('=', '=') | ('<', '=') | ('>', '=') | ('<', '>') | ('-', '>') => {
                         word.push(ch);
                         word.push(next_ch);
                         self.advance();
@@ -208,6 +268,19 @@ impl<'a> Lexer<'a> {
             if ch.is_alphanumeric() || ch == '_' || ch == '-' || ch == '?' || ch == '!' {
                 word.push(ch);
                 self.advance();
+            } else if ch == '>' && word.ends_with('-') && self.continues_word(&word) {
+                // A conversion name such as `string->nat`
+                word.push(ch);
+                self.advance();
+            } else if ch == '.' && self.continues_word(&word) {
+                // A field accessor such as `Point.x`
+                word.push(ch);
+                self.advance();
+            } else if ch == ':' && self.is_namespace_separator(&word) {
+                // A qualified name such as `math::gcd`
+                word.push_str("::");
+                self.advance();
+                self.advance();
             } else if word.is_empty()
                 && ch.is_ascii_punctuation()
                 && ch != '['
@@ -223,6 +296,22 @@ impl<'a> Lexer<'a> {
             }
         }
 
+        // The ordinal words `ord+`, `ord*`, `ord<` and `ord^` end in an
+        // operator, and `test:` opens a test definition as `:` opens a word
+        // definition
+        let at_boundary = self
+            .peek_char()
+            .is_none_or(|next| next == ']' || next.is_whitespace());
+        if at_boundary {
+            match (word.as_str(), self.current_char) {
+                ("ord", Some(ch @ ('+' | '*' | '<' | '^'))) | ("test", Some(ch @ ':')) => {
+                    word.push(ch);
+                    self.advance();
+                }
+                _ => {}
+            }
+        }
+
         // Check for boolean literals
         match word.as_str() {
             "true" => Token::Literal(Value::Bool(true)),
@@ -231,6 +320,23 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Whether the operator character at the current position sits inside
+    /// an alphanumeric name, as the `>` in `string->nat` and the `.` in
+    /// `Point.x` do
+    fn continues_word(&self, word: &str) -> bool {
+        word.starts_with(char::is_alphanumeric)
+            && self.peek_char().is_some_and(char::is_alphanumeric)
+    }
+
+    /// Whether the `::` at the current position joins `word` to a name
+    /// from its module
+    fn is_namespace_separator(&self, word: &str) -> bool {
+        let mut rest = self.input.chars().skip(self.position + 1);
+        word.starts_with(char::is_alphanumeric)
+            && rest.next() == Some(':')
+            && rest.next().is_some_and(char::is_alphanumeric)
+    }
+
     /// Parse a string literal (for system messages, etc.)
     fn parse_string(&mut self) -> Result<Token, LexError> {
         self.advance(); // skip opening quote
@@ -269,8 +375,336 @@ impl<'a> Lexer<'a> {
         Err(LexError::UnexpectedEof)
     }
 
+    /// Look at the character after the current one without consuming anything
+    fn peek_char(&self) -> Option<char> {
+        self.input.chars().nth(self.position + 1)
+    }
+
+    /// Parse the remainder of a `match` expression after the `match` keyword
+    ///
+    /// ```text
+    /// match token | pattern -> body | pattern -> body
+    /// ```
+    ///
+    /// An arm body runs until the next `|`, a `;` closing the enclosing
+    /// definition, a `]` closing the enclosing quote, or the end of input.
+    /// Quotes inside the scrutinee and bodies are left as raw
+    /// `QuoteStart`/`QuoteEnd` tokens for the quote processing pass.
+    fn parse_match_expression(&mut self) -> Result<Token, LexError> {
+        let start_pos = self.position;
+
+        let value = match self.next_token()? {
+            Some(Token::QuoteStart) => Token::Literal(Value::Quote(self.parse_raw_quote()?)),
+            Some(Token::QuoteEnd) | None => {
+                return Err(LexError::InvalidPattern(
+                    "match needs a value to match on".to_string(),
+                    start_pos,
+                ))
+            }
+            Some(Token::Word(word)) if word == "|" => {
+                return Err(LexError::InvalidPattern(
+                    "match needs a value to match on".to_string(),
+                    start_pos,
+                ))
+            }
+            Some(token) => token,
+        };
+
+        let mut arms = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.current_char != Some('|') {
+                break;
+            }
+            self.advance(); // skip '|'
+
+            let pattern = self.parse_pattern()?;
+
+            self.skip_whitespace();
+            if self.current_char == Some('-') && self.peek_char() == Some('>') {
+                self.advance();
+                self.advance();
+            } else {
+                return Err(LexError::InvalidPattern(
+                    format!("expected '->' after pattern {}", pattern),
+                    self.position,
+                ));
+            }
+
+            let body = self.parse_match_arm_body()?;
+            arms.push(MatchArm { pattern, body });
+        }
+
+        if arms.is_empty() {
+            return Err(LexError::InvalidPattern(
+                "match expression has no arms".to_string(),
+                start_pos,
+            ));
+        }
+
+        Ok(Token::MatchExpression {
+            value: Box::new(value),
+            arms,
+        })
+    }
+
+    /// Collect raw tokens up to the `]` matching an already consumed `[`
+    fn parse_raw_quote(&mut self) -> Result<Vec<Token>, LexError> {
+        let mut tokens = Vec::new();
+        let mut depth = 0;
+
+        loop {
+            match self.next_token()? {
+                Some(Token::QuoteEnd) if depth == 0 => return Ok(tokens),
+                Some(Token::Comment(_)) => {}
+                Some(token) => {
+                    match token {
+                        Token::QuoteStart => depth += 1,
+                        Token::QuoteEnd => depth -= 1,
+                        _ => {}
+                    }
+                    tokens.push(token);
+                }
+                None => return Err(LexError::UnexpectedEof),
+            }
+        }
+    }
+
+    /// Collect the tokens of a match arm body
+    fn parse_match_arm_body(&mut self) -> Result<Vec<Token>, LexError> {
+        let mut body = Vec::new();
+        let mut depth = 0;
+
+        loop {
+            self.skip_whitespace();
+            if depth == 0 && matches!(self.current_char, Some('|') | Some(';') | Some(']')) {
+                return Ok(body);
+            }
+
+            match self.next_token()? {
+                Some(Token::Comment(_)) => {}
+                Some(token) => {
+                    match token {
+                        Token::QuoteStart => depth += 1,
+                        Token::QuoteEnd => depth -= 1,
+                        _ => {}
+                    }
+                    body.push(token);
+                }
+                None if depth == 0 => return Ok(body),
+                None => return Err(LexError::UnexpectedEof),
+            }
+        }
+    }
+
+    /// Parse a single pattern: `_`, `x`, `42`, `"text"`, `true`, `()`,
+    /// `None`, `Some(x)`, `Err(_)` or `[a, 0, _]`
+    fn parse_pattern(&mut self) -> Result<Pattern, LexError> {
+        self.skip_whitespace();
+        let start_pos = self.position;
+
+        match self.current_char {
+            Some('[') => {
+                self.advance();
+                let patterns = self.parse_pattern_list(']')?;
+                Ok(Pattern::List(patterns))
+            }
+            Some('(') if self.peek_char() == Some(')') => {
+                self.advance();
+                self.advance();
+                Ok(Pattern::Literal(Value::Unit))
+            }
+            Some('"') => match self.parse_string()? {
+                Token::Literal(value) => Ok(Pattern::Literal(value)),
+                _ => unreachable!("parse_string only produces literals"),
+            },
+            Some(ch) if ch.is_ascii_digit() => match self.parse_number()? {
+                Token::Literal(value) => Ok(Pattern::Literal(value)),
+                _ => unreachable!("parse_number only produces literals"),
+            },
+            Some(ch) if ch.is_alphabetic() || ch == '_' => {
+                let mut name = String::new();
+                while let Some(ch) = self.current_char {
+                    let is_arrow = ch == '-' && self.peek_char() == Some('>');
+                    if !is_arrow
+                        && (ch.is_alphanumeric()
+                            || ch == '_'
+                            || ch == '-'
+                            || ch == '?'
+                            || ch == '!')
+                    {
+                        name.push(ch);
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+
+                match name.as_str() {
+                    "_" => Ok(Pattern::Wildcard),
+                    "true" => Ok(Pattern::Literal(Value::Bool(true))),
+                    "false" => Ok(Pattern::Literal(Value::Bool(false))),
+                    _ if name.starts_with(|c: char| c.is_uppercase()) => {
+                        let args = if self.current_char == Some('(') {
+                            self.advance();
+                            self.parse_pattern_list(')')?
+                        } else {
+                            Vec::new()
+                        };
+                        Ok(Pattern::Constructor { name, args })
+                    }
+                    _ => Ok(Pattern::Variable(name)),
+                }
+            }
+            Some(ch) => Err(LexError::InvalidPattern(
+                format!("unexpected '{}'", ch),
+                start_pos,
+            )),
+            None => Err(LexError::UnexpectedEof),
+        }
+    }
+
+    /// Parse comma-separated patterns up to and including `close`
+    fn parse_pattern_list(&mut self, close: char) -> Result<Vec<Pattern>, LexError> {
+        let mut patterns = Vec::new();
+
+        self.skip_whitespace();
+        if self.current_char == Some(close) {
+            self.advance();
+            return Ok(patterns);
+        }
+
+        loop {
+            patterns.push(self.parse_pattern()?);
+            self.skip_whitespace();
+            match self.current_char {
+                Some(',') => self.advance(),
+                Some(ch) if ch == close => {
+                    self.advance();
+                    return Ok(patterns);
+                }
+                Some(ch) => {
+                    return Err(LexError::InvalidPattern(
+                        format!("expected ',' or '{}', found '{}'", close, ch),
+                        self.position,
+                    ))
+                }
+                None => return Err(LexError::UnexpectedEof),
+            }
+        }
+    }
+
+    /// Parse a list literal such as `#[1, 2, 3]` or `#[[true], [], #[false]]`
+    ///
+    /// The elements are literal values separated by commas, like the list
+    /// patterns of `match`; nested lists may leave out the `#`. All elements
+    /// must have the same type, as `List<a>` has a single element type.
+    fn parse_list_literal(&mut self) -> Result<Token, LexError> {
+        self.advance(); // skip '#'
+        self.advance(); // skip '['
+        Ok(Token::Literal(Value::List(self.parse_list_elements()?)))
+    }
+
+    /// Parse comma-separated literal values up to and including `]`
+    fn parse_list_elements(&mut self) -> Result<Vec<Value>, LexError> {
+        let start_pos = self.position;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.current_char == Some(']') {
+            self.advance();
+            return Ok(values);
+        }
+
+        loop {
+            values.push(self.parse_list_element()?);
+            self.skip_whitespace();
+            match self.current_char {
+                Some(',') => self.advance(),
+                Some(']') => {
+                    self.advance();
+                    if list_shape(&values).is_none() {
+                        return Err(LexError::InvalidList(
+                            "elements have different types".to_string(),
+                            start_pos,
+                        ));
+                    }
+                    return Ok(values);
+                }
+                Some(ch) => {
+                    return Err(LexError::InvalidList(
+                        format!("expected ',' or ']', found '{}'", ch),
+                        self.position,
+                    ))
+                }
+                None => return Err(LexError::UnexpectedEof),
+            }
+        }
+    }
+
+    /// Parse a single element of a list literal
+    fn parse_list_element(&mut self) -> Result<Value, LexError> {
+        self.skip_whitespace();
+        let start_pos = self.position;
+
+        let token = match self.current_char {
+            Some('#') if self.peek_char() == Some('[') => self.parse_list_literal()?,
+            Some('[') => {
+                self.advance();
+                Token::Literal(Value::List(self.parse_list_elements()?))
+            }
+            Some('(') if self.peek_char() == Some(')') => {
+                self.advance();
+                self.advance();
+                Token::Literal(Value::Unit)
+            }
+            Some('"') => self.parse_string()?,
+            Some(ch) if ch.is_ascii_digit() => self.parse_number()?,
+            Some(ch) if ch.is_alphabetic() => self.parse_word(),
+            Some(ch) => {
+                return Err(LexError::InvalidList(
+                    format!("unexpected '{}'", ch),
+                    start_pos,
+                ))
+            }
+            None => return Err(LexError::UnexpectedEof),
+        };
+
+        match token {
+            Token::Literal(value) => Ok(value),
+            other => Err(LexError::InvalidList(
+                format!("'{}' is not a literal value", other),
+                start_pos,
+            )),
+        }
+    }
+
     /// Get the next token from the input
     pub fn next_token(&mut self) -> Result<Option<Token>, LexError> {
+        let token = self.scan_token()?;
+
+        if !matches!(token, Some(Token::Comment(_))) {
+            let is_word = matches!(token, Some(Token::Word(_)));
+            self.after_signature_name = self.after_signature_marker && is_word;
+            self.after_signature_marker = token == Some(Token::Word("::".to_string()));
+        }
+
+        Ok(token)
+    }
+
+    /// Get the next token from the input together with its source span
+    pub fn next_spanned_token(&mut self) -> Result<Option<SpannedToken>, LexError> {
+        self.skip_whitespace();
+        let (offset, line, column) = (self.position, self.line, self.column);
+
+        let token = self.next_token()?;
+        let len = self.position - offset;
+        let span = Span::new(offset as u32, line as u32, column as u32, len as u32);
+        Ok(token.map(|token| Spanned::new(token, span)))
+    }
+
+    /// Scan the next token, without tracking signature context
+    fn scan_token(&mut self) -> Result<Option<Token>, LexError> {
         self.skip_whitespace();
 
         match self.current_char {
@@ -279,7 +713,8 @@ impl<'a> Lexer<'a> {
                 // Heuristic to detect comments vs type signatures:
                 // Comments typically contain prose and spaces
                 // Type signatures contain structured content like: ( Nat -> Nat )
-                if self.looks_like_comment() {
+                // and always follow `:: name`, even with type variables: ( a -> a a )
+                if !self.after_signature_name && self.looks_like_comment() {
                     let token = self.parse_comment()?;
                     Ok(Some(token))
                 } else {
@@ -306,6 +741,13 @@ impl<'a> Lexer<'a> {
             }
             Some(ch) if ch.is_alphabetic() || ch == '_' => {
                 let token = self.parse_word();
+                if token == Token::Word("match".to_string()) {
+                    return self.parse_match_expression().map(Some);
+                }
+                Ok(Some(token))
+            }
+            Some('#') if self.peek_char() == Some('[') => {
+                let token = self.parse_list_literal()?;
                 Ok(Some(token))
             }
             Some(ch) if ch.is_ascii_punctuation() && ch != '[' && ch != ']' && ch != '"' => {
@@ -318,11 +760,17 @@ impl<'a> Lexer<'a> {
 
     /// Tokenize the entire input string
     pub fn tokenize(&mut self) -> Result<Vec<Token>, LexError> {
+        let tokens = self.tokenize_spanned()?;
+        Ok(tokens.into_iter().map(|token| token.node).collect())
+    }
+
+    /// Tokenize the entire input string, keeping the span of every token
+    pub fn tokenize_spanned(&mut self) -> Result<Vec<SpannedToken>, LexError> {
         let mut tokens = Vec::new();
 
-        while let Some(token) = self.next_token()? {
+        while let Some(token) = self.next_spanned_token()? {
             // Skip comments for now (they could be preserved for documentation)
-            if !matches!(token, Token::Comment(_)) {
+            if !matches!(token.node, Token::Comment(_)) {
                 tokens.push(token);
             }
         }
@@ -331,6 +779,50 @@ impl<'a> Lexer<'a> {
     }
 }
 
+/// The type of a literal value, as far as list literals need it
+#[derive(PartialEq)]
+enum Shape {
+    Scalar(std::mem::Discriminant<Value>),
+    /// A list, with no element shape while it is empty
+    List(Option<Box<Shape>>),
+}
+
+impl Shape {
+    fn of(value: &Value) -> Option<Shape> {
+        match value {
+            Value::List(values) => Some(Shape::List(list_shape(values)?.map(Box::new))),
+            value => Some(Shape::Scalar(std::mem::discriminant(value))),
+        }
+    }
+
+    /// The shape both values have, if any; an empty list fits any list
+    fn join(self, other: Shape) -> Option<Shape> {
+        match (self, other) {
+            (Shape::List(None), list @ Shape::List(_))
+            | (list @ Shape::List(_), Shape::List(None)) => Some(list),
+            (Shape::List(Some(a)), Shape::List(Some(b))) => {
+                Some(Shape::List(Some(Box::new(a.join(*b)?))))
+            }
+            (a, b) if a == b => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// The common shape of the elements of a list, `Some(None)` for an empty
+/// list and `None` when the elements disagree
+fn list_shape(values: &[Value]) -> Option<Option<Shape>> {
+    let mut shape: Option<Shape> = None;
+    for value in values {
+        let element = Shape::of(value)?;
+        shape = Some(match shape {
+            None => element,
+            Some(shape) => shape.join(element)?,
+        });
+    }
+    Some(shape)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -391,4 +883,236 @@ mod tests {
             panic!("Expected comment token");
         }
     }
+
+    #[test]
+    fn test_match_expression() {
+        let mut lexer = Lexer::new("match value | Some(x) -> x 1 + | None -> 0");
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens.len(), 1);
+        let Token::MatchExpression { value, arms } = &tokens[0] else {
+            panic!("Expected match expression");
+        };
+        assert_eq!(**value, Token::Word("value".to_string()));
+        assert_eq!(arms.len(), 2);
+        assert_eq!(
+            arms[0].pattern,
+            Pattern::Constructor {
+                name: "Some".to_string(),
+                args: vec![Pattern::Variable("x".to_string())],
+            }
+        );
+        assert_eq!(
+            arms[0].body,
+            vec![
+                Token::Word("x".to_string()),
+                Token::Literal(Value::Nat(1)),
+                Token::Word("+".to_string()),
+            ]
+        );
+        assert_eq!(
+            arms[1].pattern,
+            Pattern::Constructor {
+                name: "None".to_string(),
+                args: vec![],
+            }
+        );
+        assert_eq!(arms[1].body, vec![Token::Literal(Value::Nat(0))]);
+    }
+
+    #[test]
+    fn test_match_patterns() {
+        let mut lexer =
+            Lexer::new("match [ ] | [a, 0, _] -> a | Err(\"bad\") -> 1 | true -> 2 | _ -> 3 ; 4");
+        let tokens = lexer.tokenize().unwrap();
+
+        // The match stops at ';', which is left for the enclosing definition
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[1], Token::Word(";".to_string()));
+
+        let Token::MatchExpression { value, arms } = &tokens[0] else {
+            panic!("Expected match expression");
+        };
+        assert_eq!(**value, Token::Literal(Value::Quote(vec![])));
+        let patterns: Vec<String> = arms.iter().map(|arm| arm.pattern.to_string()).collect();
+        assert_eq!(patterns, vec!["[a, 0, _]", "Err(\"bad\")", "true", "_"]);
+    }
+
+    #[test]
+    fn test_malformed_match() {
+        assert!(Lexer::new("match x | Some(y) y").tokenize().is_err());
+        assert!(Lexer::new("match x").tokenize().is_err());
+        assert!(Lexer::new("match x | [a b] -> a").tokenize().is_err());
+    }
+
+    #[test]
+    fn test_polymorphic_signature() {
+        let mut lexer = Lexer::new(":: dup2 ( a -> a a ) ; ( a -> a a )");
+        let tokens = lexer.tokenize().unwrap();
+
+        // The declared signature is kept, the trailing stack comment is not
+        let words: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(
+            words,
+            vec!["::", "dup2", "(", "a", "->", "a", "a", ")", ";"]
+        );
+    }
+
+    #[test]
+    fn test_operator_suffixed_words() {
+        let mut lexer = Lexer::new("omega 2 ord ord* [ord<] List<Nat> x+y");
+        let tokens = lexer.tokenize().unwrap();
+
+        let words: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(
+            words,
+            vec![
+                "omega", "2", "ord", "ord*", "[", "ord<", "]", "List", "<", "Nat", ">", "x", "+",
+                "y"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_qualified_names() {
+        let mut lexer = Lexer::new(":: math::gcd ( Nat Nat -> Nat ) ; a:: ::b math::");
+        let tokens = lexer.tokenize().unwrap();
+
+        let words: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(
+            words,
+            vec![
+                "::",
+                "math::gcd",
+                "(",
+                "Nat",
+                "Nat",
+                "->",
+                "Nat",
+                ")",
+                ";",
+                "a",
+                "::",
+                "::",
+                "b",
+                "math",
+                "::"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conversion_names() {
+        let mut lexer = Lexer::new("\"42\" string->nat x-> y a- >b");
+        let tokens = lexer.tokenize().unwrap();
+
+        let words: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(
+            words,
+            vec!["42", "string->nat", "x-", ">", "y", "a-", ">", "b"]
+        );
+    }
+
+    #[test]
+    fn test_field_accessors() {
+        let mut lexer = Lexer::new("Point.x Point.x! Point? 3 .s x. y");
+        let tokens = lexer.tokenize().unwrap();
+
+        let words: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(
+            words,
+            vec!["Point.x", "Point.x!", "Point?", "3", ".", "s", "x", ".", "y"]
+        );
+    }
+
+    #[test]
+    fn test_list_literals() {
+        let mut lexer = Lexer::new("#[1, 2, 3] #[] #[[true], [], #[false]] [ 1 2 ]");
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(
+            tokens[0],
+            Token::Literal(Value::List(vec![
+                Value::Nat(1),
+                Value::Nat(2),
+                Value::Nat(3)
+            ]))
+        );
+        assert_eq!(tokens[1], Token::Literal(Value::List(vec![])));
+        assert_eq!(
+            tokens[2],
+            Token::Literal(Value::List(vec![
+                Value::List(vec![Value::Bool(true)]),
+                Value::List(vec![]),
+                Value::List(vec![Value::Bool(false)]),
+            ]))
+        );
+        assert_eq!(tokens[3], Token::QuoteStart);
+        assert_eq!(tokens[2].to_string(), "#[[true], [], [false]]");
+
+        // A list has one element type, at every level of nesting
+        for source in [
+            "#[1 2]",
+            "#[dup]",
+            "#[1,",
+            "#[,]",
+            "#[1, true]",
+            "#[[1], [], [\"a\"]]",
+        ] {
+            assert!(Lexer::new(source).tokenize().is_err(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_test_definitions() {
+        let mut lexer = Lexer::new("test: adds 1 2 + 3 assert-eq ; test :");
+        let tokens = lexer.tokenize().unwrap();
+
+        let words: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+        assert_eq!(
+            words,
+            vec![
+                "test:",
+                "adds",
+                "1",
+                "2",
+                "+",
+                "3",
+                "assert-eq",
+                ";",
+                "test",
+                ":"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_operator_suffix_only_for_ordinal_words() {
+        let words = |source: &str| -> Vec<String> {
+            let tokens = Lexer::new(source).tokenize().unwrap();
+            tokens.iter().map(|t| t.to_string()).collect()
+        };
+
+        assert_eq!(words("ord+ ord^"), vec!["ord+", "ord^"]);
+        assert_eq!(words("n+ x* a< b^ 1+"), words("n + x * a < b ^ 1 +"));
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let mut lexer = Lexer::new("1 2 +\n  \"hi\" ( a comment ) dup");
+        let tokens = lexer.tokenize_spanned().unwrap();
+
+        let spans: Vec<(u32, u32, u32)> = tokens
+            .iter()
+            .map(|t| (t.span.line, t.span.column, t.span.len))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![(1, 1, 1), (1, 3, 1), (1, 5, 1), (2, 3, 4), (2, 22, 3)]
+        );
+        assert_eq!(tokens[4].node, Token::Word("dup".to_string()));
+
+        let error = Lexer::new("1 2\n  €").tokenize().unwrap_err();
+        assert_eq!(error.position(), Some(6));
+    }
 }
@@ -23,6 +23,7 @@
 //! - **Lexer**: Tokenizes C∀O source code
 //! - **Parser**: Generates AST from token streams
 //! - **Type System**: Categorical type checking and inference
+//! - **Ordinal Verifier**: Termination checking for word definitions
 //! - **Virtual Machine**: Stack-based execution engine
 //! - **Core Library**: Built-in operations and standard library
 //!
@@ -36,24 +37,78 @@
 
 // Re-export core types for public API
 pub use types::{
-    MatchArm, OrdinalValue, Pattern, Token, Type, TypeDefinition, TypeSignature, Value,
-    WordDefinition,
+    DataConstructor, DataDefinition, MatchArm, OrdinalValue, Pattern, Span, Spanned, SpannedToken,
+    Token, Type, TypeDefinition, TypeSignature, Value, WordDefinition,
 };
 
+pub use axiom::AxiomRegistry;
 pub use core_lib::CoreLibrary;
+pub use debugger::{
+    Breakpoint, Comparison, DebugAction, DebugHandler, Debugger, Pause, PauseReason,
+};
 pub use error::{ChronosError, ErrorContext, Result};
 pub use lexer::Lexer;
-pub use vm::VirtualMachine;
+pub use observer::{ExecutionContext, ObserverId, VmObserver};
+pub use ordinal::{
+    CallCheck, OrdinalError, OrdinalVerifier, RecursiveComponent, TerminationCertificate,
+};
+pub use parser::{ParseError, Parser, Statement};
+pub use property::{CheckFailure, CheckReport, PropertyResult};
+pub use testing::{TestCase, TestResult};
+pub use theorem::{Counterexample, Proof, ProofStep, Prover, Theorem};
+pub use type_inference::{InferenceError, MissingArms, TypeInferer, Verification};
+pub use vm::{VirtualMachine, VmError};
+
+use lexer::LexError;
+use module::{LoadKind, ModuleHeader, ModuleLoader};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 // Internal modules
+mod arithmetic;
+pub mod axiom;
 mod core_lib;
+pub mod debugger;
 mod error;
 pub mod lexer;
+pub mod module;
+pub mod observer;
+pub mod ordinal;
+pub mod parser;
+pub mod property;
+pub mod testing;
+pub mod theorem;
+pub mod type_inference;
 mod types;
 mod vm;
-// mod parser; // Temporarily disabled until type_inference is available
 
 
+/// A compiled C∀O program
+///
+/// Produced by [`ChronosCore::compile`]: the statements have been parsed,
+/// their word definitions carry checked signatures and ordinal costs, and
+/// every definition has passed strict termination verification.
+#[derive(Debug, Clone, Default)]
+pub struct Program {
+    /// The program's statements in source order
+    pub statements: Vec<Statement>,
+    /// The source span of each statement, parallel to `statements`
+    pub spans: Vec<Span>,
+    /// The source span of each token of an `Expression` statement, parallel
+    /// to `statements` and empty for other statements
+    pub token_spans: Vec<Vec<Span>>,
+    /// Undeclared words whose stack effect could not be inferred; they are
+    /// defined without a signature
+    pub untyped_words: BTreeSet<String>,
+    /// Words whose `::` signature could not be checked against the body,
+    /// with the reason; the signature is assumed instead
+    pub assumed_signatures: BTreeMap<String, String>,
+    /// The termination certificate of each word definition, in source order
+    pub certificates: Vec<TerminationCertificate>,
+}
+
 /// Main interface for the Chronos Core language runtime
 ///
 /// This struct provides a high-level API for evaluating C∀O code,
@@ -61,6 +116,7 @@ mod vm;
 pub struct ChronosCore {
     vm: VirtualMachine,
     core_lib: CoreLibrary,
+    modules: ModuleLoader,
 }
 
 impl ChronosCore {
@@ -74,13 +130,18 @@ impl ChronosCore {
 
         // Load core library into VM - VM already loads its own core library
 
-        Self { vm, core_lib }
+        Self {
+            vm,
+            core_lib,
+            modules: ModuleLoader::new(),
+        }
     }
 
     /// Evaluate a string of C∀O code and return the top stack value
     ///
     /// This is the main evaluation function that handles the complete
-    /// pipeline from source code to result.
+    /// pipeline from source code to result: the source is compiled with
+    /// [`ChronosCore::compile`] and then run.
     ///
     /// # Examples
     ///
@@ -92,8 +153,7 @@ impl ChronosCore {
     /// assert_eq!(core.pop().unwrap(), Value::Nat(7));
     /// ```
     pub fn eval(&mut self, source: &str) -> Result<Value> {
-        let tokens = self.tokenize(source)?;
-        self.execute_tokens(&tokens)?;
+        self.eval_source(source)?;
 
         // TODO: No longer popping a value from the stack on eval
         // that should only be explicitly happening with a pop or "." command
@@ -105,130 +165,382 @@ impl ChronosCore {
         
     }
 
-    /// Tokenize source code into a token stream
-    pub fn tokenize(&self, source: &str) -> Result<Vec<Token>> {
-        let mut lexer = Lexer::new(source);
-        lexer
-            .tokenize()
-            .map_err(|e| ChronosError::lex_error(e.to_string(), None))
-    }
-
-    /// Parse source code into tokens with quote processing
-    pub fn parse(&self, source: &str) -> Result<Vec<Token>> {
-        let tokens = self.tokenize(source)?;
-        self.process_quotes(tokens)
+    /// Compile source code into a checked program without running it
+    ///
+    /// Compilation parses the source into statements, checks every word
+    /// definition against its `::` declaration (or infers a signature when
+    /// there is none), and verifies that each definition terminates. Words
+    /// already defined in this runtime are visible to the checks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronos_core::ChronosCore;
+    ///
+    /// let core = ChronosCore::new();
+    /// assert!(core.compile(":: square ( Nat -> Nat ) ; : square dup * ;").is_ok());
+    /// assert!(core.compile(":: broken ( Nat -> Bool ) ; : broken 1 + ;").is_err());
+    /// ```
+    pub fn compile(&self, source: &str) -> Result<Program> {
+        let tokens = self.tokenize_spanned(source)?;
+        self.compile_tokens(source, tokens)
     }
 
-    /// Process quote tokens and convert them to Quote values
-    fn process_quotes(&self, tokens: Vec<Token>) -> Result<Vec<Token>> {
-        let mut result = Vec::new();
-        let mut i = 0;
-
-        while i < tokens.len() {
-            match &tokens[i] {
-                Token::QuoteStart => {
-                    // Find matching QuoteEnd
-                    let quote_tokens = self.extract_quote(&tokens, i)?;
-                    let quote_end_index = self.find_quote_end(&tokens, i)?;
-
-                    // Create a Quote value from the tokens inside the quotes
-                    result.push(Token::Literal(Value::Quote(quote_tokens)));
+    /// Compile the tokens lexed from `source`; see [`ChronosCore::compile`]
+    fn compile_tokens(&self, source: &str, tokens: Vec<SpannedToken>) -> Result<Program> {
+        let mut parser = Parser::from_tokens(tokens);
+        let (statements, token_spans): (Vec<_>, Vec<_>) = parser
+            .parse_all_with_token_spans()
+            .map_err(|e| parser_error(source, &parser, e))?
+            .into_iter()
+            .unzip();
+        let (mut statements, spans): (Vec<_>, Vec<_>) = statements
+            .into_iter()
+            .map(|statement| (statement.node, statement.span))
+            .unzip();
+
+        // Only programs that define words need an inferer
+        let mut inferer = None;
+        let mut declared: HashMap<String, TypeSignature> = HashMap::new();
+        let mut assumed_signatures = BTreeMap::new();
+        let mut untyped_words = BTreeSet::new();
+        let mut certificates = Vec::new();
+        let mut verifier = OrdinalVerifier::strict()
+            .with_definitions(self.vm.get_all_word_definitions().values());
+
+        for (statement, span) in statements.iter_mut().zip(&spans) {
+            match statement {
+                Statement::TypeSignatureDecl { name, signature } => {
+                    inferer
+                        .get_or_insert_with(|| self.vm.signature_inferer())
+                        .add_word_signature(name.clone(), signature.clone());
+                    declared.insert(name.clone(), signature.clone());
+                }
+                Statement::WordDefinition(word_def) => {
+                    let inferer = inferer.get_or_insert_with(|| self.vm.signature_inferer());
+                    if let Some(missing) = inferer.missing_match_arms(&word_def.body) {
+                        return Err(
+                            vm_error(vm::match_arms_error(&word_def.name, missing)).with_span(*span)
+                        );
+                    }
+                    let declaration = declared
+                        .remove(&word_def.name)
+                        .or_else(|| self.vm.declared_signature(&word_def.name).cloned());
+
+                    assumed_signatures.remove(&word_def.name);
+                    untyped_words.remove(&word_def.name);
+                    word_def.signature = match declaration {
+                        Some(signature) => {
+                            let verification = inferer
+                                .check_definition(&word_def.name, &signature, &word_def.body)
+                                .map_err(|e| {
+                                    vm_error(vm::definition_type_error(
+                                        &word_def.name,
+                                        &signature,
+                                        e,
+                                    ))
+                                    .with_span(*span)
+                                })?;
+                            if let Verification::Assumed(reason) = verification {
+                                assumed_signatures.insert(word_def.name.clone(), reason);
+                            }
+                            signature
+                        }
+                        None => match inferer.infer_word_type(&word_def.body) {
+                            Ok(signature) => signature,
+                            Err(_) => {
+                                untyped_words.insert(word_def.name.clone());
+                                TypeSignature {
+                                    inputs: vec![],
+                                    outputs: vec![],
+                                }
+                            }
+                        },
+                    };
+                    if untyped_words.contains(&word_def.name) {
+                        inferer.remove_word_signature(&word_def.name);
+                    } else {
+                        inferer
+                            .add_word_signature(word_def.name.clone(), word_def.signature.clone());
+                    }
 
-                    // Skip to after the QuoteEnd
-                    i = quote_end_index + 1;
+                    // Later definitions are analysed together with this one
+                    let certificate = verifier.certify(word_def);
+                    if !certificate.is_accepted() {
+                        return Err(ChronosError::OrdinalError {
+                            message: OrdinalError::NonTerminating(certificate.to_string())
+                                .to_string(),
+                            word_name: Some(word_def.name.clone()),
+                            ordinal_context: None,
+                        }
+                        .with_span(*span));
+                    }
+                    certificates.push(certificate);
+                    word_def.ordinal_cost = self.vm.ordinal_cost(&word_def.body);
+                    verifier.add_definition(word_def.clone());
                 }
-                _ => {
-                    result.push(tokens[i].clone());
-                    i += 1;
+                Statement::AxiomDeclaration { name, signature } => {
+                    // `axiom name ;` assumes the signature declared with `::`
+                    if signature.inputs.is_empty() && signature.outputs.is_empty() {
+                        if let Some(declaration) = declared
+                            .remove(name)
+                            .or_else(|| self.vm.declared_signature(name).cloned())
+                        {
+                            *signature = declaration;
+                        }
+                    }
+                    inferer
+                        .get_or_insert_with(|| self.vm.signature_inferer())
+                        .add_word_signature(name.clone(), signature.clone());
+                }
+                Statement::TypeDefinition(type_def) => {
+                    let inferer = inferer.get_or_insert_with(|| self.vm.signature_inferer());
+                    for (name, signature) in type_def.word_signatures() {
+                        inferer.add_word_signature(name, signature);
+                    }
                 }
+                Statement::DataDefinition(data_def) => inferer
+                    .get_or_insert_with(|| self.vm.signature_inferer())
+                    .add_data_type(data_def.clone()),
+                Statement::Theorem(_) | Statement::Expression(_) => {}
             }
         }
 
-        Ok(result)
+        Ok(Program {
+            statements,
+            spans,
+            token_spans,
+            untyped_words,
+            assumed_signatures,
+            certificates,
+        })
     }
 
-    /// Extract tokens between QuoteStart and QuoteEnd
-    fn extract_quote(&self, tokens: &[Token], start: usize) -> Result<Vec<Token>> {
-        let mut quote_tokens = Vec::new();
-        let mut depth = 0;
-        let mut i = start;
-
-        while i < tokens.len() {
-            match &tokens[i] {
-                Token::QuoteStart => {
-                    depth += 1;
-                    if depth > 1 {
-                        // Nested quote - include the QuoteStart token
-                        quote_tokens.push(tokens[i].clone());
+    /// Run a compiled program against this runtime's stack and dictionary
+    pub fn run(&mut self, program: &Program) -> Result<()> {
+        for (i, statement) in program.statements.iter().enumerate() {
+            match statement {
+                Statement::Expression(tokens) => {
+                    // Run token by token so an error points at its token
+                    let token_spans = program.token_spans.get(i).map_or(&[][..], Vec::as_slice);
+                    for (j, token) in tokens.iter().enumerate() {
+                        self.vm
+                            .execute_tokens(std::slice::from_ref(token))
+                            .map_err(|e| {
+                                let error = vm_error(e);
+                                match token_spans.get(j).or(program.spans.get(i)) {
+                                    Some(span) => error.with_span(*span),
+                                    None => error,
+                                }
+                            })?;
                     }
                 }
-                Token::QuoteEnd => {
-                    depth -= 1;
-                    if depth == 0 {
-                        // Found matching end quote
-                        break;
+                Statement::WordDefinition(word_def) => {
+                    if program.untyped_words.contains(&word_def.name) {
+                        self.vm.define_untyped_word(word_def.clone());
                     } else {
-                        // Nested quote - include the QuoteEnd token
-                        quote_tokens.push(tokens[i].clone());
+                        self.vm.define_word(word_def.clone());
                     }
-                }
-                _ => {
-                    if depth > 0 {
-                        quote_tokens.push(tokens[i].clone());
+                    if let Some(reason) = program.assumed_signatures.get(&word_def.name) {
+                        self.vm.assume_signature(word_def.name.clone(), reason.clone());
                     }
                 }
+                Statement::TypeSignatureDecl { name, signature } => {
+                    self.vm.declare_signature(name.clone(), signature.clone())
+                }
+                Statement::AxiomDeclaration { name, signature } => {
+                    self.vm.define_axiom(name.clone(), signature.clone())
+                }
+                Statement::TypeDefinition(type_def) => {
+                    self.vm.define_type(type_def.clone()).map_err(vm_error)?
+                }
+                Statement::DataDefinition(data_def) => {
+                    self.vm.define_data(data_def.clone()).map_err(vm_error)?
+                }
+                Statement::Theorem(theorem) => {
+                    self.vm.add_theorem(theorem.clone()).map_err(|e| {
+                        let error = vm_error(e);
+                        match program.spans.get(i) {
+                            Some(span) => error.with_span(*span),
+                            None => error,
+                        }
+                    })?
+                }
             }
-            i += 1;
         }
+        Ok(())
+    }
 
-        if depth > 0 {
-            return Err(ChronosError::parse_error("Unclosed quote", None, None));
+    /// Tokenize source code into a token stream
+    pub fn tokenize(&self, source: &str) -> Result<Vec<Token>> {
+        let tokens = self.tokenize_spanned(source)?;
+        Ok(tokens.into_iter().map(|token| token.node).collect())
+    }
+
+    /// Tokenize source code, keeping the source span of every token
+    pub fn tokenize_spanned(&self, source: &str) -> Result<Vec<SpannedToken>> {
+        let mut lexer = Lexer::new(source);
+        lexer.tokenize_spanned().map_err(|e| lex_error(source, &e))
+    }
+
+    /// Parse source code into tokens with quote processing
+    pub fn parse(&self, source: &str) -> Result<Vec<Token>> {
+        let tokens = self.parse_spanned(source)?;
+        Ok(tokens.into_iter().map(|token| token.node).collect())
+    }
+
+    /// Parse source code into spanned tokens with quote processing
+    pub fn parse_spanned(&self, source: &str) -> Result<Vec<SpannedToken>> {
+        let tokens = self.tokenize_spanned(source)?;
+        parser::process_spanned_quotes(tokens)
+            .map_err(|e| parse_error(source, &e.node).with_span(e.span))
+    }
+
+    /// Execute a sequence of tokens
+    pub fn execute_tokens(&mut self, tokens: &[Token]) -> Result<()> {
+        self.vm.execute_tokens(tokens).map_err(vm_error)
+    }
+
+    /// Load the modules `source` imports, then compile and run it
+    ///
+    /// Errors carry the span of the offending source; see
+    /// [`ChronosError::render`].
+    pub fn eval_source(&mut self, source: &str) -> Result<()> {
+        let source = self.load_modules(source)?;
+        let program = self.compile(&source)?;
+        self.run(&program)
+    }
+
+    /// Run the `import` and `include` directives in `source`
+    ///
+    /// Returns the source with the directives blanked out, ready to be
+    /// evaluated. See the [`module`] documentation for how files are found.
+    pub fn load_modules<'a>(&mut self, source: &'a str) -> Result<Cow<'a, str>> {
+        let (header, source) = self.run_module_header(source)?;
+        header.reject_exports()?;
+        Ok(source)
+    }
+
+    /// Load a file as a module, unless it has been imported already
+    ///
+    /// The words it defines are named `<file stem>::<word>`, and the words
+    /// it exports are also defined under their own names.
+    pub fn import_file(&mut self, path: &str) -> Result<()> {
+        let file = self.modules.resolve(path)?;
+        if self.modules.is_imported(&file) {
+            return Ok(());
         }
 
-        // Recursively process nested quotes
-        self.process_quotes(quote_tokens)
+        let namespace = module::namespace(&file)?;
+        self.load_file(&file, Some(&namespace))?;
+        self.modules.mark_imported(file);
+        Ok(())
     }
 
-    /// Find the index of the matching QuoteEnd for a QuoteStart at the given index
-    fn find_quote_end(&self, tokens: &[Token], start: usize) -> Result<usize> {
-        let mut depth = 0;
-        let mut i = start;
+    /// Evaluate a file as it is written
+    pub fn include_file(&mut self, path: &str) -> Result<()> {
+        let file = self.modules.resolve(path)?;
+        self.load_file(&file, None)
+    }
 
-        while i < tokens.len() {
-            match &tokens[i] {
-                Token::QuoteStart => depth += 1,
-                Token::QuoteEnd => {
-                    depth -= 1;
-                    if depth == 0 {
-                        return Ok(i);
-                    }
-                }
-                _ => {}
+    /// Add a directory to search for imported and included files
+    pub fn add_search_path(&mut self, path: impl Into<PathBuf>) {
+        self.modules.add_search_path(path.into());
+    }
+
+    /// The directories searched for imported and included files
+    pub fn search_paths(&self) -> &[PathBuf] {
+        self.modules.search_paths()
+    }
+
+    /// Run the directives at the top level of `source`, returning the module
+    /// statements found and the source without them
+    fn run_module_header<'a>(
+        &mut self,
+        source: &'a str,
+    ) -> Result<(ModuleHeader, Cow<'a, str>)> {
+        if !["import", "include", "export"]
+            .iter()
+            .any(|keyword| source.contains(keyword))
+        {
+            return Ok((ModuleHeader::default(), Cow::Borrowed(source)));
+        }
+
+        let header = ModuleHeader::scan(&self.tokenize_spanned(source)?)?;
+        for directive in &header.directives {
+            match directive.kind {
+                LoadKind::Import => self.import_file(&directive.path)?,
+                LoadKind::Include => self.include_file(&directive.path)?,
             }
-            i += 1;
         }
 
-        Err(ChronosError::parse_error("Unclosed quote", None, None))
+        let source = if header.is_empty() {
+            Cow::Borrowed(source)
+        } else {
+            Cow::Owned(header.strip(source))
+        };
+        Ok((header, source))
     }
 
-    /// Execute a sequence of tokens
-    pub fn execute_tokens(&mut self, tokens: &[Token]) -> Result<()> {
-        self.vm
-            .execute_tokens(tokens)
-            .map_err(|e| ChronosError::runtime_error(e.to_string(), None))
+    /// Evaluate a file, moving its words into `namespace` when imported
+    fn load_file(&mut self, file: &Path, namespace: Option<&str>) -> Result<()> {
+        let source = fs::read_to_string(file).map_err(|e| ChronosError::ModuleError {
+            message: "Cannot read file".to_string(),
+            path: file.display().to_string(),
+            cause: Some(e.to_string()),
+        })?;
+
+        self.modules.enter(file.to_path_buf())?;
+        let result = self.eval_module(&source, namespace);
+        self.modules.leave();
+
+        // Errors from nested files already say where they came from
+        result.map_err(|e| match e {
+            ChronosError::ModuleError { .. } => e,
+            e => ChronosError::ModuleError {
+                message: "Failed to load".to_string(),
+                path: file.display().to_string(),
+                cause: Some(e.render(&source)),
+            },
+        })
     }
 
-    /// Execute source code with quote parsing
-    pub fn eval_source(&mut self, source: &str) -> Result<()> {
-        let tokens = self.parse(source)?;
-        self.execute_tokens(&tokens)
+    fn eval_module(&mut self, source: &str, namespace: Option<&str>) -> Result<()> {
+        let (header, source) = self.run_module_header(source)?;
+        let mut tokens = self.tokenize_spanned(&source)?;
+
+        match namespace {
+            Some(namespace) => module::qualify(&mut tokens, namespace),
+            None => header.reject_exports()?,
+        }
+        let program = self.compile_tokens(&source, tokens)?;
+        self.run(&program)?;
+
+        let Some(namespace) = namespace else {
+            return Ok(());
+        };
+        for (name, _) in &header.exports {
+            let qualified = format!("{}::{}", namespace, name);
+            let Some(mut definition) = self.vm.get_word_definition(&qualified).cloned() else {
+                return Err(ChronosError::DefinitionError {
+                    message: "Exported word is not defined in the module".to_string(),
+                    word_name: name.clone(),
+                    context: None,
+                });
+            };
+            definition.name = name.clone();
+            if self.vm.word_signature(&qualified).is_some() {
+                self.vm.define_word(definition);
+            } else {
+                self.vm.define_untyped_word(definition);
+            }
+        }
+        Ok(())
     }
 
     /// Execute a single token
     pub fn execute_token(&mut self, token: &Token) -> Result<()> {
-        self.vm
-            .execute_token(token)
-            .map_err(|e| ChronosError::runtime_error(e.to_string(), None))
+        self.vm.execute_token(token).map_err(vm_error)
     }
 
     /// Define a new word in the virtual machine
@@ -237,6 +549,90 @@ impl ChronosCore {
         Ok(())
     }
 
+    /// Define a word whose stack effect is unknown, without type checking it
+    pub fn define_untyped_word(&mut self, definition: WordDefinition) -> Result<()> {
+        self.vm.define_untyped_word(definition);
+        Ok(())
+    }
+
+    /// Declare a composite type, generating its constructor, `Name.field`
+    /// getters, `Name.field!` setters and `Name?` predicate
+    pub fn define_type(&mut self, definition: TypeDefinition) -> Result<()> {
+        self.vm.define_type(definition).map_err(vm_error)
+    }
+
+    /// Get a type declared with `type`
+    pub fn get_type_definition(&self, name: &str) -> Option<&TypeDefinition> {
+        self.vm.get_type_definition(name)
+    }
+
+    /// Declare a sum type, generating a word for each of its constructors
+    pub fn define_data(&mut self, definition: DataDefinition) -> Result<()> {
+        self.vm.define_data(definition).map_err(vm_error)
+    }
+
+    /// Get a type declared with `data`
+    pub fn get_data_definition(&self, name: &str) -> Option<&DataDefinition> {
+        self.vm.get_data_definition(name)
+    }
+
+    /// Prove a theorem, or assume a lemma; see [`theorem`]
+    pub fn add_theorem(&mut self, theorem: Theorem) -> Result<()> {
+        self.vm.add_theorem(theorem).map_err(vm_error)
+    }
+
+    /// Get the theorems proved so far, in declaration order
+    pub fn proofs(&self) -> &[Proof] {
+        self.vm.proofs()
+    }
+
+    /// Get the equations assumed with `lemma`, in declaration order
+    pub fn lemmas(&self) -> &[Theorem] {
+        self.vm.lemmas()
+    }
+
+    /// Assume a word with the given signature; see [`axiom`]
+    pub fn define_axiom(&mut self, name: String, signature: TypeSignature) {
+        self.vm.define_axiom(name, signature)
+    }
+
+    /// Get the axioms declared with `axiom`
+    pub fn axioms(&self) -> &AxiomRegistry {
+        self.vm.axioms()
+    }
+
+    /// Get the axioms each user word relies on, directly or through the
+    /// words it calls
+    pub fn axiom_dependencies(&self) -> BTreeMap<String, BTreeSet<String>> {
+        self.vm.axiom_dependencies()
+    }
+
+    /// Get the definition of a user-defined word
+    pub fn get_word_definition(&self, name: &str) -> Option<&WordDefinition> {
+        self.vm.get_word_definition(name)
+    }
+
+    /// The signature of a word, or `None` when its stack effect is unknown
+    pub fn word_signature(&self, name: &str) -> Option<&TypeSignature> {
+        self.vm.word_signature(name)
+    }
+
+    /// Words whose `::` signature could not be checked against their body
+    /// and is assumed instead, with the reason
+    pub fn assumed_signatures(&self) -> &BTreeMap<String, String> {
+        self.vm.assumed_signatures()
+    }
+
+    /// Signatures declared with `::` for words that are not defined yet
+    pub fn pending_signatures(&self) -> &HashMap<String, TypeSignature> {
+        self.vm.declared_signatures()
+    }
+
+    /// Declare the signature the next definition of `name` must satisfy
+    pub fn declare_signature(&mut self, name: String, signature: TypeSignature) {
+        self.vm.declare_signature(name, signature);
+    }
+
     /// Get the current stack as a vector of values
     pub fn get_stack(&self) -> Vec<Value> {
         self.vm.stack().to_vec()
@@ -247,6 +643,87 @@ impl ChronosCore {
         self.vm.stack().len()
     }
 
+    /// Tests defined with `test:`, in definition order
+    pub fn tests(&self) -> &[TestCase] {
+        self.vm.tests()
+    }
+
+    /// Run the test defined as `test: name ... ;` against this runtime
+    pub fn run_test(&mut self, name: &str) -> Result<()> {
+        let test = self
+            .vm
+            .tests()
+            .iter()
+            .find(|test| test.name == name)
+            .cloned()
+            .ok_or_else(|| ChronosError::undefined_error(name))?;
+        self.vm.execute_tokens(&test.body).map_err(vm_error)
+    }
+
+    /// Check a word against its type signature on random stacks, and run
+    /// the tests that use it as its properties; see [`property`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chronos_core::ChronosCore;
+    ///
+    /// let mut core = ChronosCore::new();
+    /// core.eval(":: double ( Nat -> Nat ) ; : double dup + ;").unwrap();
+    /// core.eval("test: doubles [ double ] [ 2 * ] equiv? assert ;").unwrap();
+    /// assert!(core.check_word("double").unwrap().passed());
+    /// ```
+    pub fn check_word(&mut self, name: &str) -> Result<CheckReport> {
+        let failure = self.vm.check_signature(name).map_err(vm_error)?;
+
+        let tests: Vec<TestCase> = self
+            .vm
+            .tests()
+            .iter()
+            .filter(|test| {
+                let mut words = Vec::new();
+                axiom::collect_words(&test.body, &mut words);
+                words.contains(&name)
+            })
+            .cloned()
+            .collect();
+        let properties = tests
+            .into_iter()
+            .map(|test| {
+                self.vm.take_counterexample();
+                let error = self.vm.run_isolated(&test.body).err().map(vm_error);
+                PropertyResult {
+                    name: test.name,
+                    error,
+                    counterexample: self.vm.take_counterexample(),
+                }
+            })
+            .collect();
+
+        Ok(CheckReport {
+            word: name.to_string(),
+            failure,
+            properties,
+        })
+    }
+
+    /// Register an observer to be notified as the virtual machine executes
+    pub fn add_observer(&mut self, observer: Box<dyn VmObserver>) -> ObserverId {
+        self.vm.add_observer(observer)
+    }
+
+    /// Unregister an observer, returning it
+    pub fn remove_observer(&mut self, id: ObserverId) -> Option<Box<dyn VmObserver>> {
+        self.vm.remove_observer(id)
+    }
+
+    /// Attach a step debugger to the virtual machine, or detach it with `None`
+    ///
+    /// Returns the previously attached debugger.
+    pub fn set_debugger(&mut self, debugger: Option<Debugger>) -> Option<Debugger> {
+        self.vm.set_debugger(debugger)
+    }
+
     /// Push a value onto the stack
     pub fn push(&mut self, value: Value) {
         self.vm.push(value);
@@ -278,9 +755,59 @@ impl ChronosCore {
             "<".to_string(),
             ">".to_string(),
             "if".to_string(),
+            "when".to_string(),
+            "unless".to_string(),
+            "times".to_string(),
+            "call".to_string(),
+            "while".to_string(),
+            "each".to_string(),
+            "map".to_string(),
+            "filter".to_string(),
+            "fold".to_string(),
+            "range".to_string(),
+            "cons".to_string(),
+            "head".to_string(),
+            "head?".to_string(),
+            "tail".to_string(),
+            "tail?".to_string(),
+            "nth".to_string(),
+            "nth?".to_string(),
+            "append".to_string(),
+            "reverse".to_string(),
+            "sort".to_string(),
+            "zip".to_string(),
             ".".to_string(),
             ".s".to_string(),
             "print".to_string(),
+            "words".to_string(),
+            "see".to_string(),
+            "clear".to_string(),
+            "depth".to_string(),
+            "help".to_string(),
+            "omega".to_string(),
+            "ord".to_string(),
+            "ord+".to_string(),
+            "ord*".to_string(),
+            "ord^".to_string(),
+            "ord<".to_string(),
+            "print-string".to_string(),
+            "concat".to_string(),
+            "length".to_string(),
+            "substring".to_string(),
+            "split".to_string(),
+            "join".to_string(),
+            "to-upper".to_string(),
+            "to-lower".to_string(),
+            "trim".to_string(),
+            "contains?".to_string(),
+            "string->nat".to_string(),
+            "nat->string".to_string(),
+            "to-string".to_string(),
+            "format".to_string(),
+            "assert".to_string(),
+            "assert-eq".to_string(),
+            "assert-stack".to_string(),
+            "equiv?".to_string(),
         ];
 
         // Add user-defined words
@@ -306,12 +833,65 @@ impl ChronosCore {
                 | "<"
                 | ">"
                 | "if"
+                | "when"
+                | "unless"
+                | "times"
+                | "call"
+                | "while"
+                | "each"
+                | "map"
+                | "filter"
+                | "fold"
+                | "range"
+                | "cons"
+                | "head"
+                | "head?"
+                | "tail"
+                | "tail?"
+                | "nth"
+                | "nth?"
+                | "append"
+                | "reverse"
+                | "sort"
+                | "zip"
                 | "."
                 | ".s"
                 | "print"
+                | "words"
+                | "see"
+                | "clear"
+                | "depth"
+                | "help"
+                | "omega"
+                | "ω"
+                | "ord"
+                | "ord+"
+                | "ord*"
+                | "ord^"
+                | "ord<"
+                | "print-string"
+                | "concat"
+                | "length"
+                | "substring"
+                | "split"
+                | "join"
+                | "to-upper"
+                | "to-lower"
+                | "trim"
+                | "contains?"
+                | "string->nat"
+                | "nat->string"
+                | "to-string"
+                | "format"
+                | "assert"
+                | "assert-eq"
+                | "assert-stack"
+                | "equiv?"
                 | "::"
                 | ":"
+                | "test:"
         ) || self.vm.get_word_definition(name).is_some()
+            || self.vm.is_type_word(name)
     }
 
     /// Reset the virtual machine to initial state
@@ -325,6 +905,19 @@ impl ChronosCore {
         self.vm.get_user_words()
     }
 
+    /// Run strict termination analysis on a defined word
+    ///
+    /// The word is checked against every definition in the dictionary; the
+    /// certificate explains the verdict whether or not the word is accepted.
+    pub fn certify_termination(&self, name: &str) -> Result<TerminationCertificate> {
+        let word_def = self.vm.get_word_definition(name).ok_or_else(|| {
+            ChronosError::runtime_error(format!("Unknown word: {}", name), Some(name.to_string()))
+        })?;
+        let verifier = OrdinalVerifier::strict()
+            .with_definitions(self.vm.get_all_word_definitions().values());
+        Ok(verifier.certify(word_def))
+    }
+
     /// Get documentation for a core word (simplified)
     pub fn get_word_doc(&self, _name: &str) -> Option<&str> {
         None // Simplified for now
@@ -337,6 +930,73 @@ impl Default for ChronosCore {
     }
 }
 
+/// Convert a lexer error into the public error type, locating it in `source`
+fn lex_error(source: &str, error: &LexError) -> ChronosError {
+    let offset = error
+        .position()
+        .unwrap_or_else(|| source.chars().count());
+    ChronosError::LexError {
+        message: error.message(),
+        position: error.position(),
+        source: None,
+        span: Some(Span::at_offset(source, offset as u32, 1)),
+    }
+}
+
+/// Convert a parser error into the public error type
+fn parse_error(source: &str, error: &ParseError) -> ChronosError {
+    match error {
+        ParseError::LexError(e) => lex_error(source, e),
+        other => ChronosError::parse_error(other.to_string(), None, None),
+    }
+}
+
+/// Convert an error raised by `parser`, locating it at the parser's position
+fn parser_error(source: &str, parser: &Parser, error: ParseError) -> ChronosError {
+    match parse_error(source, &error) {
+        ChronosError::ParseError {
+            message,
+            expected,
+            found,
+            ..
+        } => ChronosError::ParseError {
+            message,
+            token_position: Some(parser.token_position()),
+            expected,
+            found,
+            span: parser.error_span(),
+        },
+        other => other,
+    }
+}
+
+/// Convert a virtual machine error into the public error type
+fn vm_error(error: VmError) -> ChronosError {
+    match error {
+        VmError::TypeError {
+            word,
+            message,
+            expected,
+            found,
+        } => ChronosError::type_error(message, expected, found).with_word_context(word),
+        VmError::ArithmeticOverflow {
+            operation,
+            operands,
+        } => ChronosError::arithmetic_error("Nat overflow", operation, operands),
+        VmError::AssertionFailed {
+            word,
+            expected,
+            actual,
+        } => ChronosError::AssertionError {
+            message: word,
+            expected,
+            actual,
+            span: None,
+        },
+        other => ChronosError::runtime_error(other.to_string(), None),
+    }
+}
+
 /// Create a new virtual machine instance
 ///
 /// This is a convenience function for creating a VM without the full
@@ -357,9 +1017,7 @@ pub fn create_core_lib() -> CoreLibrary {
 /// Convenience function for tokenizing without creating a full ChronosCore instance.
 pub fn parse_tokens(source: &str) -> Result<Vec<Token>> {
     let mut lexer = Lexer::new(source);
-    lexer
-        .tokenize()
-        .map_err(|e| ChronosError::lex_error(e.to_string(), None))
+    lexer.tokenize().map_err(|e| lex_error(source, &e))
 }
 
 /// Traits for extending the core functionality
@@ -0,0 +1,464 @@
+//! Loading C∀O source files with `import` and `include`
+//!
+//! Source code can pull in other `.cao` files with two top-level directives:
+//!
+//! - `include "file.cao"` evaluates the file as it is written, every time the
+//!   directive appears.
+//! - `import "file.cao"` evaluates the file once, as a module named after the
+//!   file. Every word the module defines is moved into the module namespace,
+//!   so `: gcd ... ;` in `math.cao` defines `math::gcd`. Words listed in an
+//!   `export gcd lcm ;` clause are also defined under their plain names.
+//!
+//! A relative path is looked up next to the file containing the directive (the
+//! working directory for code that is not in a file), then in each search
+//! path in turn. Search paths are read from the `CHRONOS_PATH` environment
+//! variable and can be added with
+//! [`ChronosCore::add_search_path`](crate::ChronosCore::add_search_path).
+//! Directives run before the rest of the source, so its definitions can use
+//! the words they load.
+
+use crate::error::{ChronosError, Result};
+use crate::types::{MatchArm, Span, SpannedToken, Token, Value};
+use std::collections::HashSet;
+use std::env;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+/// Environment variable listing extra directories to search for files
+pub const SEARCH_PATH_VAR: &str = "CHRONOS_PATH";
+
+/// How a directive loads its file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LoadKind {
+    Import,
+    Include,
+}
+
+/// A top-level `import` or `include` directive
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Directive {
+    pub kind: LoadKind,
+    pub path: String,
+}
+
+/// The module statements found at the top level of some source
+#[derive(Debug, Default)]
+pub(crate) struct ModuleHeader {
+    pub directives: Vec<Directive>,
+    /// Words named in `export` clauses
+    pub exports: Vec<(String, Span)>,
+    /// Where the directives and export clauses are, so they can be removed
+    pub spans: Vec<Span>,
+}
+
+impl ModuleHeader {
+    /// Find the module statements among the tokens of some source
+    ///
+    /// Only statements outside definitions and quotes count.
+    pub fn scan(tokens: &[SpannedToken]) -> Result<Self> {
+        let mut header = ModuleHeader::default();
+        let mut depth = 0usize;
+        let mut in_definition = false;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let token = &tokens[i];
+            match &token.node {
+                Token::QuoteStart => depth += 1,
+                Token::QuoteEnd => depth = depth.saturating_sub(1),
+                Token::Word(_) if depth > 0 => {}
+                Token::Word(word) if in_definition => in_definition = word != ";",
+                Token::Word(word) if word == ":" || word == "::" => in_definition = true,
+                Token::Word(word) if word == "import" || word == "include" => {
+                    let Some(Token::Literal(Value::String(path))) =
+                        tokens.get(i + 1).map(|next| &next.node)
+                    else {
+                        return Err(ChronosError::parse_error(
+                            format!("'{}' needs a file name", word),
+                            Some("a quoted path".to_string()),
+                            None,
+                        )
+                        .with_span(token.span));
+                    };
+
+                    let kind = if word == "import" {
+                        LoadKind::Import
+                    } else {
+                        LoadKind::Include
+                    };
+                    header.directives.push(Directive {
+                        kind,
+                        path: path.clone(),
+                    });
+                    header.spans.push(token.span.to(tokens[i + 1].span));
+                    i += 2;
+                    continue;
+                }
+                Token::Word(word) if word == "export" => {
+                    let end = tokens[i + 1..]
+                        .iter()
+                        .position(|t| t.node == Token::Word(";".to_string()))
+                        .map(|offset| i + 1 + offset)
+                        .ok_or_else(|| {
+                            ChronosError::parse_error(
+                                "Unterminated export list",
+                                Some(";".to_string()),
+                                None,
+                            )
+                            .with_span(token.span)
+                        })?;
+
+                    for name in &tokens[i + 1..end] {
+                        let Token::Word(word) = &name.node else {
+                            return Err(ChronosError::parse_error(
+                                "Only words can be exported",
+                                None,
+                                Some(name.node.to_string()),
+                            )
+                            .with_span(name.span));
+                        };
+                        header.exports.push((word.clone(), name.span));
+                    }
+                    header.spans.push(token.span.to(tokens[end].span));
+                    i = end + 1;
+                    continue;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        Ok(header)
+    }
+
+    /// Fail if there are exports, for source that is not imported
+    pub fn reject_exports(&self) -> Result<()> {
+        match self.exports.first() {
+            Some((_, span)) => Err(ChronosError::parse_error(
+                "'export' is only allowed in imported files",
+                None,
+                None,
+            )
+            .with_span(*span)),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the source had no module statements
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The source with the module statements blanked out
+    ///
+    /// Line breaks are kept, so the spans of the remaining tokens still
+    /// point at the same place.
+    pub fn strip(&self, source: &str) -> String {
+        source
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                let i = i as u32;
+                let covered = self
+                    .spans
+                    .iter()
+                    .any(|span| span.offset <= i && i < span.offset + span.len);
+                if covered && ch != '\n' {
+                    ' '
+                } else {
+                    ch
+                }
+            })
+            .collect()
+    }
+}
+
+/// Move the words defined in `tokens` into `namespace`, renaming both their
+/// definitions and every reference to them
+pub(crate) fn qualify(tokens: &mut [SpannedToken], namespace: &str) {
+    let defined: HashSet<String> = tokens
+        .windows(2)
+        .filter_map(|pair| match (&pair[0].node, &pair[1].node) {
+            (Token::Word(keyword), Token::Word(name)) if keyword == ":" || keyword == "::" => {
+                Some(name.clone())
+            }
+            _ => None,
+        })
+        .collect();
+
+    for token in tokens {
+        qualify_token(&mut token.node, namespace, &defined);
+    }
+}
+
+fn qualify_token(token: &mut Token, namespace: &str, defined: &HashSet<String>) {
+    match token {
+        Token::Word(word) if defined.contains(word.as_str()) => {
+            *word = format!("{}::{}", namespace, word);
+        }
+        Token::Literal(Value::Quote(body)) => {
+            for token in body {
+                qualify_token(token, namespace, defined);
+            }
+        }
+        Token::MatchExpression { value, arms } => {
+            qualify_token(value, namespace, defined);
+            for MatchArm { body, .. } in arms {
+                for token in body {
+                    qualify_token(token, namespace, defined);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The namespace a file is imported into: its name without the extension
+pub(crate) fn namespace(file: &Path) -> Result<String> {
+    let stem = file
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let valid = stem.starts_with(char::is_alphanumeric)
+        && stem
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '?' | '!'));
+    if valid {
+        Ok(stem.to_string())
+    } else {
+        Err(ChronosError::module_error(
+            format!("'{}' is not a valid module name", stem),
+            file.display().to_string(),
+        ))
+    }
+}
+
+/// Finds source files and tracks which are loaded
+#[derive(Debug, Clone)]
+pub(crate) struct ModuleLoader {
+    search_paths: Vec<PathBuf>,
+    /// Files being loaded, outermost first
+    loading: Vec<PathBuf>,
+    /// Files already imported, which later imports skip
+    imported: HashSet<PathBuf>,
+}
+
+impl ModuleLoader {
+    /// Create a loader searching the directories in `CHRONOS_PATH`
+    pub fn new() -> Self {
+        let search_paths = env::var_os(SEARCH_PATH_VAR)
+            .map(|paths| env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        Self {
+            search_paths,
+            loading: Vec::new(),
+            imported: HashSet::new(),
+        }
+    }
+
+    pub fn search_paths(&self) -> &[PathBuf] {
+        &self.search_paths
+    }
+
+    pub fn add_search_path(&mut self, path: PathBuf) {
+        if !self.search_paths.contains(&path) {
+            self.search_paths.push(path);
+        }
+    }
+
+    /// Find the file a directive names
+    pub fn resolve(&self, path: &str) -> Result<PathBuf> {
+        let requested = Path::new(path);
+        let candidates: Vec<PathBuf> = if requested.is_absolute() {
+            vec![requested.to_path_buf()]
+        } else {
+            let local = match self.loading.last().and_then(|file| file.parent()) {
+                Some(dir) => dir.join(requested),
+                None => requested.to_path_buf(),
+            };
+            iter::once(local)
+                .chain(self.search_paths.iter().map(|dir| dir.join(requested)))
+                .collect()
+        };
+
+        candidates
+            .iter()
+            .find(|candidate| candidate.is_file())
+            .and_then(|file| file.canonicalize().ok())
+            .ok_or_else(|| ChronosError::module_error("File not found", path))
+    }
+
+    /// Start loading `file`, failing if that would load it inside itself
+    pub fn enter(&mut self, file: PathBuf) -> Result<()> {
+        if self.loading.contains(&file) {
+            let cycle: Vec<String> = self
+                .loading
+                .iter()
+                .skip_while(|loading| **loading != file)
+                .chain(iter::once(&file))
+                .map(|file| display_name(file))
+                .collect();
+            return Err(ChronosError::module_error(
+                format!("Import cycle {}", cycle.join(" -> ")),
+                file.display().to_string(),
+            ));
+        }
+        self.loading.push(file);
+        Ok(())
+    }
+
+    /// Finish loading the innermost file
+    pub fn leave(&mut self) {
+        self.loading.pop();
+    }
+
+    pub fn is_imported(&self, file: &Path) -> bool {
+        self.imported.contains(file)
+    }
+
+    pub fn mark_imported(&mut self, file: PathBuf) {
+        self.imported.insert(file);
+    }
+}
+
+fn display_name(file: &Path) -> String {
+    file.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| file.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ChronosCore, ChronosError, Value};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// A fresh directory holding the given files
+    fn files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("chronos-modules-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        for (file, contents) in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn import(dir: &Path, file: &str) -> String {
+        format!("import \"{}\"", dir.join(file).display())
+    }
+
+    #[test]
+    fn test_import_namespaces_and_exports() {
+        let dir = files(
+            "exports",
+            &[(
+                "math.cao",
+                "export quad ;\n\
+                 :: double ( Nat -> Nat ) ;\n\
+                 : double 2 * ;\n\
+                 : quad double double ;",
+            )],
+        );
+        let mut core = ChronosCore::new();
+        core.eval_source(&format!(
+            "{} 3 math::double 2 quad",
+            import(&dir, "math.cao")
+        ))
+        .unwrap();
+
+        assert_eq!(core.get_stack(), vec![Value::Nat(6), Value::Nat(8)]);
+        assert!(core.get_word_definition("math::quad").is_some());
+        assert!(core.get_word_definition("double").is_none());
+        assert!(core.word_signature("math::double").is_some());
+        assert_eq!(
+            core.get_word_definition("quad").unwrap().body[0].to_string(),
+            "math::double"
+        );
+    }
+
+    #[test]
+    fn test_import_once_include_every_time() {
+        let dir = files("once", &[("one.cao", "1")]);
+        let mut core = ChronosCore::new();
+        let import = import(&dir, "one.cao");
+        core.eval_source(&format!("{} {}", import, import)).unwrap();
+        assert_eq!(core.stack_depth(), 1);
+
+        core.include_file(dir.join("one.cao").to_str().unwrap())
+            .unwrap();
+        core.include_file(dir.join("one.cao").to_str().unwrap())
+            .unwrap();
+        assert_eq!(core.stack_depth(), 3);
+    }
+
+    #[test]
+    fn test_nested_imports_and_search_paths() {
+        let dir = files(
+            "search",
+            &[
+                (
+                    "lib/shapes.cao",
+                    "import \"geometry/area.cao\" export cube ; : cube dup area::square * ;",
+                ),
+                ("lib/geometry/area.cao", ": square dup * ;"),
+            ],
+        );
+        let mut core = ChronosCore::new();
+        core.add_search_path(dir.join("lib"));
+        core.eval("import \"shapes.cao\" 3 cube").unwrap();
+        assert_eq!(core.get_stack(), vec![Value::Nat(27)]);
+        assert!(core.eval("import \"area.cao\"").is_err());
+    }
+
+    #[test]
+    fn test_import_cycles() {
+        let dir = files(
+            "cycle",
+            &[("a.cao", "import \"b.cao\""), ("b.cao", "import \"a.cao\"")],
+        );
+        let mut core = ChronosCore::new();
+        let error = core.eval_source(&import(&dir, "a.cao")).unwrap_err();
+        assert_eq!(error.category(), "module");
+        assert!(error
+            .to_string()
+            .contains("Import cycle a.cao -> b.cao -> a.cao"));
+
+        // Nothing is left half-loaded, so a fixed file can be imported
+        fs::write(dir.join("b.cao"), ": b 2 ;").unwrap();
+        core.eval_source(&import(&dir, "a.cao")).unwrap();
+        assert!(core.get_word_definition("b::b").is_some());
+    }
+
+    #[test]
+    fn test_module_errors() {
+        let dir = files(
+            "errors",
+            &[
+                ("broken.cao", "1 2 +\nfrob"),
+                ("missing.cao", "export nothing ;"),
+                ("spin.cao", "export spin ;\n: spin spin ;"),
+            ],
+        );
+        let mut core = ChronosCore::new();
+
+        let error = core.eval_source(&import(&dir, "broken.cao")).unwrap_err();
+        let ChronosError::ModuleError { path, cause, .. } = &error else {
+            panic!("expected a module error, got {}", error);
+        };
+        assert!(path.ends_with("broken.cao"));
+        assert!(cause.as_deref().unwrap().contains("2 | frob"));
+
+        assert!(core.eval_source(&import(&dir, "missing.cao")).is_err());
+
+        // Imported definitions are checked like any other
+        let error = core.eval_source(&import(&dir, "spin.cao")).unwrap_err();
+        assert!(error.to_string().contains("spin -> spin"));
+        assert!(!core.is_word_defined("spin"));
+        assert!(core.eval_source("import \"no-such-file.cao\"").is_err());
+        assert!(core.eval_source("import 42").is_err());
+        assert!(core.eval_source("export dup ;").is_err());
+    }
+}
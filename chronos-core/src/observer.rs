@@ -0,0 +1,230 @@
+//! Execution hooks for the virtual machine
+//!
+//! A [`VmObserver`] registered on the [`VirtualMachine`](crate::VirtualMachine)
+//! sees execution from the inside: every token run, including those in the
+//! bodies of user-defined words, quotes run by combinators and match arms;
+//! every word entered and left; every value pushed and popped; and every error
+//! where it is raised. Tracing, profiling, debugging and coverage tools can all
+//! be written against this one interface.
+
+use crate::types::{Token, Value};
+use crate::vm::VmError;
+use std::sync::{Arc, Mutex};
+
+/// The machine state an observer is shown with each event
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionContext<'a> {
+    /// Words currently executing, outermost first
+    pub call_stack: &'a [String],
+    /// The data stack, bottom first
+    pub stack: &'a [Value],
+}
+
+/// Callbacks invoked by the VM as it executes
+///
+/// Every method has an empty default, so observers only implement the events
+/// they care about. Observers are called in the order they were registered.
+pub trait VmObserver: Send {
+    /// A token is about to run
+    ///
+    /// Returning an error stops execution with that error.
+    fn token_enter(
+        &mut self,
+        token: &Token,
+        context: &ExecutionContext<'_>,
+    ) -> Result<(), VmError> {
+        let _ = (token, context);
+        Ok(())
+    }
+
+    /// A word was entered; it is already on the call stack
+    fn word_enter(&mut self, word: &str, context: &ExecutionContext<'_>) {
+        let _ = (word, context);
+    }
+
+    /// A word is returning, successfully or not; it is still on the call stack
+    fn word_exit(&mut self, word: &str, context: &ExecutionContext<'_>) {
+        let _ = (word, context);
+    }
+
+    /// A value was pushed onto the data stack
+    fn push(&mut self, value: &Value, context: &ExecutionContext<'_>) {
+        let _ = (value, context);
+    }
+
+    /// A value was popped from the data stack
+    fn pop(&mut self, value: &Value, context: &ExecutionContext<'_>) {
+        let _ = (value, context);
+    }
+
+    /// An error was raised; reported once, by the innermost failing token
+    fn error(&mut self, error: &VmError, context: &ExecutionContext<'_>) {
+        let _ = (error, context);
+    }
+}
+
+/// Shared observers let the caller read what was observed while the VM
+/// keeps its own handle
+impl<T: VmObserver> VmObserver for Arc<Mutex<T>> {
+    fn token_enter(
+        &mut self,
+        token: &Token,
+        context: &ExecutionContext<'_>,
+    ) -> Result<(), VmError> {
+        lock(self).token_enter(token, context)
+    }
+
+    fn word_enter(&mut self, word: &str, context: &ExecutionContext<'_>) {
+        lock(self).word_enter(word, context)
+    }
+
+    fn word_exit(&mut self, word: &str, context: &ExecutionContext<'_>) {
+        lock(self).word_exit(word, context)
+    }
+
+    fn push(&mut self, value: &Value, context: &ExecutionContext<'_>) {
+        lock(self).push(value, context)
+    }
+
+    fn pop(&mut self, value: &Value, context: &ExecutionContext<'_>) {
+        lock(self).pop(value, context)
+    }
+
+    fn error(&mut self, error: &VmError, context: &ExecutionContext<'_>) {
+        lock(self).error(error, context)
+    }
+}
+
+/// Lock a shared observer, recovering it if a previous holder panicked
+fn lock<T>(observer: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    observer
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Identifies a registered observer so that it can be removed again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(pub(crate) usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChronosCore;
+
+    /// Records every event as a line of text
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl VmObserver for Recorder {
+        fn token_enter(
+            &mut self,
+            token: &Token,
+            context: &ExecutionContext<'_>,
+        ) -> Result<(), VmError> {
+            self.events
+                .push(format!("token {} @{}", token, context.call_stack.len()));
+            Ok(())
+        }
+
+        fn word_enter(&mut self, word: &str, _: &ExecutionContext<'_>) {
+            self.events.push(format!("enter {}", word));
+        }
+
+        fn word_exit(&mut self, word: &str, _: &ExecutionContext<'_>) {
+            self.events.push(format!("exit {}", word));
+        }
+
+        fn push(&mut self, value: &Value, _: &ExecutionContext<'_>) {
+            self.events.push(format!("push {}", value));
+        }
+
+        fn pop(&mut self, value: &Value, _: &ExecutionContext<'_>) {
+            self.events.push(format!("pop {}", value));
+        }
+
+        fn error(&mut self, error: &VmError, context: &ExecutionContext<'_>) {
+            self.events.push(format!(
+                "error {} in {}",
+                error,
+                context.call_stack.join(">")
+            ));
+        }
+    }
+
+    fn observe(code: &str) -> Vec<String> {
+        let mut core = ChronosCore::new();
+        core.eval_source(": square dup * ; : quad square square ;")
+            .unwrap();
+
+        let recorder = Arc::new(Mutex::new(Recorder::default()));
+        let id = core.add_observer(Box::new(recorder.clone()));
+        let _ = core.eval_source(code);
+        assert!(core.remove_observer(id).is_some());
+
+        let mut recorder = recorder.lock().unwrap();
+        std::mem::take(&mut recorder.events)
+    }
+
+    #[test]
+    fn test_observes_inside_words() {
+        let events = observe("3 square");
+        assert_eq!(
+            events,
+            vec![
+                "token 3 @0",
+                "push 3",
+                "token square @0",
+                "enter square",
+                "token dup @1",
+                "enter dup",
+                "push 3",
+                "exit dup",
+                "token * @1",
+                "enter *",
+                "pop 3",
+                "pop 3",
+                "push 9",
+                "exit *",
+                "exit square",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_observes_quotes_run_by_combinators() {
+        let events = observe("true [ 1 ] [ 2 ] if");
+        assert!(events.contains(&"token 1 @1".to_string()));
+        assert!(!events.contains(&"token 2 @1".to_string()));
+    }
+
+    #[test]
+    fn test_errors_are_reported_once_where_raised() {
+        let events = observe("quad");
+        let errors: Vec<&String> = events.iter().filter(|e| e.starts_with("error")).collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].ends_with("in quad>square>dup"));
+        assert_eq!(events.last().map(String::as_str), Some("exit quad"));
+    }
+
+    #[test]
+    fn test_token_enter_can_stop_execution() {
+        struct Limit(usize);
+
+        impl VmObserver for Limit {
+            fn token_enter(&mut self, _: &Token, _: &ExecutionContext<'_>) -> Result<(), VmError> {
+                if self.0 == 0 {
+                    return Err(VmError::RuntimeError("step limit reached".to_string()));
+                }
+                self.0 -= 1;
+                Ok(())
+            }
+        }
+
+        let mut core = ChronosCore::new();
+        core.add_observer(Box::new(Limit(2)));
+        assert!(core.eval_source("1 2 3").is_err());
+        assert_eq!(core.get_stack(), vec![Value::Nat(1), Value::Nat(2)]);
+    }
+}
@@ -0,0 +1,1620 @@
+//! Ordinal Analysis for C∀O (Kao) termination verification
+//!
+//! This module implements ordinal analysis to ensure program termination and system consistency.
+//! The default verifier uses quick heuristics; [`OrdinalVerifier::strict`] builds the call graph
+//! across word definitions and checks every recursive call for a decreasing measure.
+
+use crate::type_inference::core_signatures;
+use crate::types::{OrdinalValue, Pattern, Token, Value, WordDefinition};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Errors that can occur during ordinal verification
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrdinalError {
+    /// Non-terminating recursion detected
+    NonTerminating(String),
+    /// Invalid ordinal structure
+    InvalidOrdinal(String),
+    /// Ordinal arithmetic overflow
+    Overflow(String),
+    /// Consistency check failed
+    ConsistencyFailed(String),
+}
+
+impl fmt::Display for OrdinalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrdinalError::NonTerminating(msg) => write!(f, "Non-terminating: {}", msg),
+            OrdinalError::InvalidOrdinal(msg) => write!(f, "Invalid ordinal: {}", msg),
+            OrdinalError::Overflow(msg) => write!(f, "Ordinal overflow: {}", msg),
+            OrdinalError::ConsistencyFailed(msg) => write!(f, "Consistency failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OrdinalError {}
+
+/// A call from one user-defined word to another, as judged by strict analysis
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallCheck {
+    pub caller: String,
+    pub callee: String,
+    /// The conditional on the caller's measure that the call is nested
+    /// under (`if`, `when`, `unless`, `match`)
+    pub guard: Option<String>,
+    /// How the measure passed to the callee has decreased since the caller
+    /// was entered
+    pub decrease: Option<String>,
+}
+
+impl CallCheck {
+    /// A recursive call is accepted when it is guarded and decreasing
+    pub fn is_accepted(&self) -> bool {
+        self.guard.is_some() && self.decrease.is_some()
+    }
+}
+
+/// A strongly connected component of the call graph containing recursion
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecursiveComponent {
+    /// The mutually recursive words, sorted by name
+    pub words: Vec<String>,
+    /// Every call between words of the component
+    pub calls: Vec<CallCheck>,
+}
+
+/// The outcome of strict termination analysis for one word
+///
+/// The `Display` form is a readable account of every recursive call the
+/// word can reach and why it was accepted or rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminationCertificate {
+    pub word: String,
+    /// An ordinal bound on the word's running time, when it terminates
+    pub bound: Option<OrdinalValue>,
+    /// Recursive components reachable from the word, callees first
+    pub components: Vec<RecursiveComponent>,
+}
+
+impl TerminationCertificate {
+    /// Whether the word was proven to terminate
+    pub fn is_accepted(&self) -> bool {
+        self.bound.is_some()
+    }
+}
+
+impl fmt::Display for TerminationCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Termination certificate for '{}'", self.word)?;
+        match &self.bound {
+            Some(bound) => write!(f, "  verdict: terminates, bound {}", bound)?,
+            None => write!(f, "  verdict: rejected")?,
+        }
+        if self.components.is_empty() {
+            write!(f, "\n  no recursive calls")?;
+        }
+        for component in &self.components {
+            write!(
+                f,
+                "\n  recursive component [{}]:",
+                component.words.join(", ")
+            )?;
+            for call in &component.calls {
+                write!(f, "\n    {} -> {}: ", call.caller, call.callee)?;
+                let mut problems = Vec::new();
+                if call.guard.is_none() {
+                    problems.push("not guarded by a conditional on the measure".to_string());
+                }
+                if call.decrease.is_none() {
+                    problems.push(
+                        "no decreasing argument before the call (expected a net `n -`, \
+                         or `n /` where the guard rules out 0)"
+                            .to_string(),
+                    );
+                }
+                match (&call.guard, &call.decrease) {
+                    (Some(guard), Some(decrease)) => {
+                        write!(f, "guarded by '{}', decreases by '{}'", guard, decrease)?
+                    }
+                    _ => write!(f, "{}", problems.join("; "))?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Ordinal verifier for word definitions
+///
+/// The default verifier uses heuristics on a single definition. A strict
+/// verifier ([`OrdinalVerifier::strict`]) analyses the call graph across
+/// every definition it knows about and produces a [`TerminationCertificate`].
+pub struct OrdinalVerifier {
+    /// Whether to use strict call-graph analysis
+    strict_mode: bool,
+    /// Definitions visible to strict analysis, by name
+    definitions: HashMap<String, WordDefinition>,
+}
+
+impl OrdinalVerifier {
+    /// Create a new ordinal verifier
+    pub fn new() -> Self {
+        OrdinalVerifier {
+            strict_mode: false,
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Create a verifier that runs strict call-graph analysis
+    pub fn strict() -> Self {
+        OrdinalVerifier {
+            strict_mode: true,
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Make existing word definitions visible to strict analysis
+    pub fn with_definitions<'a>(
+        mut self,
+        definitions: impl IntoIterator<Item = &'a WordDefinition>,
+    ) -> Self {
+        for word_def in definitions {
+            self.add_definition(word_def.clone());
+        }
+        self
+    }
+
+    /// Make a word definition visible to strict analysis
+    pub fn add_definition(&mut self, word_def: WordDefinition) {
+        self.definitions.insert(word_def.name.clone(), word_def);
+    }
+
+    /// Verify that a word definition terminates and compute its ordinal cost
+    pub fn verify_termination(
+        &self,
+        word_def: &WordDefinition,
+    ) -> Result<OrdinalValue, OrdinalError> {
+        if self.strict_mode {
+            self.analyze_termination_strict(word_def)
+        } else {
+            self.analyze_termination_mock(word_def)
+        }
+    }
+
+    /// Mock termination analysis (Phase 1)
+    fn analyze_termination_mock(
+        &self,
+        word_def: &WordDefinition,
+    ) -> Result<OrdinalValue, OrdinalError> {
+        // Simple heuristics for mock verification:
+        // 1. If the word contains recursive calls to itself, assign higher cost
+        // 2. If it's clearly non-recursive, assign cost 1
+        // 3. For complex cases, assign a reasonable finite ordinal
+
+        let recursive_calls = self.count_recursive_calls(&word_def.body, &word_def.name);
+
+        if recursive_calls == 0 {
+            // Non-recursive word
+            Ok(OrdinalValue::Finite(1))
+        } else if recursive_calls <= 2 && self.has_decreasing_pattern(&word_def.body) {
+            // Likely terminating recursion with decreasing pattern
+            Ok(OrdinalValue::Finite((recursive_calls + 1) as u64))
+        } else if recursive_calls > 10 {
+            // Suspicious - might be non-terminating
+            Err(OrdinalError::NonTerminating(format!(
+                "Word '{}' has {} recursive calls without clear termination",
+                word_def.name, recursive_calls
+            )))
+        } else {
+            // Assume terminating but assign higher ordinal cost
+            Ok(OrdinalValue::Finite(recursive_calls as u64 * 2))
+        }
+    }
+
+    /// Strict termination analysis
+    ///
+    /// Rejections carry the rendered [`TerminationCertificate`].
+    fn analyze_termination_strict(
+        &self,
+        word_def: &WordDefinition,
+    ) -> Result<OrdinalValue, OrdinalError> {
+        let certificate = self.certify(word_def);
+        certificate
+            .bound
+            .clone()
+            .ok_or_else(|| OrdinalError::NonTerminating(certificate.to_string()))
+    }
+
+    /// Run strict termination analysis on a word definition
+    ///
+    /// The definition is analysed together with every definition known to
+    /// this verifier, replacing any earlier definition of the same name.
+    ///
+    /// 1. Build the call graph between user-defined words.
+    /// 2. Split it into strongly connected components.
+    /// 3. Take the top of the stack on entry to each word as its measure and
+    ///    follow it through the body. In each recursive component, require
+    ///    every call to sit under a conditional that tests the measure, and
+    ///    to pass the callee a measure that is strictly smaller: a net `n -`
+    ///    with n ≥ 1, or `n /` with n ≥ 2 in a branch that rules out 0. The
+    ///    measure is a `Nat`, so every recursive path descends a
+    ///    well-founded order.
+    /// 4. Bound each component's cost in Cantor normal form: one pass through
+    ///    a component is repeated at most ω times, so a recursive component
+    ///    whose body costs below ω^d is bounded by ω^(d+1).
+    pub fn certify(&self, word_def: &WordDefinition) -> TerminationCertificate {
+        let mut definitions: HashMap<&str, &WordDefinition> = self
+            .definitions
+            .iter()
+            .map(|(name, def)| (name.as_str(), def))
+            .collect();
+        definitions.insert(word_def.name.as_str(), word_def);
+
+        let graph = CallGraph::build(&word_def.name, &definitions);
+        let mut bounds: HashMap<&str, Cnf> = HashMap::new();
+        let mut components = Vec::new();
+        let mut accepted = true;
+
+        // Tarjan's algorithm yields components callees-first
+        for component in graph.components() {
+            let members: HashSet<&str> = component.iter().copied().collect();
+            let recursive = component.len() > 1
+                || graph.calls[component[0]]
+                    .iter()
+                    .any(|call| call.callee == component[0]);
+
+            let words = |word: &str| {
+                if members.contains(word) {
+                    Some(Cnf::default())
+                } else {
+                    bounds.get(word).cloned()
+                }
+            };
+            let pass = component
+                .iter()
+                .map(|name| token_cost(&definitions[name].body, &words))
+                .fold(Cnf::default(), Cnf::max);
+            let bound = if recursive { pass.repeated(None) } else { pass };
+
+            if recursive {
+                let mut words: Vec<String> = component.iter().map(|w| w.to_string()).collect();
+                words.sort();
+                let calls: Vec<CallCheck> = component
+                    .iter()
+                    .flat_map(|name| graph.calls[name].iter())
+                    .filter(|call| members.contains(call.callee.as_str()))
+                    .cloned()
+                    .collect();
+                accepted &= calls.iter().all(CallCheck::is_accepted);
+                components.push(RecursiveComponent { words, calls });
+            }
+
+            for name in component {
+                bounds.insert(name, bound.clone());
+            }
+        }
+
+        TerminationCertificate {
+            word: word_def.name.clone(),
+            bound: accepted.then(|| bounds[word_def.name.as_str()].to_ordinal()),
+            components,
+        }
+    }
+
+    /// Count recursive calls in a token sequence
+    fn count_recursive_calls(&self, tokens: &[Token], word_name: &str) -> usize {
+        tokens
+            .iter()
+            .filter(|token| {
+                if let Token::Word(name) = token {
+                    name == word_name
+                } else {
+                    false
+                }
+            })
+            .count()
+    }
+
+    /// Check for decreasing patterns that suggest termination
+    fn has_decreasing_pattern(&self, tokens: &[Token]) -> bool {
+        // Look for patterns like:
+        // - Numerical decrements (n 1 -)
+        // - Conditional termination (0 = [...] [...] if)
+        // - List/structure decomposition patterns
+
+        let mut i = 0;
+        while i < tokens.len() {
+            // Pattern: number 1 -
+            if i + 2 < tokens.len() {
+                if let (Token::Literal(_), Token::Literal(Value::Nat(1)), Token::Word(op)) =
+                    (&tokens[i], &tokens[i + 1], &tokens[i + 2])
+                {
+                    if op == "-" {
+                        return true;
+                    }
+                }
+            }
+
+            // Pattern: 0 = (equality check with zero)
+            if i + 1 < tokens.len() {
+                if let (Token::Literal(Value::Nat(0)), Token::Word(op)) =
+                    (&tokens[i], &tokens[i + 1])
+                {
+                    if op == "=" || op == "<" {
+                        return true;
+                    }
+                }
+            }
+
+            i += 1;
+        }
+
+        false
+    }
+
+    /// Compare two ordinals by their Cantor normal forms
+    pub fn compare_ordinals(&self, a: &OrdinalValue, b: &OrdinalValue) -> std::cmp::Ordering {
+        a.cmp(b)
+    }
+
+    /// Add two ordinals
+    pub fn add_ordinals(
+        &self,
+        a: &OrdinalValue,
+        b: &OrdinalValue,
+    ) -> Result<OrdinalValue, OrdinalError> {
+        a.plus(b)
+    }
+
+    /// Multiply two ordinals
+    pub fn multiply_ordinals(
+        &self,
+        a: &OrdinalValue,
+        b: &OrdinalValue,
+    ) -> Result<OrdinalValue, OrdinalError> {
+        a.times(b)
+    }
+
+    /// Check if an ordinal sequence is strictly decreasing (well-founded)
+    pub fn is_well_founded(&self, sequence: &[OrdinalValue]) -> bool {
+        if sequence.len() <= 1 {
+            return true;
+        }
+
+        for i in 1..sequence.len() {
+            if self.compare_ordinals(&sequence[i - 1], &sequence[i]) != std::cmp::Ordering::Greater
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Verify global consistency of ordinal assignments
+    pub fn verify_global_consistency(
+        &self,
+        word_definitions: &[WordDefinition],
+    ) -> Result<(), OrdinalError> {
+        // Check that ordinal assignments are consistent across all definitions
+        // This would ensure no paradoxes in the type system
+
+        if self.strict_mode {
+            // Every non-axiom word must terminate given all the others
+            let verifier = OrdinalVerifier::strict().with_definitions(word_definitions);
+            for word_def in word_definitions.iter().filter(|def| !def.is_axiom) {
+                verifier.analyze_termination_strict(word_def)?;
+            }
+            Ok(())
+        } else {
+            // Mock verification - just check for obviously problematic cases
+            for word_def in word_definitions {
+                if word_def.ordinal_cost == OrdinalValue::Zero && !word_def.is_axiom {
+                    return Err(OrdinalError::ConsistencyFailed(format!(
+                        "Non-axiom word '{}' has zero ordinal cost",
+                        word_def.name
+                    )));
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+impl Default for OrdinalVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrdinalValue {
+    /// Rewrite this ordinal in Cantor normal form
+    ///
+    /// The result is `Zero`, `Finite(n)`, a single term `ω^e * c`, or a `Sum`
+    /// of such terms with strictly decreasing exponents, each exponent itself
+    /// in normal form. Every ordinal below ε₀ has exactly one such form.
+    pub fn normalize(&self) -> Result<OrdinalValue, OrdinalError> {
+        Ok(Cnf::from_ordinal(self, Overflow::Error)?.to_ordinal())
+    }
+
+    /// Ordinal addition `self + other`, in normal form
+    ///
+    /// Addition is not commutative: `1 + ω = ω` but `ω + 1 > ω`.
+    pub fn plus(&self, other: &OrdinalValue) -> Result<OrdinalValue, OrdinalError> {
+        let (a, b) = Cnf::pair(self, other)?;
+        Ok(a.plus(&b, Overflow::Error)?.to_ordinal())
+    }
+
+    /// Ordinal multiplication `self * other`, in normal form
+    ///
+    /// Multiplication is not commutative: `2 * ω = ω` but `ω * 2 = ω + ω`.
+    pub fn times(&self, other: &OrdinalValue) -> Result<OrdinalValue, OrdinalError> {
+        let (a, b) = Cnf::pair(self, other)?;
+        Ok(a.times(&b, Overflow::Error)?.to_ordinal())
+    }
+
+    /// Ordinal exponentiation `self ^ other`, in normal form
+    ///
+    /// For example `2 ^ ω = ω` and `2 ^ (ω + 1) = ω * 2`.
+    pub fn pow(&self, other: &OrdinalValue) -> Result<OrdinalValue, OrdinalError> {
+        let (a, b) = Cnf::pair(self, other)?;
+        Ok(a.pow(&b)?.to_ordinal())
+    }
+
+    /// Normal form used for comparison, saturating coefficients that overflow
+    fn comparison_key(&self) -> Cnf {
+        Cnf::from_ordinal(self, Overflow::Saturate).unwrap_or_default()
+    }
+}
+
+/// Ordinals are equal when their Cantor normal forms are, so `Zero` equals
+/// `Finite(0)` and `Sum([Finite(1), Omega])` equals `Omega`
+impl PartialEq for OrdinalValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.comparison_key() == other.comparison_key()
+    }
+}
+
+impl Eq for OrdinalValue {}
+
+impl PartialOrd for OrdinalValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdinalValue {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.comparison_key().cmp(&other.comparison_key())
+    }
+}
+
+/// What to do when a finite coefficient leaves the range of `u64`
+#[derive(Debug, Clone, Copy)]
+enum Overflow {
+    Error,
+    Saturate,
+}
+
+impl Overflow {
+    fn apply(self, result: Option<u64>, operation: &str) -> Result<u64, OrdinalError> {
+        match (result, self) {
+            (Some(n), _) => Ok(n),
+            (None, Overflow::Saturate) => Ok(u64::MAX),
+            (None, Overflow::Error) => Err(OrdinalError::Overflow(format!(
+                "coefficient too large in {}",
+                operation
+            ))),
+        }
+    }
+}
+
+/// An ordinal below ε₀ in Cantor normal form
+///
+/// `ω^e1 * c1 + ω^e2 * c2 + ...` is stored as `[(e1, c1), (e2, c2), ...]`
+/// with `e1 > e2 > ...` and every `c > 0`. With that invariant the derived
+/// lexicographic ordering is the ordinal ordering. Ordinal arithmetic and the
+/// running costs bounded by [`token_cost`] both use this form.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Cnf {
+    terms: Vec<(Cnf, u64)>,
+}
+
+impl Cnf {
+    /// Exponents of ω in a finite base raised to a huge finite power grow the
+    /// normal form without bound, so they are capped
+    const MAX_FINITE_EXPONENT: u64 = 1 << 16;
+
+    fn finite(n: u64) -> Self {
+        if n == 0 {
+            Cnf::default()
+        } else {
+            Cnf {
+                terms: vec![(Cnf::default(), n)],
+            }
+        }
+    }
+
+    /// ω^exponent
+    fn omega_power(exponent: Cnf) -> Self {
+        Cnf {
+            terms: vec![(exponent, 1)],
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The value, if this is a natural number
+    fn as_finite(&self) -> Option<u64> {
+        match self.terms.as_slice() {
+            [] => Some(0),
+            [(exponent, c)] if exponent.is_zero() => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn pair(a: &OrdinalValue, b: &OrdinalValue) -> Result<(Cnf, Cnf), OrdinalError> {
+        Ok((
+            Cnf::from_ordinal(a, Overflow::Error)?,
+            Cnf::from_ordinal(b, Overflow::Error)?,
+        ))
+    }
+
+    fn from_ordinal(ordinal: &OrdinalValue, overflow: Overflow) -> Result<Self, OrdinalError> {
+        match ordinal {
+            OrdinalValue::Zero => Ok(Cnf::default()),
+            OrdinalValue::Finite(n) => Ok(Cnf::finite(*n)),
+            OrdinalValue::Omega => Ok(Cnf::omega_power(Cnf::finite(1))),
+            OrdinalValue::OmegaPower(exponent) => {
+                Ok(Cnf::omega_power(Cnf::from_ordinal(exponent, overflow)?))
+            }
+            OrdinalValue::Sum(terms) => terms.iter().try_fold(Cnf::default(), |sum, term| {
+                sum.plus(&Cnf::from_ordinal(term, overflow)?, overflow)
+            }),
+            OrdinalValue::Product(a, b) => {
+                Cnf::from_ordinal(a, overflow)?.times(&Cnf::from_ordinal(b, overflow)?, overflow)
+            }
+        }
+    }
+
+    pub(crate) fn to_ordinal(&self) -> OrdinalValue {
+        let mut terms: Vec<OrdinalValue> = self
+            .terms
+            .iter()
+            .map(|(exponent, c)| {
+                let power = match exponent.as_finite() {
+                    Some(0) => return OrdinalValue::Finite(*c),
+                    Some(1) => OrdinalValue::Omega,
+                    _ => OrdinalValue::OmegaPower(Box::new(exponent.to_ordinal())),
+                };
+                if *c == 1 {
+                    power
+                } else {
+                    OrdinalValue::Product(Box::new(power), Box::new(OrdinalValue::Finite(*c)))
+                }
+            })
+            .collect();
+
+        match terms.len() {
+            0 => OrdinalValue::Zero,
+            1 => terms.remove(0),
+            _ => OrdinalValue::Sum(terms),
+        }
+    }
+
+    /// `self + other`: terms of `self` below the leading term of `other` are absorbed
+    fn plus(&self, other: &Cnf, overflow: Overflow) -> Result<Cnf, OrdinalError> {
+        let Some((lead, c)) = other.terms.first() else {
+            return Ok(self.clone());
+        };
+
+        let mut terms: Vec<(Cnf, u64)> = self
+            .terms
+            .iter()
+            .take_while(|(exponent, _)| exponent >= lead)
+            .cloned()
+            .collect();
+        match terms.last_mut() {
+            Some((exponent, sum)) if exponent == lead => {
+                *sum = overflow.apply(sum.checked_add(*c), "addition")?;
+            }
+            _ => terms.push((lead.clone(), *c)),
+        }
+        terms.extend(other.terms[1..].iter().cloned());
+        Ok(Cnf { terms })
+    }
+
+    /// `self * other`, distributing over the terms of `other` on the right
+    fn times(&self, other: &Cnf, overflow: Overflow) -> Result<Cnf, OrdinalError> {
+        let Some((lead, c)) = self.terms.first() else {
+            return Ok(Cnf::default());
+        };
+
+        let mut product = Cnf::default();
+        for (exponent, d) in &other.terms {
+            let term = if exponent.is_zero() {
+                // (ω^e * c + rest) * d = ω^e * (c * d) + rest
+                let mut terms = self.terms.clone();
+                terms[0].1 = overflow.apply(c.checked_mul(*d), "multiplication")?;
+                Cnf { terms }
+            } else {
+                // (ω^e * c + rest) * ω^f = ω^(e + f)
+                Cnf {
+                    terms: vec![(lead.plus(exponent, overflow)?, *d)],
+                }
+            };
+            product = product.plus(&term, overflow)?;
+        }
+        Ok(product)
+    }
+
+    /// `self ^ other`, splitting `other` into a limit part and a finite part
+    fn pow(&self, other: &Cnf) -> Result<Cnf, OrdinalError> {
+        let overflow = Overflow::Error;
+        if other.is_zero() {
+            return Ok(Cnf::finite(1));
+        }
+        if self.is_zero() || self.as_finite() == Some(1) {
+            return Ok(self.clone());
+        }
+
+        let (limit, k) = match other.terms.split_last() {
+            Some(((exponent, k), rest)) if exponent.is_zero() => (
+                Cnf {
+                    terms: rest.to_vec(),
+                },
+                *k,
+            ),
+            _ => (other.clone(), 0),
+        };
+
+        let (limit_power, finite_power) = match self.as_finite() {
+            Some(n) => {
+                // n^(ω * β) = ω^β, where ω * β = limit
+                let beta = Cnf {
+                    terms: limit
+                        .terms
+                        .iter()
+                        .map(|(exponent, c)| match exponent.as_finite() {
+                            Some(f) => (Cnf::finite(f - 1), *c),
+                            None => (exponent.clone(), *c),
+                        })
+                        .collect(),
+                };
+                let n_k = u32::try_from(k).ok().and_then(|k| n.checked_pow(k));
+                (
+                    Cnf::omega_power(beta),
+                    Cnf::finite(overflow.apply(n_k, "exponentiation")?),
+                )
+            }
+            None => {
+                // (ω^e * c + rest)^λ = ω^(e * λ) for a limit λ
+                let lead = &self.terms[0].0;
+                if self.terms.len() > 1 && k > Self::MAX_FINITE_EXPONENT {
+                    return Err(OrdinalError::Overflow(format!(
+                        "exponent {} is too large",
+                        k
+                    )));
+                }
+                (
+                    Cnf::omega_power(lead.times(&limit, overflow)?),
+                    self.pow_finite(k)?,
+                )
+            }
+        };
+
+        limit_power.times(&finite_power, overflow)
+    }
+
+    /// `self ^ k` by repeated squaring
+    fn pow_finite(&self, mut k: u64) -> Result<Cnf, OrdinalError> {
+        let mut base = self.clone();
+        let mut result = Cnf::finite(1);
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.times(&base, Overflow::Error)?;
+            }
+            k >>= 1;
+            if k > 0 {
+                base = base.times(&base, Overflow::Error)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Running costs, which saturate coefficients that leave the range of `u64`
+/// rather than failing
+impl Cnf {
+    fn omega() -> Self {
+        Cnf::omega_power(Cnf::finite(1))
+    }
+
+    /// The cost of a word, as recorded in its definition
+    pub(crate) fn cost_of(ordinal: &OrdinalValue) -> Self {
+        Cnf::from_ordinal(ordinal, Overflow::Saturate).unwrap_or_default()
+    }
+
+    /// The cost of running `self` and then `other`
+    fn then(&self, other: &Cnf) -> Cnf {
+        self.plus(other, Overflow::Saturate).unwrap_or_default()
+    }
+
+    /// The cost of running `self` `count` times, or an unbounded finite
+    /// number of times when the count is unknown
+    fn repeated(&self, count: Option<u64>) -> Cnf {
+        let count = count.map_or_else(Cnf::omega, Cnf::finite);
+        self.times(&count, Overflow::Saturate).unwrap_or_default()
+    }
+}
+
+/// The call graph between user-defined words reachable from one word
+struct CallGraph<'a> {
+    /// Calls made by each reachable word, in body order
+    calls: HashMap<&'a str, Vec<CallCheck>>,
+    /// Reachable words in discovery order
+    order: Vec<&'a str>,
+}
+
+impl<'a> CallGraph<'a> {
+    fn build(root: &'a str, definitions: &HashMap<&'a str, &'a WordDefinition>) -> Self {
+        let mut graph = CallGraph {
+            calls: HashMap::new(),
+            order: Vec::new(),
+        };
+        let mut pending = vec![root];
+
+        while let Some(name) = pending.pop() {
+            if graph.calls.contains_key(name) {
+                continue;
+            }
+            let Some((&name, word_def)) = definitions.get_key_value(name) else {
+                continue;
+            };
+            let calls = CallCollector::collect(name, &word_def.body, definitions);
+            for call in &calls {
+                if let Some((&callee, _)) = definitions.get_key_value(call.callee.as_str()) {
+                    pending.push(callee);
+                }
+            }
+            graph.calls.insert(name, calls);
+            graph.order.push(name);
+        }
+
+        graph
+    }
+
+    /// Strongly connected components, each listed after every component it calls
+    fn components(&self) -> Vec<Vec<&'a str>> {
+        struct Tarjan<'g, 'a> {
+            graph: &'g CallGraph<'a>,
+            index: HashMap<&'a str, usize>,
+            lowlink: HashMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            on_stack: HashSet<&'a str>,
+            components: Vec<Vec<&'a str>>,
+        }
+
+        impl<'a> Tarjan<'_, 'a> {
+            fn visit(&mut self, word: &'a str) {
+                let index = self.index.len();
+                self.index.insert(word, index);
+                self.lowlink.insert(word, index);
+                self.stack.push(word);
+                self.on_stack.insert(word);
+
+                for call in &self.graph.calls[word] {
+                    let Some((&callee, _)) = self.graph.calls.get_key_value(call.callee.as_str())
+                    else {
+                        continue;
+                    };
+                    if !self.index.contains_key(callee) {
+                        self.visit(callee);
+                        let low = self.lowlink[word].min(self.lowlink[callee]);
+                        self.lowlink.insert(word, low);
+                    } else if self.on_stack.contains(callee) {
+                        let low = self.lowlink[word].min(self.index[callee]);
+                        self.lowlink.insert(word, low);
+                    }
+                }
+
+                if self.lowlink[word] == self.index[word] {
+                    let mut component = Vec::new();
+                    while let Some(member) = self.stack.pop() {
+                        self.on_stack.remove(member);
+                        component.push(member);
+                        if member == word {
+                            break;
+                        }
+                    }
+                    self.components.push(component);
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+        for &word in &self.order {
+            if !tarjan.index.contains_key(word) {
+                tarjan.visit(word);
+            }
+        }
+        tarjan.components
+    }
+}
+
+/// What strict analysis knows about one value on the stack
+#[derive(Debug, Clone, PartialEq)]
+enum Slot<'t> {
+    /// The measure: the top of the stack when the word was entered, divided
+    /// by `divisor` and then shifted by `offset`
+    Measure {
+        divisor: u64,
+        offset: i64,
+    },
+    /// A comparison involving the measure, with the operator and literal
+    /// when the unchanged measure was compared with a literal
+    Test {
+        comparison: Option<(&'t str, u64)>,
+        negated: bool,
+    },
+    Literal(u64),
+    /// A quotation consumed by the conditional that follows it
+    Branch(&'t [Token]),
+    Unknown,
+}
+
+impl Slot<'_> {
+    /// The measure unchanged, as it was on entry
+    const ENTRY: Slot<'static> = Slot::Measure {
+        divisor: 1,
+        offset: 0,
+    };
+
+    /// The guard for the `taken` branch of a conditional `word` on this
+    /// slot, if it tests the measure
+    fn guard<'t>(&self, word: &'t str, taken: bool) -> Option<Guard<'t>> {
+        matches!(self, Slot::Test { .. }).then(|| Guard {
+            word,
+            excludes_zero: self.excludes_zero(taken),
+        })
+    }
+
+    /// Whether taking the `taken` branch of this test rules out a zero measure
+    fn excludes_zero(&self, taken: bool) -> bool {
+        let Slot::Test {
+            comparison: Some((op, k)),
+            negated,
+        } = self
+        else {
+            return false;
+        };
+        match (*op, taken != *negated) {
+            ("=", true) | (">=", true) | ("<>", false) => *k >= 1,
+            ("=", false) | ("<>", true) => *k == 0,
+            (">", true) | ("<=", false) => true,
+            ("<", false) => *k >= 1,
+            _ => false,
+        }
+    }
+}
+
+/// The conditional on the measure that a call is nested under
+#[derive(Debug, Clone, Copy)]
+struct Guard<'t> {
+    word: &'t str,
+    /// Whether the branch taken rules out a zero measure
+    excludes_zero: bool,
+}
+
+/// Records the calls between user-defined words, following the measure
+/// through each word body
+struct CallCollector<'d, 'a> {
+    caller: &'a str,
+    definitions: &'d HashMap<&'a str, &'a WordDefinition>,
+    calls: Vec<CallCheck>,
+}
+
+impl<'a> CallCollector<'_, 'a> {
+    /// Record the calls in a word body, starting from its measure
+    fn collect(
+        caller: &'a str,
+        body: &'a [Token],
+        definitions: &HashMap<&'a str, &'a WordDefinition>,
+    ) -> Vec<CallCheck> {
+        let mut collector = CallCollector {
+            caller,
+            definitions,
+            calls: Vec::new(),
+        };
+        collector.walk(body, &mut vec![Slot::ENTRY], None, &HashMap::new());
+        collector.calls
+    }
+
+    /// Follow `tokens` on the known top of the stack, which is everything
+    /// the analysis knows about it; the values below are unknown
+    fn walk<'t>(
+        &mut self,
+        tokens: &'t [Token],
+        stack: &mut Vec<Slot<'t>>,
+        guard: Option<Guard<'t>>,
+        bindings: &HashMap<&'t str, Slot<'t>>,
+    ) {
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                Token::Literal(Value::Nat(n)) => stack.push(Slot::Literal(*n)),
+                Token::Literal(Value::Quote(body)) => {
+                    // Conditionals run their quotes on the current stack
+                    let consumer = tokens[i + 1..]
+                        .iter()
+                        .find(|t| !matches!(t, Token::Literal(Value::Quote(_))));
+                    match consumer {
+                        Some(Token::Word(w)) if matches!(w.as_str(), "if" | "when" | "unless") => {
+                            stack.push(Slot::Branch(body))
+                        }
+                        _ => {
+                            self.walk(body, &mut Vec::new(), guard, bindings);
+                            stack.push(Slot::Unknown);
+                        }
+                    }
+                }
+                Token::Literal(_) => stack.push(Slot::Unknown),
+                Token::Word(word) => self.word(word, stack, guard, bindings),
+                Token::MatchExpression { value, arms } => {
+                    match value.as_ref() {
+                        Token::Literal(Value::Quote(body)) => {
+                            self.walk(body, stack, guard, bindings)
+                        }
+                        value => self.walk(std::slice::from_ref(value), stack, guard, bindings),
+                    }
+                    let scrutinee = self.pop(stack, guard);
+                    let measured = matches!(scrutinee, Slot::Measure { .. });
+                    let mut zero_matched = false;
+                    let mut after = Vec::new();
+                    for arm in arms {
+                        let mut arm_stack = stack.clone();
+                        let mut arm_bindings = bindings.clone();
+                        if let Pattern::Variable(name) = &arm.pattern {
+                            arm_bindings.insert(name.as_str(), scrutinee.clone());
+                        }
+                        let excludes_zero = scrutinee == Slot::ENTRY
+                            && match &arm.pattern {
+                                Pattern::Literal(Value::Nat(n)) => *n > 0,
+                                _ => zero_matched,
+                            };
+                        zero_matched |= arm.pattern == Pattern::Literal(Value::Nat(0));
+                        let arm_guard = if measured {
+                            Some(Guard {
+                                word: "match",
+                                excludes_zero,
+                            })
+                        } else {
+                            guard
+                        };
+                        self.walk(&arm.body, &mut arm_stack, arm_guard, &arm_bindings);
+                        after.push(arm_stack);
+                    }
+                    Self::join(stack, after);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn word<'t>(
+        &mut self,
+        word: &'t str,
+        stack: &mut Vec<Slot<'t>>,
+        guard: Option<Guard<'t>>,
+        bindings: &HashMap<&'t str, Slot<'t>>,
+    ) {
+        if let Some(slot) = bindings.get(word) {
+            stack.push(slot.clone());
+            return;
+        }
+        if let Some(word_def) = self.definitions.get(word) {
+            self.calls.push(CallCheck {
+                caller: self.caller.to_string(),
+                callee: word.to_string(),
+                guard: guard.map(|g| g.word.to_string()),
+                decrease: Self::decrease(stack.last(), guard),
+            });
+            let signature = &word_def.signature;
+            self.apply(
+                stack,
+                guard,
+                signature.inputs.len(),
+                signature.outputs.len(),
+            );
+            return;
+        }
+
+        match word {
+            "dup" => {
+                let top = self.pop(stack, guard);
+                stack.extend([top.clone(), top]);
+            }
+            "dup2" => {
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.extend([a.clone(), b.clone(), a, b]);
+            }
+            "drop" => {
+                self.pop(stack, guard);
+            }
+            "swap" => {
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.extend([b, a]);
+            }
+            "over" => {
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.extend([a.clone(), b, a]);
+            }
+            "rot" => {
+                let c = self.pop(stack, guard);
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.extend([b, c, a]);
+            }
+            "nip" => {
+                let b = self.pop(stack, guard);
+                self.pop(stack, guard);
+                stack.push(b);
+            }
+            "tuck" => {
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.extend([b.clone(), a, b]);
+            }
+            "+" | "-" | "*" | "/" => {
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.push(Self::arithmetic(word, a, b));
+            }
+            "=" | "<>" | "<" | ">" | "<=" | ">=" => {
+                let b = self.pop(stack, guard);
+                let a = self.pop(stack, guard);
+                stack.push(Self::compare(word, a, b));
+            }
+            "not" => match self.pop(stack, guard) {
+                Slot::Test {
+                    comparison,
+                    negated,
+                } => stack.push(Slot::Test {
+                    comparison,
+                    negated: !negated,
+                }),
+                _ => stack.push(Slot::Unknown),
+            },
+            "if" => {
+                let else_branch = stack.pop().unwrap_or(Slot::Unknown);
+                let then_branch = stack.pop().unwrap_or(Slot::Unknown);
+                let condition = self.pop(stack, guard);
+                let after = [(then_branch, true), (else_branch, false)]
+                    .into_iter()
+                    .map(|(branch, taken)| {
+                        let guard = condition.guard(word, taken).or(guard);
+                        self.branch(branch, stack.clone(), guard, bindings)
+                    })
+                    .collect();
+                Self::join(stack, after);
+            }
+            "when" | "unless" => {
+                let body = stack.pop().unwrap_or(Slot::Unknown);
+                let condition = self.pop(stack, guard);
+                let guard = condition.guard(word, word == "when").or(guard);
+                let body_stack = self.branch(body, stack.clone(), guard, bindings);
+                Self::join(stack, vec![body_stack, stack.clone()]);
+            }
+            _ => match core_signatures().get(word) {
+                Some(signature) => self.apply(
+                    stack,
+                    guard,
+                    signature.inputs.len(),
+                    signature.outputs.len(),
+                ),
+                // Nothing is known about the stack after an unknown word
+                None => stack.clear(),
+            },
+        }
+    }
+
+    /// Follow one branch of a conditional from `stack`, returning the stack
+    /// after it
+    fn branch<'t>(
+        &mut self,
+        branch: Slot<'t>,
+        mut stack: Vec<Slot<'t>>,
+        guard: Option<Guard<'t>>,
+        bindings: &HashMap<&'t str, Slot<'t>>,
+    ) -> Vec<Slot<'t>> {
+        if let Slot::Branch(body) = branch {
+            self.walk(body, &mut stack, guard, bindings);
+        }
+        stack
+    }
+
+    /// Pop the top slot; quotes popped by anything but their conditional
+    /// still have their calls recorded
+    fn pop<'t>(&mut self, stack: &mut Vec<Slot<'t>>, guard: Option<Guard<'t>>) -> Slot<'t> {
+        match stack.pop() {
+            Some(Slot::Branch(body)) => {
+                self.walk(body, &mut Vec::new(), guard, &HashMap::new());
+                Slot::Branch(body)
+            }
+            Some(slot) => slot,
+            None => Slot::Unknown,
+        }
+    }
+
+    /// Apply a stack effect about which only the arity is known
+    fn apply<'t>(
+        &mut self,
+        stack: &mut Vec<Slot<'t>>,
+        guard: Option<Guard<'t>>,
+        inputs: usize,
+        outputs: usize,
+    ) {
+        for _ in 0..inputs {
+            self.pop(stack, guard);
+        }
+        stack.extend(std::iter::repeat_n(Slot::Unknown, outputs));
+    }
+
+    /// The stack after paths that rejoin, known only where they agree
+    fn join<'t>(stack: &mut Vec<Slot<'t>>, paths: Vec<Vec<Slot<'t>>>) {
+        let mut paths = paths.into_iter();
+        let first = paths.next().unwrap_or_default();
+        *stack = if paths.all(|path| path == first) {
+            first
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn arithmetic<'t>(op: &str, a: Slot<'t>, b: Slot<'t>) -> Slot<'t> {
+        match (op, a, b) {
+            ("+", Slot::Literal(x), Slot::Literal(y)) => {
+                x.checked_add(y).map_or(Slot::Unknown, Slot::Literal)
+            }
+            ("-", Slot::Literal(x), Slot::Literal(y)) => {
+                x.checked_sub(y).map_or(Slot::Unknown, Slot::Literal)
+            }
+            ("*", Slot::Literal(x), Slot::Literal(y)) => {
+                x.checked_mul(y).map_or(Slot::Unknown, Slot::Literal)
+            }
+            ("+", Slot::Measure { divisor, offset }, Slot::Literal(k))
+            | ("+", Slot::Literal(k), Slot::Measure { divisor, offset }) => i64::try_from(k)
+                .ok()
+                .and_then(|k| offset.checked_add(k))
+                .map_or(Slot::Unknown, |offset| Slot::Measure { divisor, offset }),
+            ("-", Slot::Measure { divisor, offset }, Slot::Literal(k)) => i64::try_from(k)
+                .ok()
+                .and_then(|k| offset.checked_sub(k))
+                .map_or(Slot::Unknown, |offset| Slot::Measure { divisor, offset }),
+            // (n / d + o) / k is at most n / (d * k) when o is not positive
+            ("/", Slot::Measure { divisor, offset }, Slot::Literal(k)) if k >= 2 && offset <= 0 => {
+                divisor
+                    .checked_mul(k)
+                    .map_or(Slot::Unknown, |divisor| Slot::Measure {
+                        divisor,
+                        offset: 0,
+                    })
+            }
+            _ => Slot::Unknown,
+        }
+    }
+
+    fn compare<'t>(op: &'t str, a: Slot<'t>, b: Slot<'t>) -> Slot<'t> {
+        let test = |comparison| Slot::Test {
+            comparison,
+            negated: false,
+        };
+        match (a, b) {
+            (Slot::ENTRY, Slot::Literal(k)) => test(Some((op, k))),
+            (Slot::Literal(k), Slot::ENTRY) => {
+                // `k < n` tests `n > k`
+                let flipped = match op {
+                    "<" => ">",
+                    ">" => "<",
+                    "<=" => ">=",
+                    ">=" => "<=",
+                    op => op,
+                };
+                test(Some((flipped, k)))
+            }
+            (Slot::Measure { .. }, _) | (_, Slot::Measure { .. }) => test(None),
+            _ => Slot::Unknown,
+        }
+    }
+
+    /// How the measure on top of the stack has strictly decreased since the
+    /// word was entered, if it has
+    ///
+    /// Subtracting is a decrease on its own, since `-` fails rather than go
+    /// below zero. Dividing leaves zero unchanged, so it only counts when the
+    /// guard rules out a zero measure.
+    fn decrease(top: Option<&Slot>, guard: Option<Guard>) -> Option<String> {
+        let Some(&Slot::Measure { divisor, offset }) = top else {
+            return None;
+        };
+        let excludes_zero = guard.is_some_and(|g| g.excludes_zero);
+        match (divisor, offset) {
+            (1, offset) if offset < 0 => Some(format!("{} -", offset.unsigned_abs())),
+            (1, _) => None,
+            (divisor, 0) if excludes_zero => Some(format!("{} /", divisor)),
+            (divisor, offset) if offset < 0 => {
+                Some(format!("{} / {} -", divisor, offset.unsigned_abs()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Compute an ordinal bound on the number of steps a token sequence takes
+///
+/// `words` gives the cost of user-defined words; any other word is a
+/// primitive step costing 1. See [`crate::VirtualMachine::ordinal_cost`].
+pub(crate) fn token_cost(tokens: &[Token], words: &dyn Fn(&str) -> Option<Cnf>) -> Cnf {
+    let mut total = Cnf::default();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let step = match token {
+            Token::Word(word) => word_cost(word, &tokens[..i], words),
+            Token::MatchExpression { value, arms } => {
+                let scrutinee = match value.as_ref() {
+                    Token::Literal(Value::Quote(tokens)) => token_cost(tokens, words),
+                    _ => Cnf::finite(1),
+                };
+                let arm = arms
+                    .iter()
+                    .map(|arm| token_cost(&arm.body, words))
+                    .fold(Cnf::default(), Cnf::max);
+                scrutinee.then(&Cnf::finite(1)).then(&arm)
+            }
+            Token::Comment(_) => Cnf::default(),
+            _ => Cnf::finite(1),
+        };
+        total = total.then(&step);
+    }
+
+    total
+}
+
+/// Cost of a single word, given the tokens that precede it
+fn word_cost(word: &str, preceding: &[Token], words: &dyn Fn(&str) -> Option<Cnf>) -> Cnf {
+    let one = Cnf::finite(1);
+    let quote_at = |back: usize| -> Option<&Vec<Token>> {
+        match preceding.len().checked_sub(back).map(|i| &preceding[i]) {
+            Some(Token::Literal(Value::Quote(tokens))) => Some(tokens),
+            _ => None,
+        }
+    };
+    let nat_at = |back: usize| -> Option<u64> {
+        match preceding.len().checked_sub(back).map(|i| &preceding[i]) {
+            Some(Token::Literal(Value::Nat(n))) => Some(*n),
+            _ => None,
+        }
+    };
+    // Length of a list built by a literal `start end range` ending `back` tokens ago
+    let range_len_at = |back: usize| -> Option<u64> {
+        match preceding.len().checked_sub(back).map(|i| &preceding[i]) {
+            Some(Token::Word(w)) if w == "range" => {
+                let end = nat_at(back + 1)?;
+                let start = nat_at(back + 2)?;
+                Some(end.saturating_sub(start))
+            }
+            _ => None,
+        }
+    };
+
+    match word {
+        "call" | "when" | "unless" => {
+            one.then(&quote_at(1).map_or(one.clone(), |q| token_cost(q, words)))
+        }
+        "if" => {
+            let then_cost = quote_at(2).map_or(one.clone(), |q| token_cost(q, words));
+            let else_cost = quote_at(1).map_or(one.clone(), |q| token_cost(q, words));
+            one.then(&then_cost.max(else_cost))
+        }
+        "times" => {
+            let body = quote_at(1).map_or(one.clone(), |q| token_cost(q, words));
+            one.then(&body.repeated(nat_at(2)))
+        }
+        "while" => {
+            let condition = quote_at(2).map_or(one.clone(), |q| token_cost(q, words));
+            let body = quote_at(1).map_or(one.clone(), |q| token_cost(q, words));
+            let iteration = condition.then(&body);
+            one.then(&iteration.repeated(nat_at(3))).then(&condition)
+        }
+        "each" | "map" | "filter" => {
+            let body = quote_at(1).map_or(one.clone(), |q| token_cost(q, words));
+            one.then(&body.repeated(range_len_at(2)))
+        }
+        "fold" => {
+            let body = quote_at(1).map_or(one.clone(), |q| token_cost(q, words));
+            one.then(&body.repeated(range_len_at(3)))
+        }
+        "range" => match (nat_at(2), nat_at(1)) {
+            (Some(start), Some(end)) => one.then(&Cnf::finite(end.saturating_sub(start))),
+            _ => one.then(&one.repeated(None)),
+        },
+        _ => words(word).unwrap_or(one),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Type, TypeSignature};
+
+    #[test]
+    fn test_mock_verifier_non_recursive() {
+        let verifier = OrdinalVerifier::new();
+        let word_def = WordDefinition {
+            name: "double".to_string(),
+            signature: TypeSignature {
+                inputs: vec![Type::Nat],
+                outputs: vec![Type::Nat],
+            },
+            body: vec![Token::Literal(Value::Nat(2)), Token::Word("*".to_string())],
+            is_axiom: false,
+            ordinal_cost: OrdinalValue::Finite(1),
+        };
+
+        let result = verifier.verify_termination(&word_def);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), OrdinalValue::Finite(1));
+    }
+
+    fn word(name: &str, source: &str) -> WordDefinition {
+        let tokens = crate::lexer::Lexer::new(source).tokenize().unwrap();
+        WordDefinition {
+            name: name.to_string(),
+            signature: TypeSignature {
+                inputs: vec![Type::Nat],
+                outputs: vec![Type::Nat],
+            },
+            body: crate::parser::process_quotes(tokens).unwrap(),
+            is_axiom: false,
+            ordinal_cost: OrdinalValue::Finite(1),
+        }
+    }
+
+    #[test]
+    fn test_strict_accepts_structural_recursion() {
+        let fact = word("fact", "dup 0 = [drop 1] [dup 1 - fact *] if");
+        let verifier = OrdinalVerifier::strict();
+
+        assert_eq!(verifier.verify_termination(&fact), Ok(OrdinalValue::Omega));
+
+        let certificate = verifier.certify(&fact);
+        assert_eq!(certificate.components.len(), 1);
+        assert!(certificate
+            .to_string()
+            .contains("fact -> fact: guarded by 'if', decreases by '1 -'"));
+    }
+
+    #[test]
+    fn test_strict_rejects_unguarded_recursion() {
+        let spin = word("spin", "dup spin");
+
+        let certificate = OrdinalVerifier::strict().certify(&spin);
+        assert!(!certificate.is_accepted());
+        let text = certificate.to_string();
+        assert!(text.contains("verdict: rejected"));
+        assert!(text.contains("spin -> spin: not guarded by a conditional"));
+
+        match OrdinalVerifier::strict().verify_termination(&spin) {
+            Err(OrdinalError::NonTerminating(message)) => assert_eq!(message, text),
+            other => panic!("Expected a rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_mutual_recursion() {
+        let even = word("even", "dup 0 = [drop true] [1 - odd] if");
+        let odd = word("odd", "dup 0 = [drop false] [1 - even] if");
+        let verifier = OrdinalVerifier::strict().with_definitions([&odd]);
+
+        let certificate = verifier.certify(&even);
+        assert_eq!(certificate.bound, Some(OrdinalValue::Omega));
+        assert_eq!(certificate.components[0].words, vec!["even", "odd"]);
+        assert_eq!(certificate.components[0].calls.len(), 2);
+
+        // Growing the argument on one side of the cycle breaks the proof
+        let odd = word("odd", "dup 0 = [drop false] [1 + even] if");
+        let verifier = OrdinalVerifier::strict().with_definitions([&odd]);
+        let certificate = verifier.certify(&even);
+        assert!(!certificate.is_accepted());
+        assert!(certificate
+            .to_string()
+            .contains("odd -> even: no decreasing argument"));
+    }
+
+    #[test]
+    fn test_strict_follows_the_measure() {
+        let verifier = OrdinalVerifier::strict();
+        let verdict = |word_def: &WordDefinition| verifier.certify(word_def).to_string();
+
+        // `2 + 1 -` ends with a decrement but grows the measure overall
+        let grows = word("f", "dup 0 = [ ] [ 2 + 1 - f ] if");
+        assert!(!verifier.certify(&grows).is_accepted());
+        assert!(verdict(&grows).contains("f -> f: no decreasing argument"));
+
+        let shrinks = word("f", "dup 0 = [ ] [ 1 + 2 - f ] if");
+        assert_eq!(verifier.certify(&shrinks).bound, Some(OrdinalValue::Omega));
+        assert!(verdict(&shrinks).contains("decreases by '1 -'"));
+
+        // The guard must test the measure, not some other value
+        let parity = word("g", "dup 2 mod 0 = [ ] [ 1 - g ] if");
+        assert!(verdict(&parity).contains("g -> g: not guarded by a conditional on the measure"));
+
+        // Halving only decreases a measure the guard has ruled out being 0
+        let halve = word("h", "dup 0 = [ ] [ 2 / h ] if");
+        assert!(verdict(&halve).contains("h -> h: guarded by 'if', decreases by '2 /'"));
+        let halve_zero = word("h", "dup 5 < [ 2 / h ] [ ] if");
+        assert!(!verifier.certify(&halve_zero).is_accepted());
+    }
+
+    #[test]
+    fn test_strict_bounds_compose() {
+        let fact = word("fact", "dup 0 = [drop 1] [dup 1 - fact *] if");
+        let double = word("double", "2 *");
+        let verifier = OrdinalVerifier::strict().with_definitions([&fact, &double]);
+
+        // Non-recursive words get a finite bound
+        assert_eq!(
+            verifier.certify(&double).bound,
+            Some(OrdinalValue::Finite(2))
+        );
+
+        // Recursion over a word bounded by ω is bounded by ω^2
+        let facts = word("facts", "dup 0 = [drop 0] [dup fact swap 1 - facts +] if");
+        assert_eq!(
+            verifier.certify(&facts).bound,
+            Some(OrdinalValue::OmegaPower(Box::new(OrdinalValue::Finite(2))))
+        );
+        assert!(verifier
+            .verify_global_consistency(&[fact, double, facts])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_ordinal_comparison() {
+        let verifier = OrdinalVerifier::new();
+
+        assert_eq!(
+            verifier.compare_ordinals(&OrdinalValue::Finite(1), &OrdinalValue::Finite(2)),
+            std::cmp::Ordering::Less
+        );
+
+        assert_eq!(
+            verifier.compare_ordinals(&OrdinalValue::Finite(5), &OrdinalValue::Omega),
+            std::cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_ordinal_addition() {
+        let verifier = OrdinalVerifier::new();
+
+        let result = verifier.add_ordinals(&OrdinalValue::Finite(3), &OrdinalValue::Finite(4));
+        assert_eq!(result.unwrap(), OrdinalValue::Finite(7));
+
+        let result = verifier.add_ordinals(&OrdinalValue::Finite(3), &OrdinalValue::Omega);
+        assert_eq!(result.unwrap(), OrdinalValue::Omega);
+    }
+
+    #[test]
+    fn test_well_founded_sequence() {
+        let verifier = OrdinalVerifier::new();
+
+        let decreasing = vec![
+            OrdinalValue::Finite(5),
+            OrdinalValue::Finite(3),
+            OrdinalValue::Finite(1),
+            OrdinalValue::Zero,
+        ];
+
+        assert!(verifier.is_well_founded(&decreasing));
+
+        let increasing = vec![
+            OrdinalValue::Finite(1),
+            OrdinalValue::Finite(3),
+            OrdinalValue::Finite(5),
+        ];
+
+        assert!(!verifier.is_well_founded(&increasing));
+    }
+
+    fn omega_power(exponent: OrdinalValue) -> OrdinalValue {
+        OrdinalValue::OmegaPower(Box::new(exponent))
+    }
+
+    #[test]
+    fn test_cnf_arithmetic_is_not_commutative() {
+        let omega = OrdinalValue::Omega;
+        let one = OrdinalValue::Finite(1);
+        let two = OrdinalValue::Finite(2);
+
+        assert_eq!(one.plus(&omega).unwrap(), omega);
+        assert_eq!(
+            omega.plus(&one).unwrap(),
+            OrdinalValue::Sum(vec![omega.clone(), one.clone()])
+        );
+        assert_eq!(two.times(&omega).unwrap(), omega);
+        assert_eq!(omega.times(&two).unwrap().to_string(), "ω * 2");
+        assert_eq!(two.pow(&omega).unwrap(), omega);
+        assert_eq!(omega.pow(&two).unwrap(), omega_power(two.clone()));
+
+        // (ω + 1) * (ω + 1) = ω^2 + ω + 1
+        let succ = omega.plus(&one).unwrap();
+        assert_eq!(succ.times(&succ).unwrap().to_string(), "ω^2 + ω + 1");
+
+        // (ω + 1)^ω = ω^ω and 2^(ω + 1) = ω * 2
+        assert_eq!(succ.pow(&omega).unwrap(), omega_power(omega.clone()));
+        assert_eq!(two.pow(&succ).unwrap().to_string(), "ω * 2");
+
+        // ω^(ω + 1) keeps a compound exponent
+        assert_eq!(omega.pow(&succ).unwrap().to_string(), "ω^(ω + 1)");
+    }
+
+    #[test]
+    fn test_cnf_ordering_and_normalization() {
+        let omega = OrdinalValue::Omega;
+        let omega_squared = omega_power(OrdinalValue::Finite(2));
+        let omega_omega = omega_power(omega.clone());
+
+        let mut ordinals = [
+            omega_omega.clone(),
+            OrdinalValue::Sum(vec![omega_squared.clone(), OrdinalValue::Finite(3)]),
+            OrdinalValue::Finite(7),
+            omega.clone(),
+            omega_squared.clone(),
+            OrdinalValue::Zero,
+        ];
+        ordinals.sort();
+        let sorted: Vec<String> = ordinals.iter().map(|o| o.to_string()).collect();
+        assert_eq!(sorted, vec!["0", "7", "ω", "ω^2", "ω^2 + 3", "ω^ω"]);
+
+        // Non-normal forms compare by value
+        let absorbed = OrdinalValue::Sum(vec![OrdinalValue::Finite(5), omega.clone()]);
+        assert_eq!(absorbed, omega);
+        assert_eq!(absorbed.normalize().unwrap(), OrdinalValue::Omega);
+        assert_eq!(OrdinalValue::Finite(0), OrdinalValue::Zero);
+        assert!(omega_squared < omega_omega);
+
+        let huge = OrdinalValue::Finite(u64::MAX);
+        assert!(matches!(
+            huge.plus(&OrdinalValue::Finite(1)),
+            Err(OrdinalError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_cost_keeps_transfinite_exponents() {
+        let omega_omega = omega_power(OrdinalValue::Omega);
+        let words = |word: &str| (word == "big").then(|| Cnf::cost_of(&omega_omega));
+        let tokens = crate::lexer::Lexer::new("big 3 [ big ] times")
+            .tokenize()
+            .unwrap();
+        let tokens = crate::parser::process_quotes(tokens).unwrap();
+
+        let cost = token_cost(&tokens, &words).to_ordinal();
+        assert!(cost > omega_omega);
+        assert_eq!(cost.to_string(), "ω^ω * 4");
+    }
+}
@@ -0,0 +1,1037 @@
+//! Parser for C∀O (Kao) source code
+//!
+//! This module handles parsing of tokenized C∀O code into executable structures.
+//! It supports the concatenative syntax with postfix notation.
+
+use crate::lexer::{LexError, Lexer};
+use crate::theorem::Theorem;
+use crate::type_inference::TypeInferer;
+use crate::types::{
+    DataConstructor, DataDefinition, MatchArm, OrdinalValue, Span, Spanned, SpannedToken, Token,
+    Type, TypeDefinition, TypeSignature, Value, WordDefinition,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Errors that can occur during parsing
+#[derive(Debug, Clone)]
+pub enum ParseError {
+    /// Lexical error during tokenization
+    LexError(LexError),
+    /// Unexpected token
+    UnexpectedToken(Token, String),
+    /// Unexpected end of input
+    UnexpectedEof(String),
+    /// Missing token
+    MissingToken(String),
+    /// Invalid type signature
+    InvalidTypeSignature(String),
+    /// Invalid word definition
+    InvalidWordDefinition(String),
+    /// Invalid type definition
+    InvalidTypeDefinition(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::LexError(e) => write!(f, "Lexical error: {}", e),
+            ParseError::UnexpectedToken(token, expected) => {
+                write!(f, "Unexpected token '{}', expected {}", token, expected)
+            }
+            ParseError::UnexpectedEof(expected) => {
+                write!(f, "Unexpected end of input, expected {}", expected)
+            }
+            ParseError::MissingToken(expected) => {
+                write!(f, "Missing {}", expected)
+            }
+            ParseError::InvalidTypeSignature(msg) => {
+                write!(f, "Invalid type signature: {}", msg)
+            }
+            ParseError::InvalidWordDefinition(msg) => {
+                write!(f, "Invalid word definition: {}", msg)
+            }
+            ParseError::InvalidTypeDefinition(msg) => {
+                write!(f, "Invalid type definition: {}", msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<LexError> for ParseError {
+    fn from(error: LexError) -> Self {
+        ParseError::LexError(error)
+    }
+}
+
+/// Parsed C∀O statements
+#[derive(Debug, Clone)]
+pub enum Statement {
+    /// A sequence of tokens to execute
+    Expression(Vec<Token>),
+    /// A type signature declaration
+    TypeSignatureDecl {
+        name: String,
+        signature: TypeSignature,
+    },
+    /// A word definition
+    WordDefinition(WordDefinition),
+    /// A type definition
+    TypeDefinition(TypeDefinition),
+    /// A sum type declared with `data`
+    DataDefinition(DataDefinition),
+    /// An axiom declaration
+    AxiomDeclaration {
+        name: String,
+        signature: TypeSignature,
+    },
+    /// A theorem or lemma relating two quotations
+    Theorem(Theorem),
+}
+
+/// A statement with its source span, and the span of each token when it is
+/// an expression
+pub type SpannedStatement = (Spanned<Statement>, Vec<Span>);
+
+/// The parser for C∀O source code
+pub struct Parser {
+    tokens: Vec<Token>,
+    spans: Vec<Span>,
+    position: usize,
+    /// The span of each token of the last expression statement parsed
+    expression_spans: Vec<Span>,
+    type_inferer: TypeInferer,
+}
+
+impl Parser {
+    /// Create a new parser from source code
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let mut lexer = Lexer::new(input);
+        Ok(Self::from_tokens(lexer.tokenize_spanned()?))
+    }
+
+    /// Create a parser for tokens that have already been lexed
+    pub fn from_tokens(tokens: Vec<SpannedToken>) -> Self {
+        let (tokens, spans) = tokens
+            .into_iter()
+            .map(|token| (token.node, token.span))
+            .unzip();
+        Parser {
+            tokens,
+            spans,
+            position: 0,
+            expression_spans: Vec::new(),
+            type_inferer: TypeInferer::new(),
+        }
+    }
+
+    /// Get the current token without advancing
+    fn current_token(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    /// Advance to the next token
+    fn advance(&mut self) -> Option<&Token> {
+        self.position += 1;
+        self.current_token()
+    }
+
+    /// Parse a type from tokens, such as `Nat`, `a` or `Result<Nat, String>`
+    fn parse_type(&mut self) -> Result<Type, ParseError> {
+        let name = match self.current_token() {
+            Some(Token::Word(name)) => name.clone(),
+            Some(token) => {
+                return Err(ParseError::UnexpectedToken(
+                    token.clone(),
+                    "type name".to_string(),
+                ))
+            }
+            None => return Err(ParseError::UnexpectedEof("type name".to_string())),
+        };
+        self.advance();
+
+        let mut params = Vec::new();
+        if matches!(self.current_token(), Some(Token::Word(w)) if w == "<") {
+            self.advance();
+            loop {
+                params.push(self.parse_type()?);
+                match self.current_token() {
+                    Some(Token::Word(w)) if w == "," => {
+                        self.advance();
+                    }
+                    Some(Token::Word(w)) if w == ">" => {
+                        self.advance();
+                        break;
+                    }
+                    _ => {
+                        return Err(ParseError::InvalidTypeSignature(format!(
+                            "expected ',' or '>' in parameters of {}",
+                            name
+                        )))
+                    }
+                }
+            }
+        }
+
+        let mut params = params.into_iter();
+        let ty = match (name.as_str(), params.len()) {
+            ("Unit", 0) => Type::Unit,
+            ("Bool", 0) => Type::Bool,
+            ("Nat", 0) => Type::Nat,
+            ("String", 0) => Type::String,
+            ("Ordinal", 0) => Type::Ordinal,
+            ("Quote", 0) => Type::Quote,
+            ("Option", 1) => Type::Option(Box::new(params.next().unwrap())),
+            ("List", 1) => Type::List(Box::new(params.next().unwrap())),
+            ("Result", 2) => Type::Result(
+                Box::new(params.next().unwrap()),
+                Box::new(params.next().unwrap()),
+            ),
+            (_, 0) if name.starts_with(|c: char| c.is_uppercase()) => Type::Composite {
+                name,
+                fields: HashMap::new(),
+            },
+            (_, 0) => Type::Variable(name),
+            _ => {
+                return Err(ParseError::InvalidTypeSignature(format!(
+                    "unknown type '{}'",
+                    name
+                )))
+            }
+        };
+        Ok(ty)
+    }
+
+    /// Parse a type signature like "( Nat Nat -> Point )"
+    fn parse_type_signature(&mut self) -> Result<TypeSignature, ParseError> {
+        // Expect opening parenthesis
+        match self.current_token() {
+            Some(Token::Word(w)) if w == "(" => {
+                self.advance();
+            }
+            _ => {
+                return Err(ParseError::MissingToken(
+                    "'(' for type signature".to_string(),
+                ));
+            }
+        }
+
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        let mut found_arrow = false;
+
+        while let Some(token) = self.current_token() {
+            match token {
+                Token::Word(w) if w == ")" => {
+                    self.advance();
+                    break;
+                }
+                Token::Word(w) if w == "->" => {
+                    self.advance();
+                    found_arrow = true;
+                }
+                _ => {
+                    let ty = self.parse_type()?;
+                    if found_arrow {
+                        outputs.push(ty);
+                    } else {
+                        inputs.push(ty);
+                    }
+                }
+            }
+        }
+
+        Ok(TypeSignature { inputs, outputs })
+    }
+
+    /// Parse a quotation (code block)
+    fn parse_quotation(&mut self) -> Result<Vec<Token>, ParseError> {
+        let mut quote_tokens = Vec::new();
+        let mut depth = 1;
+
+        // Skip the opening '['
+        self.advance();
+
+        while let Some(token) = self.current_token() {
+            match token {
+                Token::QuoteStart => {
+                    depth += 1;
+                    quote_tokens.push(token.clone());
+                    self.advance();
+                }
+                Token::QuoteEnd => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        break;
+                    } else {
+                        quote_tokens.push(token.clone());
+                        self.advance();
+                    }
+                }
+                _ => {
+                    quote_tokens.push(token.clone());
+                    self.advance();
+                }
+            }
+        }
+
+        if depth > 0 {
+            return Err(ParseError::UnexpectedEof(
+                "']' to close quotation".to_string(),
+            ));
+        }
+
+        process_quotes(quote_tokens)
+    }
+
+    /// Parse a word definition: ": name body ;"
+    fn parse_word_definition(&mut self) -> Result<WordDefinition, ParseError> {
+        // Skip the ':'
+        self.advance();
+
+        // Get the word name
+        let name = match self.current_token() {
+            Some(Token::Word(name)) => {
+                let name = name.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(ParseError::MissingToken("word name".to_string())),
+        };
+
+        // Parse the body until ';'
+        let mut body = Vec::new();
+        while let Some(token) = self.current_token() {
+            match token {
+                Token::Word(w) if w == ";" => {
+                    self.advance();
+                    break;
+                }
+                Token::QuoteStart => {
+                    let quote_tokens = self.parse_quotation()?;
+                    body.push(Token::Literal(Value::Quote(quote_tokens)));
+                }
+                _ => {
+                    body.push(process_token(token)?);
+                    self.advance();
+                }
+            }
+        }
+
+        // Try to infer the type signature from the body
+        let signature = match self.type_inferer.infer_word_type(&body) {
+            Ok(inferred_sig) => inferred_sig,
+            Err(_) => {
+                // If inference fails, use empty signature (will need explicit type later)
+                TypeSignature {
+                    inputs: vec![],
+                    outputs: vec![],
+                }
+            }
+        };
+
+        Ok(WordDefinition {
+            name,
+            signature,
+            body,
+            is_axiom: false,
+            ordinal_cost: OrdinalValue::Finite(1), // Default cost
+        })
+    }
+
+    /// Parse a type signature declaration: ":: name ( types )"
+    fn parse_type_signature_decl(&mut self) -> Result<(String, TypeSignature), ParseError> {
+        // Skip the '::'
+        self.advance();
+
+        // Get the word name
+        let name = match self.current_token() {
+            Some(Token::Word(name)) => {
+                let name = name.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(ParseError::MissingToken("word name after '::'".to_string())),
+        };
+
+        // Check if there's a type signature following
+        let signature = if let Some(Token::Word(w)) = self.current_token() {
+            if w == "(" {
+                self.parse_type_signature()?
+            } else {
+                // No type signature provided, use empty signature
+                TypeSignature {
+                    inputs: vec![],
+                    outputs: vec![],
+                }
+            }
+        } else {
+            // No more tokens, use empty signature
+            TypeSignature {
+                inputs: vec![],
+                outputs: vec![],
+            }
+        };
+
+        // Expect and consume the trailing semicolon
+        match self.current_token() {
+            Some(Token::Word(w)) if w == ";" => {
+                self.advance();
+            }
+            _ => {
+                return Err(ParseError::MissingToken(
+                    "';' after type signature".to_string(),
+                ));
+            }
+        }
+
+        Ok((name, signature))
+    }
+
+    /// Parse a type definition: "type Name { field1: Type1, field2: Type2 }"
+    ///
+    /// Fields may also be written `field::Type`.
+    fn parse_type_definition(&mut self) -> Result<TypeDefinition, ParseError> {
+        // Skip 'type'
+        self.advance();
+
+        // Get the type name
+        let name = match self.current_token() {
+            Some(Token::Word(name)) => {
+                let name = name.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(ParseError::MissingToken("type name".to_string())),
+        };
+
+        // Expect '{'
+        match self.current_token() {
+            Some(Token::Word(w)) if w == "{" => {
+                self.advance();
+            }
+            _ => return Err(ParseError::MissingToken("'{' after type name".to_string())),
+        }
+
+        let mut fields: Vec<(String, Type)> = Vec::new();
+
+        // Parse fields
+        while let Some(token) = self.current_token() {
+            match token {
+                Token::Word(w) if w == "}" => {
+                    self.advance();
+                    break;
+                }
+                Token::Word(field_name) => {
+                    // `field::Type` lexes as one qualified name
+                    if let Some((field, ty)) = field_name.split_once("::") {
+                        let split = [field, "::", ty].map(|w| Token::Word(w.to_string()));
+                        self.tokens.splice(self.position..=self.position, split);
+                        if let Some(&span) = self.spans.get(self.position) {
+                            self.spans.splice(self.position..=self.position, [span; 3]);
+                        }
+                        continue;
+                    }
+
+                    let field_name = field_name.clone();
+                    self.advance();
+
+                    // Expect ':' or '::'
+                    match self.current_token() {
+                        Some(Token::Word(w)) if w == ":" || w == "::" => {
+                            self.advance();
+                        }
+                        _ => {
+                            return Err(ParseError::MissingToken(
+                                "':' after field name".to_string(),
+                            ));
+                        }
+                    }
+
+                    if fields.iter().any(|(name, _)| *name == field_name) {
+                        return Err(ParseError::InvalidTypeDefinition(format!(
+                            "duplicate field '{}' in type {}",
+                            field_name, name
+                        )));
+                    }
+                    let field_type = self.parse_type()?;
+                    fields.push((field_name, field_type));
+
+                    // Skip optional comma
+                    if let Some(Token::Word(w)) = self.current_token() {
+                        if w == "," {
+                            self.advance();
+                        }
+                    }
+                }
+                _ => {
+                    return Err(ParseError::UnexpectedToken(
+                        token.clone(),
+                        "field name or '}'".to_string(),
+                    ));
+                }
+            }
+        }
+
+        // Constructor signature: field types -> composite type
+        let constructor_signature = TypeSignature {
+            inputs: fields.iter().map(|(_, ty)| ty.clone()).collect(),
+            outputs: vec![Type::Composite {
+                name: name.clone(),
+                fields: fields.iter().cloned().collect(),
+            }],
+        };
+
+        Ok(TypeDefinition {
+            name,
+            fields,
+            constructor_signature,
+        })
+    }
+
+    /// Parse a sum type: "data Shape = Circle Nat | Rect Nat Nat ;"
+    fn parse_data_definition(&mut self) -> Result<DataDefinition, ParseError> {
+        // Skip 'data'
+        self.advance();
+
+        let name = match self.current_token() {
+            Some(Token::Word(name)) if name.starts_with(|c: char| c.is_uppercase()) => {
+                let name = name.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(ParseError::MissingToken("type name".to_string())),
+        };
+
+        match self.current_token() {
+            Some(Token::Word(w)) if w == "=" => {
+                self.advance();
+            }
+            _ => return Err(ParseError::MissingToken("'=' after type name".to_string())),
+        }
+
+        let mut constructors: Vec<DataConstructor> = Vec::new();
+        loop {
+            let constructor = match self.current_token() {
+                Some(Token::Word(w)) if w.starts_with(|c: char| c.is_uppercase()) => w.clone(),
+                Some(token) => {
+                    return Err(ParseError::UnexpectedToken(
+                        token.clone(),
+                        "constructor name".to_string(),
+                    ))
+                }
+                None => return Err(ParseError::UnexpectedEof("constructor name".to_string())),
+            };
+            if constructors
+                .iter()
+                .any(|existing| existing.name == constructor)
+            {
+                return Err(ParseError::InvalidTypeDefinition(format!(
+                    "duplicate constructor '{}' in type {}",
+                    constructor, name
+                )));
+            }
+            self.advance();
+
+            let mut fields = Vec::new();
+            loop {
+                match self.current_token() {
+                    Some(Token::Word(w)) if w == "|" || w == ";" => break,
+                    Some(_) => fields.push(self.parse_type()?),
+                    None => {
+                        return Err(ParseError::MissingToken("';' after data type".to_string()))
+                    }
+                }
+            }
+            constructors.push(DataConstructor {
+                name: constructor,
+                fields,
+            });
+
+            let separator = matches!(self.current_token(), Some(Token::Word(w)) if w == ";");
+            self.advance();
+            if separator {
+                break;
+            }
+        }
+
+        Ok(DataDefinition { name, constructors })
+    }
+
+    /// Parse an axiom declaration: "axiom name ( types ) ;"
+    ///
+    /// Without a signature the axiom gets an empty one, which the compiler
+    /// replaces with a signature declared earlier with `::`.
+    fn parse_axiom_declaration(&mut self) -> Result<(String, TypeSignature), ParseError> {
+        // Skip 'axiom'
+        self.advance();
+
+        // Get the axiom name
+        let name = match self.current_token() {
+            Some(Token::Word(name)) => {
+                let name = name.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(ParseError::MissingToken("axiom name".to_string())),
+        };
+
+        let signature = match self.current_token() {
+            Some(Token::Word(w)) if w == "(" => self.parse_type_signature()?,
+            _ => TypeSignature {
+                inputs: vec![],
+                outputs: vec![],
+            },
+        };
+
+        // Skip the optional ';'
+        if let Some(Token::Word(w)) = self.current_token() {
+            if w == ";" {
+                self.advance();
+            }
+        }
+
+        Ok((name, signature))
+    }
+
+    /// Parse a theorem or lemma: "theorem name: [ lhs ] == [ rhs ] ;"
+    fn parse_theorem(&mut self) -> Result<Theorem, ParseError> {
+        let is_lemma = matches!(self.current_token(), Some(Token::Word(w)) if w == "lemma");
+        self.advance();
+
+        let name = match self.current_token() {
+            Some(Token::Word(name)) => {
+                let name = name.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(ParseError::MissingToken("theorem name".to_string())),
+        };
+        if let Some(Token::Word(w)) = self.current_token() {
+            if w == ":" {
+                self.advance();
+            }
+        }
+
+        let side = |parser: &mut Self| match parser.current_token() {
+            Some(Token::QuoteStart) => parser.parse_quotation(),
+            _ => Err(ParseError::MissingToken(format!(
+                "quotation in theorem '{}'",
+                name
+            ))),
+        };
+        let lhs = side(self)?;
+        match self.current_token() {
+            Some(Token::Word(w)) if w == "==" => {
+                self.advance();
+            }
+            _ => {
+                return Err(ParseError::MissingToken(format!(
+                    "'==' in theorem '{}'",
+                    name
+                )))
+            }
+        }
+        let rhs = side(self)?;
+        match self.current_token() {
+            Some(Token::Word(w)) if w == ";" => {
+                self.advance();
+            }
+            _ => {
+                return Err(ParseError::MissingToken(format!(
+                    "';' after theorem '{}'",
+                    name
+                )))
+            }
+        }
+
+        Ok(Theorem {
+            name,
+            lhs,
+            rhs,
+            is_lemma,
+        })
+    }
+
+    /// Parse a single statement
+    pub fn parse_statement(&mut self) -> Result<Option<Statement>, ParseError> {
+        self.expression_spans.clear();
+        match self.current_token() {
+            None => Ok(None),
+            Some(Token::Word(w)) => {
+                match w.as_str() {
+                    ":" => {
+                        let word_def = self.parse_word_definition()?;
+                        Ok(Some(Statement::WordDefinition(word_def)))
+                    }
+                    "::" => {
+                        let (name, signature) = self.parse_type_signature_decl()?;
+                        Ok(Some(Statement::TypeSignatureDecl { name, signature }))
+                    }
+                    "type" => {
+                        let type_def = self.parse_type_definition()?;
+                        Ok(Some(Statement::TypeDefinition(type_def)))
+                    }
+                    "data" => {
+                        let data_def = self.parse_data_definition()?;
+                        Ok(Some(Statement::DataDefinition(data_def)))
+                    }
+                    "axiom" => {
+                        let (name, signature) = self.parse_axiom_declaration()?;
+                        Ok(Some(Statement::AxiomDeclaration { name, signature }))
+                    }
+                    "theorem" | "lemma" => {
+                        let theorem = self.parse_theorem()?;
+                        Ok(Some(Statement::Theorem(theorem)))
+                    }
+                    _ => {
+                        // Parse as expression
+                        let mut expr_tokens = Vec::new();
+                        while let Some(token) = self.current_token() {
+                            // Stop at statement keywords
+                            if let Token::Word(w) = token {
+                                if matches!(
+                                    w.as_str(),
+                                    ":" | "::" | "type" | "data" | "axiom" | "theorem" | "lemma"
+                                ) {
+                                    break;
+                                }
+                            }
+
+                            let start = self.position;
+                            match token {
+                                Token::QuoteStart => {
+                                    let quote_tokens = self.parse_quotation()?;
+                                    expr_tokens.push(Token::Literal(Value::Quote(quote_tokens)));
+                                }
+                                _ => {
+                                    expr_tokens.push(process_token(token)?);
+                                    self.advance();
+                                }
+                            }
+                            self.expression_spans.push(self.span_since(start));
+                        }
+
+                        if !expr_tokens.is_empty() {
+                            Ok(Some(Statement::Expression(expr_tokens)))
+                        } else {
+                            Ok(None)
+                        }
+                    }
+                }
+            }
+            Some(_token) => {
+                // Parse as expression
+                let mut expr_tokens = Vec::new();
+                while let Some(token) = self.current_token() {
+                    let start = self.position;
+                    match token {
+                        Token::QuoteStart => {
+                            let quote_tokens = self.parse_quotation()?;
+                            expr_tokens.push(Token::Literal(Value::Quote(quote_tokens)));
+                        }
+                        _ => {
+                            expr_tokens.push(process_token(token)?);
+                            self.advance();
+                        }
+                    }
+                    self.expression_spans.push(self.span_since(start));
+                }
+
+                if !expr_tokens.is_empty() {
+                    Ok(Some(Statement::Expression(expr_tokens)))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    /// Parse all statements in the input
+    pub fn parse_all(&mut self) -> Result<Vec<Statement>, ParseError> {
+        let mut statements = Vec::new();
+        while let Some(statement) = self.parse_statement()? {
+            statements.push(statement);
+        }
+        Ok(statements)
+    }
+
+    /// Parse all statements in the input, keeping the source span of each
+    pub fn parse_all_spanned(&mut self) -> Result<Vec<Spanned<Statement>>, ParseError> {
+        Ok(self
+            .parse_all_with_token_spans()?
+            .into_iter()
+            .map(|(statement, _)| statement)
+            .collect())
+    }
+
+    /// Parse all statements in the input, keeping the source span of each
+    /// and, for an expression, the span of each of its tokens
+    ///
+    /// A quotation in an expression is one token, spanning its brackets.
+    pub fn parse_all_with_token_spans(&mut self) -> Result<Vec<SpannedStatement>, ParseError> {
+        let mut statements = Vec::new();
+        loop {
+            let start = self.position;
+            let Some(statement) = self.parse_statement()? else {
+                break;
+            };
+            if self.spans.get(start).is_none() {
+                continue;
+            }
+            let span = self.span_since(start);
+            let token_spans = std::mem::take(&mut self.expression_spans);
+            statements.push((Spanned::new(statement, span), token_spans));
+        }
+        Ok(statements)
+    }
+
+    /// The span from the token at `start` to the last token consumed
+    fn span_since(&self, start: usize) -> Span {
+        let first = self.spans[start];
+        match self
+            .position
+            .checked_sub(1)
+            .and_then(|last| self.spans.get(last))
+        {
+            Some(last) if self.position > start => first.to(*last),
+            _ => first,
+        }
+    }
+
+    /// The index of the token the parser stopped at
+    pub fn token_position(&self) -> usize {
+        self.position
+    }
+
+    /// The span of the token the parser stopped at
+    ///
+    /// At the end of input this is the position just past the last token.
+    pub fn error_span(&self) -> Option<Span> {
+        match self.spans.get(self.position) {
+            Some(span) => Some(*span),
+            None => self.spans.last().map(|last| {
+                Span::new(last.offset + last.len, last.line, last.column + last.len, 1)
+            }),
+        }
+    }
+
+    /// Add a known word signature to the type inferer
+    pub fn add_word_signature(&mut self, name: String, signature: TypeSignature) {
+        self.type_inferer.add_word_signature(name, signature);
+    }
+
+    /// Enable or disable type inference debugging
+    pub fn set_type_debug(&mut self, debug: bool) {
+        self.type_inferer.set_debug(debug);
+    }
+}
+
+/// Convert raw `[` and `]` tokens into quotation literals
+///
+/// Quotations nested inside other quotations and inside the scrutinee and
+/// arms of match expressions are converted as well.
+pub fn process_quotes(tokens: Vec<Token>) -> Result<Vec<Token>, ParseError> {
+    let mut outer: Vec<Vec<Token>> = Vec::new();
+    let mut current = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::QuoteStart => outer.push(std::mem::take(&mut current)),
+            Token::QuoteEnd => {
+                let quote = std::mem::take(&mut current);
+                current = outer.pop().ok_or_else(|| {
+                    ParseError::UnexpectedToken(Token::QuoteEnd, "matching '['".to_string())
+                })?;
+                current.push(Token::Literal(Value::Quote(quote)));
+            }
+            token => current.push(process_token(&token)?),
+        }
+    }
+
+    if !outer.is_empty() {
+        return Err(ParseError::UnexpectedEof(
+            "']' to close quotation".to_string(),
+        ));
+    }
+
+    Ok(current)
+}
+
+/// Convert raw `[` and `]` tokens into quotation literals, keeping spans
+///
+/// Each quotation literal spans from its `[` to its `]`. Errors carry the
+/// span of the stray `]` or of the `[` that was never closed.
+pub fn process_spanned_quotes(
+    tokens: Vec<SpannedToken>,
+) -> Result<Vec<SpannedToken>, Spanned<ParseError>> {
+    let mut outer: Vec<(Span, Vec<SpannedToken>)> = Vec::new();
+    let mut current = Vec::new();
+
+    for token in tokens {
+        let span = token.span;
+        match token.node {
+            Token::QuoteStart => outer.push((span, std::mem::take(&mut current))),
+            Token::QuoteEnd => {
+                let quote = std::mem::take(&mut current);
+                let (start, enclosing) = outer.pop().ok_or_else(|| {
+                    Spanned::new(
+                        ParseError::UnexpectedToken(Token::QuoteEnd, "matching '['".to_string()),
+                        span,
+                    )
+                })?;
+                current = enclosing;
+                let quote = quote.into_iter().map(|token| token.node).collect();
+                current.push(Spanned::new(
+                    Token::Literal(Value::Quote(quote)),
+                    start.to(span),
+                ));
+            }
+            node => {
+                let node = process_token(&node).map_err(|e| Spanned::new(e, span))?;
+                current.push(Spanned::new(node, span));
+            }
+        }
+    }
+
+    if let Some((start, _)) = outer.pop() {
+        return Err(Spanned::new(
+            ParseError::UnexpectedEof("']' to close quotation".to_string()),
+            start,
+        ));
+    }
+
+    Ok(current)
+}
+
+/// Convert the raw quotations inside a single match expression
+fn process_token(token: &Token) -> Result<Token, ParseError> {
+    match token {
+        Token::MatchExpression { value, arms } => {
+            let value = match value.as_ref() {
+                Token::Literal(Value::Quote(tokens)) => {
+                    Token::Literal(Value::Quote(process_quotes(tokens.clone())?))
+                }
+                other => other.clone(),
+            };
+            let arms = arms
+                .iter()
+                .map(|arm| {
+                    Ok(MatchArm {
+                        pattern: arm.pattern.clone(),
+                        body: process_quotes(arm.body.clone())?,
+                    })
+                })
+                .collect::<Result<Vec<_>, ParseError>>()?;
+
+            Ok(Token::MatchExpression {
+                value: Box::new(value),
+                arms,
+            })
+        }
+        _ => Ok(token.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expression() {
+        let mut parser = Parser::new("3 4 +").unwrap();
+        let statements = parser.parse_all().unwrap();
+
+        assert_eq!(statements.len(), 1);
+        if let Statement::Expression(tokens) = &statements[0] {
+            assert_eq!(tokens.len(), 3);
+        } else {
+            panic!("Expected expression statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_word_definition() {
+        let mut parser = Parser::new(": square dup * ;").unwrap();
+        let statements = parser.parse_all().unwrap();
+
+        assert_eq!(statements.len(), 1);
+        if let Statement::WordDefinition(word_def) = &statements[0] {
+            assert_eq!(word_def.name, "square");
+            assert_eq!(word_def.body.len(), 2);
+        } else {
+            panic!("Expected word definition");
+        }
+    }
+
+    #[test]
+    fn test_parse_type_signature() {
+        let mut parser = Parser::new(":: square ( Nat -> Nat ) ;").unwrap();
+        let statements = parser.parse_all().unwrap();
+
+        assert_eq!(statements.len(), 1);
+        if let Statement::TypeSignatureDecl { name, signature } = &statements[0] {
+            assert_eq!(name, "square");
+            assert_eq!(signature.inputs.len(), 1);
+            assert_eq!(signature.outputs.len(), 1);
+        } else {
+            panic!("Expected type signature declaration");
+        }
+    }
+
+    #[test]
+    fn test_parse_quote() {
+        let mut parser = Parser::new("[ dup * ]").unwrap();
+        let statements = parser.parse_all().unwrap();
+
+        assert_eq!(statements.len(), 1);
+        if let Statement::Expression(tokens) = &statements[0] {
+            assert_eq!(tokens.len(), 1);
+            if let Token::Literal(Value::Quote(quote_tokens)) = &tokens[0] {
+                assert_eq!(quote_tokens.len(), 2);
+            } else {
+                panic!("Expected quote literal");
+            }
+        } else {
+            panic!("Expected expression statement");
+        }
+    }
+
+    #[test]
+    fn test_statement_spans() {
+        let mut parser = Parser::new("dup dup +\n: sq dup * ;").unwrap();
+        let statements = parser.parse_all_spanned().unwrap();
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!((statements[0].span.line, statements[0].span.column), (1, 1));
+        assert_eq!(statements[0].span.len, 9);
+        assert_eq!((statements[1].span.line, statements[1].span.column), (2, 1));
+
+        let mut parser = Parser::new("dup\n: 1 ;").unwrap();
+        assert!(parser.parse_all().is_err());
+        let span = parser.error_span().unwrap();
+        assert_eq!((span.line, span.column), (2, 3));
+    }
+
+    #[test]
+    fn test_spanned_quotes() {
+        let tokens = Lexer::new("1 [ dup [ * ] ] call")
+            .tokenize_spanned()
+            .unwrap();
+        let tokens = process_spanned_quotes(tokens).unwrap();
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!((tokens[1].span.column, tokens[1].span.len), (3, 13));
+
+        let tokens = Lexer::new("1 2 ] +").tokenize_spanned().unwrap();
+        let error = process_spanned_quotes(tokens).unwrap_err();
+        assert_eq!(error.span.column, 5);
+
+        let tokens = Lexer::new("[ 1 [ 2 ]").tokenize_spanned().unwrap();
+        let error = process_spanned_quotes(tokens).unwrap_err();
+        assert_eq!(error.span.column, 1);
+    }
+}
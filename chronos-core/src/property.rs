@@ -0,0 +1,457 @@
+//! Property-based checks of words against their type signatures
+//!
+//! [`ChronosCore::check_word`](crate::ChronosCore::check_word) runs a word
+//! on random stacks built from its signature's inputs and checks that it
+//! leaves values of the output types. A failing stack is shrunk to a minimal
+//! one before it is reported. The `test:` definitions that use the word are
+//! run as its properties, and `equiv?` states the common property that two
+//! quotations agree on random inputs:
+//!
+//! ```text
+//! :: double ( Nat -> Nat ) ;
+//! : double dup + ;
+//! test: double-is-times-two [ double ] [ 2 * ] equiv? assert ;
+//! ```
+
+use crate::error::ChronosError;
+use crate::theorem::Counterexample;
+use crate::types::{DataDefinition, OrdinalValue, Type, TypeDefinition, Value};
+use crate::vm::format_values;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How many random stacks a word is run on
+pub const TRIALS: usize = 100;
+
+/// How many times a failing stack may be shrunk
+const MAX_SHRINKS: usize = 200;
+
+/// How deeply lists and data types nest in generated values
+const MAX_DEPTH: usize = 3;
+
+/// The result of checking a word
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    pub word: String,
+    /// The smallest stack found on which the word breaks its signature
+    pub failure: Option<CheckFailure>,
+    /// The tests that use the word, run as its properties
+    pub properties: Vec<PropertyResult>,
+}
+
+impl CheckReport {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none() && self.properties.iter().all(PropertyResult::passed)
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.failure {
+            Some(failure) => write!(f, "{} failed {}", self.word, failure)?,
+            None => write!(f, "{} passed {} random stacks", self.word, TRIALS)?,
+        }
+        for property in &self.properties {
+            match &property.error {
+                None => write!(f, "\n  test {}: ok", property.name)?,
+                Some(error) => write!(f, "\n  test {}: failed: {}", property.name, error)?,
+            }
+            if let Some(counterexample) = &property.counterexample {
+                write!(f, "\n    counterexample {}", counterexample)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A stack on which a word fails or leaves values of the wrong types
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckFailure {
+    pub inputs: Vec<Value>,
+    pub problem: String,
+    /// How many times the first failing stack was shrunk to reach `inputs`
+    pub shrinks: usize,
+}
+
+impl fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "on the stack {}: {}",
+            format_values(&self.inputs),
+            self.problem
+        )?;
+        match self.shrinks {
+            0 => {}
+            1 => write!(f, " (shrunk once)")?,
+            n => write!(f, " (shrunk {} times)", n)?,
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of one test run as a property of a word
+#[derive(Debug, Clone)]
+pub struct PropertyResult {
+    pub name: String,
+    pub error: Option<ChronosError>,
+    /// The inputs an `equiv?` in the test disagreed on, if any
+    pub counterexample: Option<Counterexample>,
+}
+
+impl PropertyResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// A small deterministic random number generator (SplitMix64)
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    /// A generator seeded from the FNV-1a hash of `seed`, so that checks of
+    /// the same thing are reproducible
+    pub(crate) fn seeded(seed: &str) -> Self {
+        Rng(seed.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        }))
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// Generates random values of a type, including the declared `type` and
+/// `data` types
+pub(crate) struct Generator<'a> {
+    rng: Rng,
+    types: &'a HashMap<String, TypeDefinition>,
+    data: &'a HashMap<String, DataDefinition>,
+}
+
+impl<'a> Generator<'a> {
+    pub(crate) fn new(
+        seed: &str,
+        types: &'a HashMap<String, TypeDefinition>,
+        data: &'a HashMap<String, DataDefinition>,
+    ) -> Self {
+        Self {
+            rng: Rng::seeded(seed),
+            types,
+            data,
+        }
+    }
+
+    /// A random stack for a signature's inputs, with the concrete type
+    /// chosen for each type variable, or the first type that cannot be
+    /// generated
+    pub(crate) fn inputs(
+        &mut self,
+        inputs: &[Type],
+    ) -> Result<(Vec<Value>, HashMap<String, Type>), Type> {
+        let mut bindings = HashMap::new();
+        for ty in inputs {
+            self.bind_variables(ty, &mut bindings);
+        }
+        let values = inputs
+            .iter()
+            .map(|ty| self.value(ty, &bindings, 0))
+            .collect::<Result<_, _>>()?;
+        Ok((values, bindings))
+    }
+
+    fn bind_variables(&mut self, ty: &Type, bindings: &mut HashMap<String, Type>) {
+        match ty {
+            Type::Variable(name) if !bindings.contains_key(name) => {
+                let concrete = match self.rng.below(3) {
+                    0 => Type::Nat,
+                    1 => Type::Bool,
+                    _ => Type::String,
+                };
+                bindings.insert(name.clone(), concrete);
+            }
+            Type::Option(inner) | Type::List(inner) => self.bind_variables(inner, bindings),
+            Type::Result(ok, err) => {
+                self.bind_variables(ok, bindings);
+                self.bind_variables(err, bindings);
+            }
+            _ => {}
+        }
+    }
+
+    fn value(
+        &mut self,
+        ty: &Type,
+        bindings: &HashMap<String, Type>,
+        depth: usize,
+    ) -> Result<Value, Type> {
+        let value = match ty {
+            Type::Unit => Value::Unit,
+            Type::Bool => Value::Bool(self.rng.below(2) == 0),
+            Type::Nat => Value::Nat(self.rng.below(100)),
+            Type::String => {
+                let length = self.rng.below(8);
+                Value::String(
+                    (0..length)
+                        .map(|_| char::from(b'a' + self.rng.below(26) as u8))
+                        .collect(),
+                )
+            }
+            Type::Ordinal => Value::Ordinal(OrdinalValue::Finite(self.rng.below(100))),
+            Type::Variable(name) => match bindings.get(name) {
+                Some(concrete) => return self.value(&concrete.clone(), bindings, depth),
+                None => Value::Nat(self.rng.below(100)),
+            },
+            Type::Option(inner) => match self.rng.below(4) {
+                0 => Value::Option(None),
+                _ => Value::Option(Some(Box::new(self.value(inner, bindings, depth)?))),
+            },
+            Type::Result(ok, err) => match self.rng.below(2) {
+                0 => Value::Result(Ok(Box::new(self.value(ok, bindings, depth)?))),
+                _ => Value::Result(Err(Box::new(self.value(err, bindings, depth)?))),
+            },
+            Type::List(element) => {
+                let length = if depth < MAX_DEPTH {
+                    self.rng.below(6)
+                } else {
+                    0
+                };
+                Value::List(
+                    (0..length)
+                        .map(|_| self.value(element, bindings, depth + 1))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            Type::Composite { name, .. } => {
+                if let Some(definition) = self.types.get(name) {
+                    let mut fields = HashMap::new();
+                    for (field, field_type) in &definition.fields {
+                        fields.insert(field.clone(), self.value(field_type, bindings, depth + 1)?);
+                    }
+                    Value::Composite {
+                        type_name: name.clone(),
+                        fields,
+                    }
+                } else if let Some(definition) = self.data.get(name) {
+                    // Deep values only use the smallest constructors, so
+                    // recursive types stay finite
+                    let smallest = definition.constructors.iter().map(|c| c.fields.len()).min();
+                    let candidates: Vec<_> = definition
+                        .constructors
+                        .iter()
+                        .filter(|c| depth < MAX_DEPTH || Some(c.fields.len()) == smallest)
+                        .collect();
+                    let constructor = candidates[self.rng.below(candidates.len() as u64) as usize];
+                    Value::Variant {
+                        type_name: name.clone(),
+                        constructor: constructor.name.clone(),
+                        args: constructor
+                            .fields
+                            .iter()
+                            .map(|field| self.value(field, bindings, depth + 1))
+                            .collect::<Result<_, _>>()?,
+                    }
+                } else {
+                    return Err(ty.clone());
+                }
+            }
+            Type::Quote => return Err(ty.clone()),
+        };
+        Ok(value)
+    }
+}
+
+/// Whether `value` has type `ty`, given the types chosen for its variables
+///
+/// A variable no input fixed accepts any value.
+pub(crate) fn conforms(value: &Value, ty: &Type, bindings: &HashMap<String, Type>) -> bool {
+    match (value, ty) {
+        (_, Type::Variable(name)) => bindings
+            .get(name)
+            .is_none_or(|concrete| conforms(value, concrete, bindings)),
+        (Value::Unit, Type::Unit)
+        | (Value::Bool(_), Type::Bool)
+        | (Value::Nat(_), Type::Nat)
+        | (Value::String(_), Type::String)
+        | (Value::Ordinal(_), Type::Ordinal)
+        | (Value::Quote(_), Type::Quote) => true,
+        #[cfg(feature = "bigint")]
+        (Value::BigNat(_), Type::Nat) => true,
+        (Value::Option(inner), Type::Option(inner_type)) => inner
+            .as_ref()
+            .is_none_or(|inner| conforms(inner, inner_type, bindings)),
+        (Value::Result(Ok(inner)), Type::Result(ok, _)) => conforms(inner, ok, bindings),
+        (Value::Result(Err(inner)), Type::Result(_, err)) => conforms(inner, err, bindings),
+        (Value::List(values), Type::List(element)) => values
+            .iter()
+            .all(|value| conforms(value, element, bindings)),
+        (Value::Composite { type_name, .. }, Type::Composite { name, .. })
+        | (Value::Variant { type_name, .. }, Type::Composite { name, .. }) => type_name == name,
+        _ => false,
+    }
+}
+
+/// Smaller values of the same type, smallest first
+fn shrink_value(value: &Value) -> Vec<Value> {
+    match value {
+        Value::Bool(true) => vec![Value::Bool(false)],
+        Value::Nat(n) if *n > 0 => {
+            let mut smaller = vec![Value::Nat(0), Value::Nat(n / 2), Value::Nat(n - 1)];
+            smaller.dedup();
+            smaller
+        }
+        Value::String(s) if !s.is_empty() => {
+            let chars: Vec<char> = s.chars().collect();
+            vec![
+                Value::String(String::new()),
+                Value::String(chars[..chars.len() / 2].iter().collect()),
+                Value::String(chars[..chars.len() - 1].iter().collect()),
+            ]
+        }
+        Value::Option(Some(inner)) => std::iter::once(Value::Option(None))
+            .chain(
+                shrink_value(inner)
+                    .into_iter()
+                    .map(|inner| Value::Option(Some(Box::new(inner)))),
+            )
+            .collect(),
+        Value::Result(Ok(inner)) => shrink_value(inner)
+            .into_iter()
+            .map(|inner| Value::Result(Ok(Box::new(inner))))
+            .collect(),
+        Value::Result(Err(inner)) => shrink_value(inner)
+            .into_iter()
+            .map(|inner| Value::Result(Err(Box::new(inner))))
+            .collect(),
+        Value::List(values) if !values.is_empty() => {
+            let mut smaller = vec![Value::List(vec![])];
+            for i in 0..values.len() {
+                let mut without = values.clone();
+                without.remove(i);
+                smaller.push(Value::List(without));
+            }
+            for (i, element) in values.iter().enumerate() {
+                for element in shrink_value(element) {
+                    let mut shrunk = values.clone();
+                    shrunk[i] = element;
+                    smaller.push(Value::List(shrunk));
+                }
+            }
+            smaller
+        }
+        Value::Composite { type_name, fields } => {
+            let mut names: Vec<&String> = fields.keys().collect();
+            names.sort();
+            names
+                .into_iter()
+                .flat_map(|name| {
+                    shrink_value(&fields[name]).into_iter().map(|field| {
+                        let mut fields = fields.clone();
+                        fields.insert(name.clone(), field);
+                        Value::Composite {
+                            type_name: type_name.clone(),
+                            fields,
+                        }
+                    })
+                })
+                .collect()
+        }
+        Value::Variant {
+            type_name,
+            constructor,
+            args,
+        } => (0..args.len())
+            .flat_map(|i| {
+                shrink_value(&args[i]).into_iter().map(move |arg| {
+                    let mut args = args.clone();
+                    args[i] = arg;
+                    Value::Variant {
+                        type_name: type_name.clone(),
+                        constructor: constructor.clone(),
+                        args,
+                    }
+                })
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Shrink a failing stack one value at a time while it keeps failing,
+/// returning the smallest stack found and how many steps it took
+pub(crate) fn shrink(
+    mut inputs: Vec<Value>,
+    mut fails: impl FnMut(&[Value]) -> bool,
+) -> (Vec<Value>, usize) {
+    let mut shrinks = 0;
+    'shrinking: while shrinks < MAX_SHRINKS {
+        for i in 0..inputs.len() {
+            for candidate in shrink_value(&inputs[i]) {
+                let mut smaller = inputs.clone();
+                smaller[i] = candidate;
+                if fails(&smaller) {
+                    inputs = smaller;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+        }
+        break;
+    }
+    (inputs, shrinks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_values_conform() {
+        let types = HashMap::new();
+        let data = HashMap::new();
+        let mut generator = Generator::new("seed", &types, &data);
+        let inputs = vec![
+            Type::Nat,
+            Type::List(Box::new(Type::Variable("a".to_string()))),
+            Type::Option(Box::new(Type::String)),
+            Type::Variable("a".to_string()),
+        ];
+        for _ in 0..TRIALS {
+            let (values, bindings) = generator.inputs(&inputs).unwrap();
+            assert!(values
+                .iter()
+                .zip(&inputs)
+                .all(|(value, ty)| conforms(value, ty, &bindings)));
+        }
+        assert_eq!(generator.inputs(&[Type::Quote]).unwrap_err(), Type::Quote);
+    }
+
+    #[test]
+    fn test_shrinking_finds_minimal_stack() {
+        // Fails whenever the first number is at least 10 and the list is not empty
+        let fails = |stack: &[Value]| match stack {
+            [Value::Nat(n), Value::List(values)] => *n >= 10 && !values.is_empty(),
+            _ => false,
+        };
+        let inputs = vec![
+            Value::Nat(87),
+            Value::List(vec![Value::Nat(5), Value::Nat(42), Value::Nat(3)]),
+        ];
+        let (shrunk, shrinks) = shrink(inputs, fails);
+        assert_eq!(
+            shrunk,
+            vec![Value::Nat(10), Value::List(vec![Value::Nat(0)])]
+        );
+        assert!(shrinks > 0);
+    }
+}
@@ -0,0 +1,140 @@
+//! Unit tests written in C∀O
+//!
+//! `test: name ... ;` defines a test the way `:` defines a word. A test passes
+//! when its body runs without error; `assert`, `assert-eq` and `assert-stack`
+//! fail it with the expected and actual values:
+//!
+//! ```text
+//! : square dup * ;
+//! test: square-of-three 3 square 9 assert-eq ;
+//! test: squares-leave-one-value 2 square 4 square [ 4 16 ] assert-stack ;
+//! ```
+
+use crate::error::{ChronosError, Result};
+use crate::types::Token;
+use crate::ChronosCore;
+
+/// A test defined with `test:`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub body: Vec<Token>,
+}
+
+/// The outcome of running one test
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub name: String,
+    /// Why the test failed, or `None` if it passed
+    pub error: Option<ChronosError>,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Run every test that `source` defines, each in a fresh runtime
+///
+/// `setup` prepares each runtime before `source` is loaded into it, for
+/// example by adding search paths. Fails if the source itself cannot be
+/// loaded.
+pub fn run_tests(source: &str, setup: impl Fn(&mut ChronosCore)) -> Result<Vec<TestResult>> {
+    let load = || -> Result<ChronosCore> {
+        let mut core = ChronosCore::new();
+        setup(&mut core);
+        core.eval_source(source)?;
+        Ok(core)
+    };
+
+    let names: Vec<String> = load()?
+        .tests()
+        .iter()
+        .map(|test| test.name.clone())
+        .collect();
+    names
+        .into_iter()
+        .map(|name| {
+            let mut core = load()?;
+            let error = core.run_test(&name).err();
+            Ok(TestResult { name, error })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Value;
+
+    const SOURCE: &str = ": square dup * ;
+        test: square-of-three 3 square 9 assert-eq ;
+        test: wrong-square 3 square 6 assert-eq ;
+        test: stack 2 square 4 square [ 4 16 ] assert-stack ;
+        test: leaky 1 ;
+        test: fresh-runtime depth 0 = assert ;";
+
+    #[test]
+    fn test_assertions() {
+        let mut core = ChronosCore::new();
+        assert!(core.eval_source("true assert 3 3 assert-eq").is_ok());
+        assert!(core.eval_source("1 2 [ 1 2 ] assert-stack").is_ok());
+        assert_eq!(core.get_stack(), vec![Value::Nat(1), Value::Nat(2)]);
+
+        let error = core.eval_source("[ 1 3 ] assert-stack").unwrap_err();
+        let ChronosError::AssertionError {
+            message,
+            expected,
+            actual,
+            ..
+        } = &error
+        else {
+            panic!("expected an assertion error, got {}", error);
+        };
+        assert_eq!(message, "assert-stack");
+        assert_eq!(expected, &vec![Value::Nat(1), Value::Nat(3)]);
+        assert_eq!(actual, &vec![Value::Nat(1), Value::Nat(2)]);
+        assert!(error
+            .to_string()
+            .contains("expected: [1 3]\n  actual:   [1 2]"));
+
+        assert!(core.eval_source("false assert").is_err());
+        assert!(core.eval_source("1 2 assert-eq").is_err());
+    }
+
+    #[test]
+    fn test_definitions_do_not_run() {
+        let mut core = ChronosCore::new();
+        core.eval_source(SOURCE).unwrap();
+        assert!(core.get_stack().is_empty());
+
+        let names: Vec<&str> = core.tests().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "square-of-three",
+                "wrong-square",
+                "stack",
+                "leaky",
+                "fresh-runtime"
+            ]
+        );
+        assert!(core.get_word_definition("leaky").is_none());
+        assert!(core.run_test("square-of-three").is_ok());
+        assert!(core.run_test("missing").is_err());
+    }
+
+    #[test]
+    fn test_run_tests_in_fresh_runtimes() {
+        let results = run_tests(SOURCE, |_| {}).unwrap();
+        let passed: Vec<bool> = results.iter().map(TestResult::passed).collect();
+        assert_eq!(passed, vec![true, false, true, true, true]);
+
+        let error = results[1].error.as_ref().unwrap();
+        assert_eq!(error.category(), "assertion");
+        assert!(error.to_string().contains("expected: [6]\n  actual:   [9]"));
+
+        assert!(run_tests("1 frob", |_| {}).is_err());
+    }
+}
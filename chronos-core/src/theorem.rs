@@ -0,0 +1,381 @@
+//! Equational theorems about concatenative words
+//!
+//! `theorem name: [ lhs ] == [ rhs ] ;` claims that two quotations have the
+//! same effect on every stack. The claim is proved by rewriting both sides to
+//! a normal form with the [shuffle axioms](shuffle_axioms), the lemmas and
+//! theorems declared before it, and the definitions of user words:
+//!
+//! ```text
+//! theorem swap-twice: [ swap swap ] == [ ] ;
+//! : nip swap drop ;
+//! theorem dup-nip: [ dup nip ] == [ ] ;
+//! ```
+//!
+//! `lemma name: [ lhs ] == [ rhs ] ;` states an equation without proving it,
+//! so later proofs may rewrite with it. Sides that cannot be rewritten to
+//! the same normal form are run on random stacks instead, to look for a
+//! [`Counterexample`].
+
+use crate::property::Rng;
+use crate::types::{Token, Value};
+use crate::vm::format_values;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How many rewrites normalizing one side may take, so that recursive
+/// definitions and looping lemmas cannot hang the prover
+const MAX_REWRITES: usize = 256;
+
+/// How many random stacks to try when looking for a counterexample
+pub const TRIALS: usize = 100;
+
+/// How many values each random stack holds
+const STACK_DEPTH: usize = 6;
+
+/// An equation between two quotations, declared with `theorem` or `lemma`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theorem {
+    pub name: String,
+    pub lhs: Vec<Token>,
+    pub rhs: Vec<Token>,
+    /// Whether the equation is assumed with `lemma` rather than proved
+    pub is_lemma: bool,
+}
+
+impl fmt::Display for Theorem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keyword = if self.is_lemma { "lemma" } else { "theorem" };
+        write!(
+            f,
+            "{} {}: {} == {}",
+            keyword,
+            self.name,
+            Value::Quote(self.lhs.clone()),
+            Value::Quote(self.rhs.clone())
+        )
+    }
+}
+
+/// A rewrite of one token sequence into an equivalent one
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewriteRule {
+    pub name: String,
+    pub lhs: Vec<Token>,
+    pub rhs: Vec<Token>,
+}
+
+impl RewriteRule {
+    fn new(name: &str, lhs: &[&str], rhs: &[&str]) -> Self {
+        let words = |words: &[&str]| words.iter().map(|w| Token::Word(w.to_string())).collect();
+        Self {
+            name: name.to_string(),
+            lhs: words(lhs),
+            rhs: words(rhs),
+        }
+    }
+
+    /// Orient an equation so that it rewrites the longer side into the
+    /// shorter one, or `None` if it cannot shorten or change anything
+    fn from_theorem(theorem: &Theorem) -> Option<Self> {
+        let (lhs, rhs) = if theorem.rhs.len() > theorem.lhs.len() {
+            (&theorem.rhs, &theorem.lhs)
+        } else {
+            (&theorem.lhs, &theorem.rhs)
+        };
+        (!lhs.is_empty() && lhs != rhs).then(|| Self {
+            name: theorem.name.clone(),
+            lhs: lhs.clone(),
+            rhs: rhs.clone(),
+        })
+    }
+}
+
+/// Equations that hold for the stack shuffling words whatever the values
+pub fn shuffle_axioms() -> Vec<RewriteRule> {
+    vec![
+        RewriteRule::new("swap-swap", &["swap", "swap"], &[]),
+        RewriteRule::new("dup-drop", &["dup", "drop"], &[]),
+        RewriteRule::new("over-drop", &["over", "drop"], &[]),
+        RewriteRule::new("dup-swap", &["dup", "swap"], &["dup"]),
+        RewriteRule::new("rot-rot-rot", &["rot", "rot", "rot"], &[]),
+        RewriteRule::new("over-over", &["over", "over"], &["dup2"]),
+        RewriteRule::new("dup2-drop-drop", &["dup2", "drop", "drop"], &[]),
+    ]
+}
+
+/// One rewrite in a proof
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofStep {
+    /// The axiom, lemma, theorem or definition used
+    pub rule: String,
+    /// The tokens after the rewrite
+    pub result: Vec<Token>,
+}
+
+/// How both sides of a theorem rewrite to the same normal form
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof {
+    pub theorem: Theorem,
+    pub lhs_steps: Vec<ProofStep>,
+    pub rhs_steps: Vec<ProofStep>,
+}
+
+impl Proof {
+    /// The tokens both sides rewrite to
+    pub fn normal_form(&self) -> &[Token] {
+        self.lhs_steps
+            .last()
+            .map_or(&self.theorem.lhs, |step| &step.result)
+    }
+}
+
+impl fmt::Display for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Proved {}", self.theorem.name)?;
+        for (side, start, steps) in [
+            ("left ", &self.theorem.lhs, &self.lhs_steps),
+            ("right", &self.theorem.rhs, &self.rhs_steps),
+        ] {
+            write!(f, "\n  {} {}", side, format_tokens(start))?;
+            for step in steps {
+                write!(
+                    f,
+                    "\n     = {}  by {}",
+                    format_tokens(&step.result),
+                    step.rule
+                )?;
+            }
+        }
+        write!(
+            f,
+            "\n  both sides reduce to {}",
+            format_tokens(self.normal_form())
+        )
+    }
+}
+
+/// A stack on which the two sides of a theorem leave different results
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counterexample {
+    pub stack: Vec<Value>,
+    pub lhs_result: Vec<Value>,
+    pub rhs_result: Vec<Value>,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "on the stack {} the left side leaves {} but the right side leaves {}",
+            format_values(&self.stack),
+            format_values(&self.lhs_result),
+            format_values(&self.rhs_result)
+        )
+    }
+}
+
+/// Proves theorems by rewriting both sides to a normal form
+#[derive(Debug, Clone)]
+pub struct Prover {
+    rules: Vec<RewriteRule>,
+    definitions: HashMap<String, Vec<Token>>,
+}
+
+impl Prover {
+    /// A prover that knows the [`shuffle_axioms`]
+    pub fn new() -> Self {
+        Self {
+            rules: shuffle_axioms(),
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Rewrite with a lemma or proved theorem
+    pub fn add_lemma(&mut self, theorem: &Theorem) {
+        self.rules.extend(RewriteRule::from_theorem(theorem));
+    }
+
+    /// Unfold a user word into its body when no rule applies
+    pub fn add_definition(&mut self, name: String, body: Vec<Token>) {
+        self.definitions.insert(name, body);
+    }
+
+    /// Rewrite `tokens` until no rule applies, returning each step
+    pub fn normalize(&self, tokens: &[Token]) -> Vec<ProofStep> {
+        let mut steps: Vec<ProofStep> = Vec::new();
+        while steps.len() < MAX_REWRITES {
+            let current = steps.last().map_or(tokens, |step| &step.result);
+            match self.rewrite(current) {
+                Some(step) => steps.push(step),
+                None => break,
+            }
+        }
+        steps
+    }
+
+    /// Prove a theorem, or return the normal forms of its two sides
+    pub fn prove(&self, theorem: &Theorem) -> Result<Proof, (Vec<Token>, Vec<Token>)> {
+        let lhs_steps = self.normalize(&theorem.lhs);
+        let rhs_steps = self.normalize(&theorem.rhs);
+        let proof = Proof {
+            theorem: theorem.clone(),
+            lhs_steps,
+            rhs_steps,
+        };
+        let rhs = proof
+            .rhs_steps
+            .last()
+            .map_or(&theorem.rhs, |step| &step.result);
+        if proof.normal_form() == rhs.as_slice() {
+            Ok(proof)
+        } else {
+            Err((proof.normal_form().to_vec(), rhs.clone()))
+        }
+    }
+
+    /// The leftmost rewrite of `tokens`, trying rules before unfolding
+    /// definitions
+    fn rewrite(&self, tokens: &[Token]) -> Option<ProofStep> {
+        let splice = |at: usize, len: usize, with: &[Token]| {
+            let mut result = tokens[..at].to_vec();
+            result.extend_from_slice(with);
+            result.extend_from_slice(&tokens[at + len..]);
+            result
+        };
+
+        for at in 0..tokens.len() {
+            let rest = &tokens[at..];
+            if let Some(rule) = self.rules.iter().find(|rule| rest.starts_with(&rule.lhs)) {
+                return Some(ProofStep {
+                    rule: rule.name.clone(),
+                    result: splice(at, rule.lhs.len(), &rule.rhs),
+                });
+            }
+
+            // Pushing a literal commutes with the shuffles that consume it
+            let word = |i: usize| match rest.get(i) {
+                Some(Token::Word(w)) => w.as_str(),
+                _ => "",
+            };
+            if let Token::Literal(a) = &rest[0] {
+                let step = match (word(1), rest.get(1)) {
+                    ("drop", _) => Some(("push-drop", vec![])),
+                    ("dup", _) => Some(("push-dup", vec![rest[0].clone(), rest[0].clone()])),
+                    (_, Some(Token::Literal(b))) if word(2) == "swap" => Some((
+                        "push-swap",
+                        vec![Token::Literal(b.clone()), Token::Literal(a.clone())],
+                    )),
+                    _ => None,
+                };
+                if let Some((rule, with)) = step {
+                    let len = if rule == "push-swap" { 3 } else { 2 };
+                    return Some(ProofStep {
+                        rule: rule.to_string(),
+                        result: splice(at, len, &with),
+                    });
+                }
+            }
+        }
+
+        tokens
+            .iter()
+            .enumerate()
+            .find_map(|(at, token)| match token {
+                Token::Word(word) => self.definitions.get(word).map(|body| ProofStep {
+                    rule: format!("definition of {}", word),
+                    result: splice(at, 1, body),
+                }),
+                _ => None,
+            })
+    }
+}
+
+impl Default for Prover {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tokens as written in a quotation, or `id` when there are none
+pub fn format_tokens(tokens: &[Token]) -> String {
+    if tokens.is_empty() {
+        return "id".to_string();
+    }
+    let tokens: Vec<String> = tokens.iter().map(|token| token.to_string()).collect();
+    tokens.join(" ")
+}
+
+/// Random stacks to run both sides of a theorem on, the same for every run
+/// of the same theorem
+pub fn random_stacks(theorem: &Theorem) -> impl Iterator<Item = Vec<Value>> {
+    let mut rng = Rng::seeded(&theorem.name);
+    (0..TRIALS).map(move |_| {
+        (0..STACK_DEPTH)
+            .map(|_| match rng.below(4) {
+                0 => Value::Bool(rng.below(2) == 0),
+                _ => Value::Nat(rng.below(100)),
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lexer;
+
+    fn theorem(lhs: &str, rhs: &str) -> Theorem {
+        let tokens = |source: &str| Lexer::new(source).tokenize().unwrap();
+        Theorem {
+            name: "t".to_string(),
+            lhs: tokens(lhs),
+            rhs: tokens(rhs),
+            is_lemma: false,
+        }
+    }
+
+    #[test]
+    fn test_shuffle_axioms() {
+        let prover = Prover::new();
+        let proof = prover.prove(&theorem("swap swap", "")).unwrap();
+        assert_eq!(proof.lhs_steps.len(), 1);
+        assert_eq!(proof.lhs_steps[0].rule, "swap-swap");
+
+        assert!(prover.prove(&theorem("dup swap drop", "")).is_ok());
+        assert!(prover
+            .prove(&theorem("rot rot rot over over", "dup2"))
+            .is_ok());
+        assert!(prover.prove(&theorem("1 2 swap drop", "2")).is_ok());
+        assert!(prover.prove(&theorem("swap", "")).is_err());
+    }
+
+    #[test]
+    fn test_lemmas_and_definitions() {
+        let mut prover = Prover::new();
+        prover.add_definition("nip".to_string(), theorem("swap drop", "").lhs);
+        assert!(prover.prove(&theorem("dup nip", "")).is_ok());
+
+        let commutes = theorem("swap +", "+");
+        assert!(prover.prove(&commutes).is_err());
+        prover.add_lemma(&commutes);
+        let proof = prover.prove(&theorem("swap swap swap +", "+")).unwrap();
+        assert_eq!(format_tokens(proof.normal_form()), "+");
+    }
+
+    #[test]
+    fn test_looping_lemmas_stop() {
+        let mut prover = Prover::new();
+        prover.add_lemma(&theorem("a", "b"));
+        prover.add_lemma(&theorem("b", "a"));
+        assert!(prover.prove(&theorem("a", "c")).is_err());
+    }
+
+    #[test]
+    fn test_random_stacks_are_reproducible() {
+        let t = theorem("swap", "");
+        let first: Vec<_> = random_stacks(&t).collect();
+        assert_eq!(first.len(), TRIALS);
+        assert_eq!(first, random_stacks(&t).collect::<Vec<_>>());
+        assert!(first.iter().all(|stack| stack.len() == STACK_DEPTH));
+    }
+}
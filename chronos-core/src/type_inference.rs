@@ -0,0 +1,1312 @@
+//! Type Inference Engine for C∀O
+//!
+//! Implements Hindley-Milner style type inference to automatically deduce types
+//! for word definitions, reducing boilerplate while maintaining type safety.
+
+use crate::core_lib::CoreLibrary;
+use crate::types::{DataDefinition, Pattern, Token, Type, TypeSignature, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Errors that can occur during type inference
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferenceError {
+    /// Cannot unify two types
+    UnificationError(Box<(Type, Type)>),
+    /// Type variable occurs in the type it's being unified with (infinite type)
+    OccursCheck(String, Type),
+    /// Unknown word referenced in definition
+    UnknownWord(String),
+    /// Cannot infer type without more context
+    InsufficientContext(String),
+    /// Two stack effects that must agree do not
+    StackEffectMismatch(TypeSignature, TypeSignature),
+    /// A quotation that must preserve the stack shape changes it
+    UnbalancedQuote(String, TypeSignature),
+    /// A definition body does not fit its declared signature
+    SignatureMismatch {
+        declared: TypeSignature,
+        inferred: TypeSignature,
+    },
+}
+
+impl fmt::Display for InferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InferenceError::UnificationError(types) => {
+                write!(f, "Cannot unify types {} and {}", types.0, types.1)
+            }
+            InferenceError::OccursCheck(var, ty) => {
+                write!(f, "Occurs check failed: {} occurs in {}", var, ty)
+            }
+            InferenceError::UnknownWord(word) => {
+                write!(f, "Unknown word: {}", word)
+            }
+            InferenceError::InsufficientContext(msg) => {
+                write!(f, "Insufficient context: {}", msg)
+            }
+            InferenceError::StackEffectMismatch(a, b) => {
+                write!(f, "Stack effects {} and {} do not match", a, b)
+            }
+            InferenceError::SignatureMismatch { declared, inferred } => {
+                write!(
+                    f,
+                    "Body has stack effect {} but is declared as {}",
+                    inferred, declared
+                )
+            }
+            InferenceError::UnbalancedQuote(word, effect) => {
+                write!(
+                    f,
+                    "Quotation passed to '{}' must leave the stack shape unchanged, but has effect {}",
+                    word, effect
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InferenceError {}
+
+/// How far [`TypeInferer::check_definition`] could check a body against
+/// its declared signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub enum Verification {
+    /// The inferred effect of the body matches the declaration
+    Checked,
+    /// The effect of the body could not be inferred, so the declaration is
+    /// assumed; the reason names the part that was not understood
+    Assumed(String),
+}
+
+/// A `match` over a `data` type with no arm for some of its constructors
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingArms {
+    pub type_name: String,
+    /// The constructors no arm covers, in declaration order
+    pub missing: Vec<String>,
+}
+
+impl fmt::Display for MissingArms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "match over {} has no arm for {}",
+            self.type_name,
+            self.missing.join(", ")
+        )
+    }
+}
+
+/// Type constraint used during inference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TypeConstraint {
+    /// Two types must be equal
+    Equal(Type, Type),
+    /// A type must be unified with another
+    Unify(Type, Type),
+}
+
+/// Type substitution mapping type variables to concrete types
+#[derive(Debug, Clone, Default)]
+pub struct Substitution {
+    mappings: HashMap<String, Type>,
+}
+
+impl Substitution {
+    pub fn new() -> Self {
+        Self {
+            mappings: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, var: String, ty: Type) {
+        self.mappings.insert(var, ty);
+    }
+
+    pub fn get(&self, var: &str) -> Option<&Type> {
+        self.mappings.get(var)
+    }
+
+    pub fn apply(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Variable(var) => {
+                if let Some(concrete_type) = self.mappings.get(var) {
+                    self.apply(concrete_type) // Apply substitution recursively
+                } else {
+                    ty.clone()
+                }
+            }
+            Type::Composite { name, fields } => {
+                let mut new_fields = HashMap::new();
+                for (key, field_type) in fields {
+                    new_fields.insert(key.clone(), self.apply(field_type));
+                }
+                Type::Composite {
+                    name: name.clone(),
+                    fields: new_fields,
+                }
+            }
+            Type::Option(inner) => Type::Option(Box::new(self.apply(inner))),
+            Type::List(inner) => Type::List(Box::new(self.apply(inner))),
+            Type::Result(ok, err) => {
+                Type::Result(Box::new(self.apply(ok)), Box::new(self.apply(err)))
+            }
+            _ => ty.clone(), // Concrete types don't need substitution
+        }
+    }
+
+    pub fn compose(&self, other: &Substitution) -> Substitution {
+        let mut result = Substitution::new();
+
+        // Apply this substitution to other's mappings
+        for (var, ty) in &other.mappings {
+            result.insert(var.clone(), self.apply(ty));
+        }
+
+        // Add this substitution's mappings (if not already present)
+        for (var, ty) in &self.mappings {
+            if !result.mappings.contains_key(var) {
+                result.insert(var.clone(), ty.clone());
+            }
+        }
+
+        result
+    }
+}
+
+/// Main type inference engine
+pub struct TypeInferer {
+    /// Current type variable counter for generating fresh variables
+    var_counter: u32,
+    /// Known word signatures from the environment, on top of the core library
+    word_signatures: HashMap<String, TypeSignature>,
+    /// Types declared with `data`, by name
+    data_types: HashMap<String, DataDefinition>,
+    /// Debug mode flag
+    debug: bool,
+}
+
+impl TypeInferer {
+    /// Create an inferer that knows the core library signatures
+    pub fn new() -> Self {
+        Self {
+            var_counter: 0,
+            word_signatures: HashMap::new(),
+            data_types: HashMap::new(),
+            debug: false,
+        }
+    }
+
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    pub fn add_word_signature(&mut self, name: String, signature: TypeSignature) {
+        self.word_signatures.insert(name, signature);
+    }
+
+    /// Forget the signature of a word, for example because it was redefined
+    /// with a body whose effect cannot be inferred
+    pub fn remove_word_signature(&mut self, name: &str) {
+        self.word_signatures.remove(name);
+    }
+
+    /// Register a `data` type and the signatures of its constructors
+    pub fn add_data_type(&mut self, definition: DataDefinition) {
+        for (name, signature) in definition.constructor_signatures() {
+            self.add_word_signature(name, signature);
+        }
+        self.data_types.insert(definition.name.clone(), definition);
+    }
+
+    /// The first `match` in `tokens`, including nested quotes and arms, that
+    /// dispatches on the constructors of a `data` type without covering all
+    /// of them
+    ///
+    /// An arm covers a constructor when its arguments are all variables or
+    /// wildcards, or when it names the constructor alone; a variable or
+    /// wildcard arm covers every constructor.
+    pub fn missing_match_arms(&self, tokens: &[Token]) -> Option<MissingArms> {
+        tokens.iter().find_map(|token| match token {
+            Token::Literal(Value::Quote(body)) => self.missing_match_arms(body),
+            Token::MatchExpression { value, arms } => self
+                .missing_match_arms(std::slice::from_ref(value))
+                .or_else(|| {
+                    arms.iter()
+                        .find_map(|arm| self.missing_match_arms(&arm.body))
+                })
+                .or_else(|| self.uncovered_constructors(arms.iter().map(|arm| &arm.pattern))),
+            _ => None,
+        })
+    }
+
+    /// The constructors of the `data` type the patterns dispatch on that
+    /// none of them covers
+    fn uncovered_constructors<'a>(
+        &self,
+        patterns: impl Iterator<Item = &'a Pattern> + Clone,
+    ) -> Option<MissingArms> {
+        let irrefutable =
+            |pattern: &Pattern| matches!(pattern, Pattern::Wildcard | Pattern::Variable(_));
+        if patterns.clone().any(irrefutable) {
+            return None;
+        }
+
+        let constructor_names = patterns.clone().filter_map(|pattern| match pattern {
+            Pattern::Constructor { name, .. } => Some(name),
+            _ => None,
+        });
+        let definition = constructor_names.clone().find_map(|name| {
+            self.data_types
+                .values()
+                .find(|definition| definition.constructor(name).is_some())
+        })?;
+
+        let covered: Vec<&String> = patterns
+            .filter_map(|pattern| match pattern {
+                Pattern::Constructor { name, args } if args.iter().all(irrefutable) => Some(name),
+                _ => None,
+            })
+            .collect();
+        let missing: Vec<String> = definition
+            .constructors
+            .iter()
+            .filter(|constructor| !covered.contains(&&constructor.name))
+            .map(|constructor| constructor.name.clone())
+            .collect();
+
+        (!missing.is_empty()).then(|| MissingArms {
+            type_name: definition.name.clone(),
+            missing,
+        })
+    }
+
+    /// Generate a fresh type variable
+    fn fresh_type_var(&mut self) -> Type {
+        let var_name = format!("T{}", self.var_counter);
+        self.var_counter += 1;
+        Type::Variable(var_name)
+    }
+
+    /// Check if a type variable occurs in a type (occurs check)
+    fn occurs_check(&self, var: &str, ty: &Type) -> bool {
+        match ty {
+            Type::Variable(v) => v == var,
+            Type::Composite { fields, .. } => fields
+                .values()
+                .any(|field_ty| self.occurs_check(var, field_ty)),
+            Type::Option(inner) | Type::List(inner) => self.occurs_check(var, inner),
+            Type::Result(ok, err) => self.occurs_check(var, ok) || self.occurs_check(var, err),
+            _ => false,
+        }
+    }
+
+    /// Unify two types, returning a substitution
+    fn unify(&mut self, t1: &Type, t2: &Type) -> Result<Substitution, InferenceError> {
+        if self.debug {
+            println!("Unifying {} with {}", t1, t2);
+        }
+
+        // Structured types unify component by component
+        let components: Vec<(&Type, &Type)> = match (t1, t2) {
+            // Identical types unify trivially
+            _ if t1 == t2 => return Ok(Substitution::new()),
+
+            // Same concrete types unify trivially
+            (Type::Unit, Type::Unit)
+            | (Type::Bool, Type::Bool)
+            | (Type::Nat, Type::Nat)
+            | (Type::String, Type::String)
+            | (Type::Ordinal, Type::Ordinal)
+            | (Type::Quote, Type::Quote) => return Ok(Substitution::new()),
+
+            // Variable unification
+            (Type::Variable(var), ty) | (ty, Type::Variable(var)) => {
+                if let Type::Variable(other_var) = ty {
+                    if var == other_var {
+                        return Ok(Substitution::new()); // Same variable
+                    }
+                }
+
+                // Occurs check
+                if self.occurs_check(var, ty) {
+                    return Err(InferenceError::OccursCheck(var.clone(), ty.clone()));
+                }
+
+                let mut subst = Substitution::new();
+                subst.insert(var.clone(), ty.clone());
+                return Ok(subst);
+            }
+
+            // Composite type unification
+            (
+                Type::Composite {
+                    name: n1,
+                    fields: f1,
+                },
+                Type::Composite {
+                    name: n2,
+                    fields: f2,
+                },
+            ) => {
+                if n1 != n2 {
+                    return Err(InferenceError::UnificationError(Box::new((
+                        t1.clone(),
+                        t2.clone(),
+                    ))));
+                }
+
+                // A type named without its fields, as in `( Point -> Nat )`,
+                // stands for any value of that type
+                if f1.is_empty() || f2.is_empty() {
+                    return Ok(Substitution::new());
+                }
+
+                if f1.len() != f2.len() {
+                    return Err(InferenceError::UnificationError(Box::new((
+                        t1.clone(),
+                        t2.clone(),
+                    ))));
+                }
+
+                let mut fields = Vec::new();
+                for (key, field1) in f1 {
+                    match f2.get(key) {
+                        Some(field2) => fields.push((field1, field2)),
+                        None => {
+                            return Err(InferenceError::UnificationError(Box::new((
+                                t1.clone(),
+                                t2.clone(),
+                            ))))
+                        }
+                    }
+                }
+                fields
+            }
+
+            // Containers unify element-wise
+            (Type::Option(a), Type::Option(b)) | (Type::List(a), Type::List(b)) => {
+                vec![(a.as_ref(), b.as_ref())]
+            }
+            (Type::Result(ok1, err1), Type::Result(ok2, err2)) => {
+                vec![(ok1.as_ref(), ok2.as_ref()), (err1.as_ref(), err2.as_ref())]
+            }
+
+            // Cannot unify different concrete types
+            _ => {
+                return Err(InferenceError::UnificationError(Box::new((
+                    t1.clone(),
+                    t2.clone(),
+                ))))
+            }
+        };
+
+        // Each component is unified under the substitution found for the ones
+        // before it, so a variable shared between components is bound consistently
+        let mut subst = Substitution::new();
+        for (a, b) in components {
+            let step = self.unify(&subst.apply(a), &subst.apply(b))?;
+            subst = step.compose(&subst);
+        }
+        Ok(subst)
+    }
+
+    /// Infer the type signature for a word definition
+    ///
+    /// The body is run symbolically: literals push their types, words apply
+    /// their (freshly instantiated) signatures, and values taken from below
+    /// the starting stack become inputs. Quotations passed to `call`, `if`,
+    /// `when`, `unless`, `times` and `while` contribute their own inferred
+    /// stack effects.
+    pub fn infer_word_type(&mut self, tokens: &[Token]) -> Result<TypeSignature, InferenceError> {
+        if self.debug {
+            println!("Inferring type for tokens: {:?}", tokens);
+        }
+
+        if tokens.is_empty() {
+            // Empty definition is identity function
+            let t = self.fresh_type_var();
+            return Ok(TypeSignature {
+                inputs: vec![t.clone()],
+                outputs: vec![t],
+            });
+        }
+
+        self.infer_effect(tokens)
+    }
+
+    /// Check that a body with the `inferred` effect can be given the `declared` signature
+    ///
+    /// Declared type variables are rigid: the body has to work for any type
+    /// they stand for, so `( a -> Nat )` does not accept a body of `( Nat -> Nat )`.
+    pub fn check_signature(
+        &mut self,
+        declared: &TypeSignature,
+        inferred: &TypeSignature,
+    ) -> Result<(), InferenceError> {
+        let mismatch = || InferenceError::SignatureMismatch {
+            declared: declared.clone(),
+            inferred: inferred.clone(),
+        };
+
+        // An effect that ignores the bottom of the stack also fits deeper declarations
+        if inferred.inputs.len() > declared.inputs.len() {
+            return Err(mismatch());
+        }
+        let padded = self.pad_effect(inferred, declared.inputs.len() - inferred.inputs.len());
+        if padded.outputs.len() != declared.outputs.len() {
+            return Err(mismatch());
+        }
+
+        let mut subst = Substitution::new();
+        let pairs = padded
+            .inputs
+            .iter()
+            .zip(&declared.inputs)
+            .chain(padded.outputs.iter().zip(&declared.outputs));
+        for (actual, expected) in pairs {
+            let step = self
+                .unify(&subst.apply(actual), &subst.apply(expected))
+                .map_err(|_| mismatch())?;
+            subst = step.compose(&subst);
+        }
+
+        let mut declared_vars = Vec::new();
+        for ty in declared.inputs.iter().chain(&declared.outputs) {
+            collect_type_variables(ty, &mut declared_vars);
+        }
+        for var in declared_vars {
+            let rigid = Type::Variable(var);
+            if subst.apply(&rigid) != rigid {
+                return Err(mismatch());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check a definition body against its declared signature
+    ///
+    /// The declaration is visible to recursive calls. A body whose effect
+    /// cannot be inferred (for example one using `match`, or calling a
+    /// quotation taken from the stack) is not rejected, but the result says
+    /// that the declaration was only assumed.
+    pub fn check_definition(
+        &mut self,
+        name: &str,
+        declared: &TypeSignature,
+        body: &[Token],
+    ) -> Result<Verification, InferenceError> {
+        self.add_word_signature(name.to_string(), declared.clone());
+
+        match self.infer_word_type(body) {
+            Ok(inferred) => self
+                .check_signature(declared, &inferred)
+                .map(|()| Verification::Checked),
+            Err(InferenceError::InsufficientContext(reason)) => Ok(Verification::Assumed(reason)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Infer the stack effect of a token sequence
+    fn infer_effect(&mut self, tokens: &[Token]) -> Result<TypeSignature, InferenceError> {
+        let mut state = EffectState::default();
+
+        for token in tokens {
+            match token {
+                Token::Literal(Value::Quote(body)) => {
+                    let effect = match self.infer_effect(body) {
+                        Ok(effect) => Some(effect),
+                        Err(InferenceError::UnknownWord(_))
+                        | Err(InferenceError::InsufficientContext(_)) => None,
+                        Err(e) => return Err(e),
+                    };
+                    state.stack.push(StackEntry {
+                        ty: Type::Quote,
+                        effect,
+                        count: None,
+                    });
+                }
+                Token::Literal(Value::Nat(n)) => state.stack.push(StackEntry {
+                    ty: Type::Nat,
+                    effect: None,
+                    count: Some(*n),
+                }),
+                Token::Literal(value) => {
+                    let ty = self.literal_type(value);
+                    state.push(ty);
+                }
+                Token::Word(word) => self.apply_word(&mut state, word)?,
+                Token::Comment(_) => {}
+                _ => {
+                    return Err(InferenceError::InsufficientContext(format!(
+                        "Cannot infer the stack effect of {}",
+                        token
+                    )))
+                }
+            }
+        }
+
+        Ok(TypeSignature {
+            inputs: state
+                .inputs
+                .iter()
+                .map(|ty| state.subst.apply(ty))
+                .collect(),
+            outputs: state
+                .stack
+                .iter()
+                .map(|entry| state.subst.apply(&entry.ty))
+                .collect(),
+        })
+    }
+
+    /// Apply the stack effect of a single word
+    fn apply_word(&mut self, state: &mut EffectState, word: &str) -> Result<(), InferenceError> {
+        match word {
+            "call" => {
+                let quote = self.expect(state, &Type::Quote)?;
+                let effect = quote_effect(quote, word)?;
+                self.apply_effect(state, &effect)
+            }
+            "if" => {
+                let else_quote = self.expect(state, &Type::Quote)?;
+                let then_quote = self.expect(state, &Type::Quote)?;
+                self.expect(state, &Type::Bool)?;
+                let then_effect = quote_effect(then_quote, word)?;
+                let else_effect = quote_effect(else_quote, word)?;
+
+                // Both branches must have the same stack effect
+                let depth = then_effect.inputs.len().max(else_effect.inputs.len());
+                let then_effect = self.pad_effect(&then_effect, depth - then_effect.inputs.len());
+                let else_effect = self.pad_effect(&else_effect, depth - else_effect.inputs.len());
+                if then_effect.outputs.len() != else_effect.outputs.len() {
+                    return Err(InferenceError::StackEffectMismatch(
+                        then_effect,
+                        else_effect,
+                    ));
+                }
+                let pairs = then_effect
+                    .inputs
+                    .iter()
+                    .zip(&else_effect.inputs)
+                    .chain(then_effect.outputs.iter().zip(&else_effect.outputs));
+                for (a, b) in pairs {
+                    self.unify_in(state, a, b)?;
+                }
+                self.apply_effect(state, &then_effect)
+            }
+            "when" | "unless" | "times" => {
+                let quote = self.expect(state, &Type::Quote)?;
+                let guard = if word == "times" {
+                    Type::Nat
+                } else {
+                    Type::Bool
+                };
+                self.expect(state, &guard)?;
+                let effect = quote_effect(quote, word)?;
+                self.apply_balanced_effect(state, &effect, word)
+            }
+            "while" => {
+                let body = self.expect(state, &Type::Quote)?;
+                let condition = self.expect(state, &Type::Quote)?;
+                self.expect(state, &Type::Nat)?;
+                let body = quote_effect(body, word)?;
+                let condition = quote_effect(condition, word)?;
+
+                // The condition may inspect the stack but must only add a Bool
+                if condition.outputs.len() != condition.inputs.len() + 1 {
+                    return Err(InferenceError::UnbalancedQuote(word.to_string(), condition));
+                }
+                for (input, output) in condition.inputs.iter().zip(&condition.outputs) {
+                    self.unify_in(state, input, output)?;
+                }
+                self.apply_effect(state, &condition)?;
+                self.expect(state, &Type::Bool)?;
+                self.apply_balanced_effect(state, &body, word)
+            }
+            "list" => {
+                // `list` takes as many items as the literal count before it
+                let count = self.expect(state, &Type::Nat)?.count.ok_or_else(|| {
+                    InferenceError::InsufficientContext(
+                        "'list' needs a literal item count".to_string(),
+                    )
+                })?;
+                let element = self.fresh_type_var();
+                for _ in 0..count {
+                    self.expect(state, &element)?;
+                }
+                let list = Type::List(Box::new(state.subst.apply(&element)));
+                state.push(list);
+                Ok(())
+            }
+            "length" => {
+                // `length` has one signature for strings and one for lists,
+                // chosen by the type of its input
+                let input = self.fresh_type_var();
+                let entry = self.expect(state, &input)?;
+                match state.subst.apply(&entry.ty) {
+                    Type::String | Type::List(_) => {}
+                    Type::Variable(_) => {
+                        return Err(InferenceError::InsufficientContext(
+                            "'length' needs a String or a List".to_string(),
+                        ))
+                    }
+                    other => {
+                        return Err(InferenceError::UnificationError(Box::new((
+                            other,
+                            Type::String,
+                        ))))
+                    }
+                }
+                state.push(Type::Nat);
+                Ok(())
+            }
+            "each" | "map" | "filter" => {
+                let quote = self.expect(state, &Type::Quote)?;
+                let element = self.fresh_type_var();
+                self.expect(state, &Type::List(Box::new(element.clone())))?;
+                let effect = quote_effect(quote, word)?;
+                // What the quotation leaves per element, and what the
+                // combinator pushes at the end
+                let (produced, output) = match word {
+                    "each" => (None, None),
+                    "map" => {
+                        let mapped = self.fresh_type_var();
+                        (Some(mapped.clone()), Some(Type::List(Box::new(mapped))))
+                    }
+                    _ => (
+                        Some(Type::Bool),
+                        Some(Type::List(Box::new(element.clone()))),
+                    ),
+                };
+                self.apply_per_element(state, &effect, word, &[element], produced.as_slice())?;
+                if let Some(output) = output {
+                    state.push(output);
+                }
+                Ok(())
+            }
+            "fold" => {
+                let quote = self.expect(state, &Type::Quote)?;
+                let accumulator = self.fresh_type_var();
+                self.expect(state, &accumulator)?;
+                let element = self.fresh_type_var();
+                self.expect(state, &Type::List(Box::new(element.clone())))?;
+                let effect = quote_effect(quote, word)?;
+                self.apply_per_element(
+                    state,
+                    &effect,
+                    word,
+                    &[accumulator.clone(), element],
+                    std::slice::from_ref(&accumulator),
+                )?;
+                state.push(accumulator);
+                Ok(())
+            }
+            _ => {
+                let signature = self
+                    .word_signatures
+                    .get(word)
+                    .or_else(|| core_signatures().get(word))
+                    .cloned()
+                    .ok_or_else(|| InferenceError::UnknownWord(word.to_string()))?;
+                let signature = self.instantiate(&signature);
+                self.apply_effect(state, &signature)
+            }
+        }
+    }
+
+    /// Consume a signature's inputs from the symbolic stack and push its outputs
+    fn apply_effect(
+        &mut self,
+        state: &mut EffectState,
+        effect: &TypeSignature,
+    ) -> Result<(), InferenceError> {
+        for input in effect.inputs.iter().rev() {
+            self.expect(state, input)?;
+        }
+        for output in &effect.outputs {
+            state.push(output.clone());
+        }
+        Ok(())
+    }
+
+    /// Apply the effect of a quotation that must leave the stack shape unchanged
+    fn apply_balanced_effect(
+        &mut self,
+        state: &mut EffectState,
+        effect: &TypeSignature,
+        word: &str,
+    ) -> Result<(), InferenceError> {
+        if effect.inputs.len() != effect.outputs.len() {
+            return Err(InferenceError::UnbalancedQuote(
+                word.to_string(),
+                effect.clone(),
+            ));
+        }
+        for (input, output) in effect.inputs.iter().zip(&effect.outputs) {
+            self.unify_in(state, input, output)?;
+        }
+        self.apply_effect(state, effect)
+    }
+
+    /// Apply the effect of a quotation run once per list element
+    ///
+    /// The top inputs of the quotation take `consumed` and its top outputs
+    /// give `produced`, which the combinator handles; below them the
+    /// quotation must leave the stack shape unchanged.
+    fn apply_per_element(
+        &mut self,
+        state: &mut EffectState,
+        effect: &TypeSignature,
+        word: &str,
+        consumed: &[Type],
+        produced: &[Type],
+    ) -> Result<(), InferenceError> {
+        let (Some(inputs), Some(outputs)) = (
+            effect.inputs.len().checked_sub(consumed.len()),
+            effect.outputs.len().checked_sub(produced.len()),
+        ) else {
+            return Err(InferenceError::UnbalancedQuote(
+                word.to_string(),
+                effect.clone(),
+            ));
+        };
+        let pairs = effect.inputs[inputs..]
+            .iter()
+            .zip(consumed)
+            .chain(effect.outputs[outputs..].iter().zip(produced));
+        for (ty, expected) in pairs {
+            self.unify_in(state, ty, expected)?;
+        }
+        let rest = TypeSignature {
+            inputs: effect.inputs[..inputs].to_vec(),
+            outputs: effect.outputs[..outputs].to_vec(),
+        };
+        self.apply_balanced_effect(state, &rest, word)
+    }
+
+    /// Pop a value of the expected type, drawing a new input if the stack is empty
+    fn expect(
+        &mut self,
+        state: &mut EffectState,
+        expected: &Type,
+    ) -> Result<StackEntry, InferenceError> {
+        let entry = match state.stack.pop() {
+            Some(entry) => entry,
+            None => {
+                let ty = self.fresh_type_var();
+                state.inputs.insert(0, ty.clone());
+                StackEntry {
+                    ty,
+                    effect: None,
+                    count: None,
+                }
+            }
+        };
+        self.unify_in(state, &entry.ty, expected)?;
+        Ok(entry)
+    }
+
+    /// Unify two types under the state's substitution, extending it
+    fn unify_in(
+        &mut self,
+        state: &mut EffectState,
+        t1: &Type,
+        t2: &Type,
+    ) -> Result<(), InferenceError> {
+        let step = self.unify(&state.subst.apply(t1), &state.subst.apply(t2))?;
+        state.subst = step.compose(&state.subst);
+        Ok(())
+    }
+
+    /// Extend an effect with `depth` untouched values below its inputs and outputs
+    fn pad_effect(&mut self, effect: &TypeSignature, depth: usize) -> TypeSignature {
+        let mut padded = effect.clone();
+        for _ in 0..depth {
+            let t = self.fresh_type_var();
+            padded.inputs.insert(0, t.clone());
+            padded.outputs.insert(0, t);
+        }
+        padded
+    }
+
+    /// Copy a signature with fresh type variables
+    fn instantiate(&mut self, signature: &TypeSignature) -> TypeSignature {
+        let mut renaming = HashMap::new();
+        TypeSignature {
+            inputs: signature
+                .inputs
+                .iter()
+                .map(|ty| self.rename_variables(ty, &mut renaming))
+                .collect(),
+            outputs: signature
+                .outputs
+                .iter()
+                .map(|ty| self.rename_variables(ty, &mut renaming))
+                .collect(),
+        }
+    }
+
+    fn rename_variables(&mut self, ty: &Type, renaming: &mut HashMap<String, Type>) -> Type {
+        match ty {
+            Type::Variable(var) => {
+                if let Some(fresh) = renaming.get(var) {
+                    return fresh.clone();
+                }
+                let fresh = self.fresh_type_var();
+                renaming.insert(var.clone(), fresh.clone());
+                fresh
+            }
+            Type::Option(inner) => Type::Option(Box::new(self.rename_variables(inner, renaming))),
+            Type::List(inner) => Type::List(Box::new(self.rename_variables(inner, renaming))),
+            Type::Result(ok, err) => Type::Result(
+                Box::new(self.rename_variables(ok, renaming)),
+                Box::new(self.rename_variables(err, renaming)),
+            ),
+            Type::Composite { name, fields } => Type::Composite {
+                name: name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|(key, field)| (key.clone(), self.rename_variables(field, renaming)))
+                    .collect(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    /// The type of a literal value
+    fn literal_type(&mut self, value: &Value) -> Type {
+        match value {
+            Value::Unit => Type::Unit,
+            Value::Bool(_) => Type::Bool,
+            Value::Nat(_) => Type::Nat,
+            #[cfg(feature = "bigint")]
+            Value::BigNat(_) => Type::Nat,
+            Value::String(_) => Type::String,
+            Value::Ordinal(_) => Type::Ordinal,
+            Value::Quote(_) => Type::Quote,
+            Value::Composite { type_name, fields } => Type::Composite {
+                name: type_name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|(key, field)| (key.clone(), self.literal_type(field)))
+                    .collect(),
+            },
+            Value::Option(Some(inner)) => Type::Option(Box::new(self.literal_type(inner))),
+            Value::Option(None) => Type::Option(Box::new(self.fresh_type_var())),
+            Value::Result(Ok(inner)) => Type::Result(
+                Box::new(self.literal_type(inner)),
+                Box::new(self.fresh_type_var()),
+            ),
+            Value::Result(Err(inner)) => Type::Result(
+                Box::new(self.fresh_type_var()),
+                Box::new(self.literal_type(inner)),
+            ),
+            Value::List(values) => {
+                let element = match values.first() {
+                    Some(first) => self.literal_type(first),
+                    None => self.fresh_type_var(),
+                };
+                Type::List(Box::new(element))
+            }
+            Value::Variant { type_name, .. } => Type::Composite {
+                name: type_name.clone(),
+                fields: HashMap::new(),
+            },
+        }
+    }
+}
+
+/// Signatures of the core library words, built once
+pub(crate) fn core_signatures() -> &'static HashMap<String, TypeSignature> {
+    static SIGNATURES: OnceLock<HashMap<String, TypeSignature>> = OnceLock::new();
+    SIGNATURES.get_or_init(|| CoreLibrary::new().get_core_signatures().clone())
+}
+
+/// A value on the symbolic stack during inference
+#[derive(Debug, Clone)]
+struct StackEntry {
+    ty: Type,
+    /// Stack effect of a quotation literal, when it could be inferred
+    effect: Option<TypeSignature>,
+    /// Value of a `Nat` literal, used as the item count of `list`
+    count: Option<u64>,
+}
+
+/// Symbolic stack for the token sequence being inferred
+#[derive(Debug, Default)]
+struct EffectState {
+    /// Types consumed from below the starting stack, bottom first
+    inputs: Vec<Type>,
+    /// Values produced so far, bottom first
+    stack: Vec<StackEntry>,
+    /// Substitution accumulated by unification
+    subst: Substitution,
+}
+
+impl EffectState {
+    fn push(&mut self, ty: Type) {
+        self.stack.push(StackEntry {
+            ty,
+            effect: None,
+            count: None,
+        });
+    }
+}
+
+/// The inferred effect of a quotation consumed by `word`
+fn quote_effect(entry: StackEntry, word: &str) -> Result<TypeSignature, InferenceError> {
+    entry.effect.ok_or_else(|| {
+        InferenceError::InsufficientContext(format!(
+            "'{}' applied to a quotation of unknown stack effect",
+            word
+        ))
+    })
+}
+
+/// Collect the names of the type variables in a type
+fn collect_type_variables(ty: &Type, vars: &mut Vec<String>) {
+    match ty {
+        Type::Variable(var) if !vars.contains(var) => vars.push(var.clone()),
+        Type::Option(inner) | Type::List(inner) => collect_type_variables(inner, vars),
+        Type::Result(ok, err) => {
+            collect_type_variables(ok, vars);
+            collect_type_variables(err, vars);
+        }
+        Type::Composite { fields, .. } => {
+            for field in fields.values() {
+                collect_type_variables(field, vars);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl Default for TypeInferer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_type_vars() {
+        let mut inferer = TypeInferer::new();
+        let t1 = inferer.fresh_type_var();
+        let t2 = inferer.fresh_type_var();
+
+        assert_ne!(t1, t2);
+        assert!(matches!(t1, Type::Variable(_)));
+        assert!(matches!(t2, Type::Variable(_)));
+    }
+
+    #[test]
+    fn test_unify_concrete_types() {
+        let mut inferer = TypeInferer::new();
+        let result = inferer.unify(&Type::Nat, &Type::Nat);
+        assert!(result.is_ok());
+
+        let result = inferer.unify(&Type::Nat, &Type::Bool);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unify_variables() {
+        let mut inferer = TypeInferer::new();
+        let var = Type::Variable("T0".to_string());
+        let result = inferer.unify(&var, &Type::Nat).unwrap();
+
+        assert_eq!(result.get("T0"), Some(&Type::Nat));
+    }
+
+    #[test]
+    fn test_occurs_check() {
+        let inferer = TypeInferer::new();
+        let var = "T0";
+        let recursive_type = Type::Variable("T0".to_string());
+
+        assert!(inferer.occurs_check(var, &recursive_type));
+        assert!(!inferer.occurs_check(var, &Type::Nat));
+
+        // Variables nested in containers are found too
+        let nested = Type::Result(
+            Box::new(Type::Nat),
+            Box::new(Type::List(Box::new(recursive_type.clone()))),
+        );
+        assert!(inferer.occurs_check(var, &nested));
+
+        let mut inferer = TypeInferer::new();
+        let option = Type::Option(Box::new(recursive_type.clone()));
+        assert!(matches!(
+            inferer.unify(&recursive_type, &option),
+            Err(InferenceError::OccursCheck(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_unify_containers() {
+        let mut inferer = TypeInferer::new();
+        let var = |name: &str| Type::Variable(name.to_string());
+        let list = |ty: Type| Type::List(Box::new(ty));
+        let result = |ok: Type, err: Type| Type::Result(Box::new(ok), Box::new(err));
+
+        assert!(inferer.unify(&Type::String, &Type::String).is_ok());
+        assert!(inferer.unify(&Type::String, &Type::Nat).is_err());
+
+        let subst = inferer.unify(&list(var("T0")), &list(Type::Nat)).unwrap();
+        assert_eq!(subst.get("T0"), Some(&Type::Nat));
+        assert!(inferer
+            .unify(&list(Type::Bool), &Type::Option(Box::new(Type::Bool)))
+            .is_err());
+
+        // A variable shared between components is bound consistently
+        let subst = inferer
+            .unify(
+                &result(var("T0"), list(var("T0"))),
+                &result(Type::String, var("T1")),
+            )
+            .unwrap();
+        assert_eq!(subst.apply(&var("T1")), list(Type::String));
+        assert!(inferer
+            .unify(
+                &result(var("T0"), var("T0")),
+                &result(Type::Nat, Type::Bool)
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_infer_arithmetic() {
+        let mut inferer = TypeInferer::new();
+        let tokens = vec![Token::Word("+".to_string())];
+        let result = inferer.infer_word_type(&tokens).unwrap();
+
+        assert_eq!(result.inputs, vec![Type::Nat, Type::Nat]);
+        assert_eq!(result.outputs, vec![Type::Nat]);
+    }
+
+    #[test]
+    fn test_infer_literal() {
+        let mut inferer = TypeInferer::new();
+        let tokens = vec![Token::Literal(Value::Nat(42))];
+        let result = inferer.infer_word_type(&tokens).unwrap();
+
+        assert_eq!(result.inputs, vec![]);
+        assert_eq!(result.outputs, vec![Type::Nat]);
+    }
+
+    #[test]
+    fn test_infer_stack_ops() {
+        let mut inferer = TypeInferer::new();
+        let tokens = vec![Token::Word("dup".to_string())];
+        let result = inferer.infer_word_type(&tokens).unwrap();
+
+        assert_eq!(result.inputs.len(), 1);
+        assert_eq!(result.outputs.len(), 2);
+        // Should be T -> T T for some type T
+        if let (Type::Variable(v1), Type::Variable(v2), Type::Variable(v3)) =
+            (&result.inputs[0], &result.outputs[0], &result.outputs[1])
+        {
+            assert_eq!(v1, v2);
+            assert_eq!(v1, v3);
+        }
+    }
+
+    #[test]
+    fn test_infer_composition() {
+        let mut inferer = TypeInferer::new();
+        let tokens = vec![Token::Word("dup".to_string()), Token::Word("*".to_string())];
+        let result = inferer.infer_word_type(&tokens).unwrap();
+
+        assert_eq!(result.inputs, vec![Type::Nat]);
+        assert_eq!(result.outputs, vec![Type::Nat]);
+    }
+
+    #[test]
+    fn test_infer_length() {
+        let mut inferer = TypeInferer::new();
+        let length = Token::Word("length".to_string());
+        let literal = |value: Value| Token::Literal(value);
+
+        for value in [
+            Value::String("abc".to_string()),
+            Value::List(vec![Value::Nat(1)]),
+        ] {
+            let result = inferer
+                .infer_word_type(&[literal(value), length.clone()])
+                .unwrap();
+            assert_eq!(result.outputs, vec![Type::Nat]);
+        }
+
+        assert!(matches!(
+            inferer.infer_word_type(&[literal(Value::Bool(true)), length.clone()]),
+            Err(InferenceError::UnificationError(_))
+        ));
+        assert!(matches!(
+            inferer.infer_word_type(&[length]),
+            Err(InferenceError::InsufficientContext(_))
+        ));
+    }
+
+    #[test]
+    fn test_infer_polymorphic_containers() {
+        let mut inferer = TypeInferer::new();
+        let word = |w: &str| Token::Word(w.to_string());
+        let nat = |n: u64| Token::Literal(Value::Nat(n));
+
+        // `Some` wraps whatever is on the stack
+        let result = inferer.infer_word_type(&[nat(1), word("Some")]).unwrap();
+        assert_eq!(result.outputs, vec![Type::Option(Box::new(Type::Nat))]);
+
+        // `Err` fixes the error type and leaves the success type open
+        let result = inferer
+            .infer_word_type(&[Token::Literal(Value::String("no".to_string())), word("Err")])
+            .unwrap();
+        match &result.outputs[..] {
+            [Type::Result(ok, err)] => {
+                assert!(matches!(ok.as_ref(), Type::Variable(_)));
+                assert_eq!(err.as_ref(), &Type::String);
+            }
+            other => panic!("Expected a Result, got {:?}", other),
+        }
+
+        // `list` takes its literal count of items, which must agree
+        let result = inferer
+            .infer_word_type(&[nat(1), word("swap"), nat(2), word("list")])
+            .unwrap();
+        assert_eq!(result.inputs, vec![Type::Nat]);
+        assert_eq!(result.outputs, vec![Type::List(Box::new(Type::Nat))]);
+        assert!(inferer
+            .infer_word_type(&[
+                nat(1),
+                Token::Literal(Value::Bool(true)),
+                nat(2),
+                word("list")
+            ])
+            .is_err());
+
+        let result = inferer.infer_word_type(&[word("print-string")]).unwrap();
+        assert_eq!(result.inputs, vec![Type::String]);
+        assert!(result.outputs.is_empty());
+    }
+
+    #[test]
+    fn test_infer_quote_combinators() {
+        let mut inferer = TypeInferer::new();
+        let tokens = vec![
+            Token::Literal(Value::Quote(vec![Token::Literal(Value::Nat(1))])),
+            Token::Literal(Value::Quote(vec![Token::Literal(Value::Nat(2))])),
+            Token::Word("if".to_string()),
+        ];
+        let result = inferer.infer_word_type(&tokens).unwrap();
+        assert_eq!(result.inputs, vec![Type::Bool]);
+        assert_eq!(result.outputs, vec![Type::Nat]);
+
+        // Branches with different effects are rejected
+        let tokens = vec![
+            Token::Literal(Value::Quote(vec![Token::Literal(Value::Nat(1))])),
+            Token::Literal(Value::Quote(vec![])),
+            Token::Word("if".to_string()),
+        ];
+        assert!(matches!(
+            inferer.infer_word_type(&tokens),
+            Err(InferenceError::StackEffectMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_infer_list_combinators() {
+        let mut inferer = TypeInferer::new();
+        let word = |w: &str| Token::Word(w.to_string());
+        let nat = |n: u64| Token::Literal(Value::Nat(n));
+        let quote = |body: Vec<Token>| Token::Literal(Value::Quote(body));
+        let nats = Type::List(Box::new(Type::Nat));
+
+        let result = inferer
+            .infer_word_type(&[quote(vec![nat(1), word("+")]), word("map")])
+            .unwrap();
+        assert_eq!(result.inputs, vec![nats.clone()]);
+        assert_eq!(result.outputs, vec![nats.clone()]);
+
+        let result = inferer
+            .infer_word_type(&[quote(vec![nat(3), word("<")]), word("filter")])
+            .unwrap();
+        assert_eq!(result.outputs, vec![nats.clone()]);
+
+        let result = inferer
+            .infer_word_type(&[nat(0), quote(vec![word("+")]), word("fold")])
+            .unwrap();
+        assert_eq!(result.inputs, vec![nats.clone()]);
+        assert_eq!(result.outputs, vec![Type::Nat]);
+
+        // `each` threads the rest of the stack through every call
+        let result = inferer
+            .infer_word_type(&[quote(vec![word("+")]), word("each")])
+            .unwrap();
+        assert_eq!(result.inputs, vec![Type::Nat, nats]);
+        assert_eq!(result.outputs, vec![Type::Nat]);
+
+        // A quotation that leaves values behind for every element is rejected
+        assert!(matches!(
+            inferer.infer_word_type(&[quote(vec![word("dup")]), word("each")]),
+            Err(InferenceError::UnbalancedQuote(_, _))
+        ));
+        assert!(matches!(
+            inferer.infer_word_type(&[word("map")]),
+            Err(InferenceError::InsufficientContext(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_definition_reports_assumptions() {
+        let mut inferer = TypeInferer::new();
+        let declared = TypeSignature {
+            inputs: vec![Type::Quote],
+            outputs: vec![],
+        };
+        let verification = inferer
+            .check_definition("run", &declared, &[Token::Word("call".to_string())])
+            .unwrap();
+        assert!(matches!(verification, Verification::Assumed(_)));
+        let verification = inferer
+            .check_definition("skip", &declared, &[Token::Word("drop".to_string())])
+            .unwrap();
+        assert_eq!(verification, Verification::Checked);
+    }
+
+    #[test]
+    fn test_check_signature() {
+        let mut inferer = TypeInferer::new();
+        let var = |name: &str| Type::Variable(name.to_string());
+        let sig = |inputs, outputs| TypeSignature { inputs, outputs };
+
+        let dup = inferer
+            .infer_word_type(&[Token::Word("dup".to_string())])
+            .unwrap();
+        assert!(inferer
+            .check_signature(&sig(vec![var("a")], vec![var("a"), var("a")]), &dup)
+            .is_ok());
+        assert!(inferer
+            .check_signature(&sig(vec![Type::Nat], vec![Type::Nat, Type::Nat]), &dup)
+            .is_ok());
+        assert!(inferer
+            .check_signature(&sig(vec![var("a")], vec![var("a")]), &dup)
+            .is_err());
+
+        // Declared variables are rigid
+        let increment = inferer
+            .infer_word_type(&[Token::Literal(Value::Nat(1)), Token::Word("+".to_string())])
+            .unwrap();
+        assert!(inferer
+            .check_signature(&sig(vec![var("a")], vec![var("a")]), &increment)
+            .is_err());
+    }
+}
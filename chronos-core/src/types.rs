@@ -16,6 +16,10 @@ pub enum Value {
     Bool(bool),
     /// Natural numbers (non-negative integers)
     Nat(u64),
+    /// Natural numbers too large for `Nat`, only produced by overflowing
+    /// `Nat` arithmetic when the `bigint` feature is enabled
+    #[cfg(feature = "bigint")]
+    BigNat(num_bigint::BigUint),
     /// String values
     String(String),
     /// Ordinal values for proof-theoretic analysis
@@ -33,10 +37,19 @@ pub enum Value {
     Result(Result<Box<Value>, Box<Value>>),
     /// List type - ordered collection of values
     List(Vec<Value>),
+    /// A value of a `data` type, built by one of its constructors
+    Variant {
+        type_name: String,
+        constructor: String,
+        args: Vec<Value>,
+    },
 }
 
 /// Ordinal values for termination analysis
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Equality and ordering compare Cantor normal forms; see
+/// [`OrdinalValue::normalize`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OrdinalValue {
     /// Zero ordinal
     Zero,
@@ -153,18 +166,202 @@ pub struct WordDefinition {
 pub struct TypeDefinition {
     /// Name of the type
     pub name: String,
-    /// Field definitions
-    pub fields: HashMap<String, Type>,
+    /// Field definitions, in declaration order
+    pub fields: Vec<(String, Type)>,
     /// Constructor signature
     pub constructor_signature: TypeSignature,
 }
 
+impl TypeDefinition {
+    /// The type of values built by the constructor
+    pub fn composite_type(&self) -> Type {
+        Type::Composite {
+            name: self.name.clone(),
+            fields: self.fields.iter().cloned().collect(),
+        }
+    }
+
+    /// The words a `type` declaration generates, with their signatures
+    ///
+    /// For `type Point { x: Nat }` these are the constructor `Point`
+    /// ( Nat -> Point ), the getter `Point.x` ( Point -> Nat ), the setter
+    /// `Point.x!` ( Point Nat -> Point ) and the predicate `Point?` ( a -> Bool ).
+    pub fn word_signatures(&self) -> Vec<(String, TypeSignature)> {
+        let this = self.composite_type();
+        let mut words = vec![
+            (self.name.clone(), self.constructor_signature.clone()),
+            (
+                format!("{}?", self.name),
+                TypeSignature {
+                    inputs: vec![Type::Variable("a".to_string())],
+                    outputs: vec![Type::Bool],
+                },
+            ),
+        ];
+        for (field, ty) in &self.fields {
+            words.push((
+                format!("{}.{}", self.name, field),
+                TypeSignature {
+                    inputs: vec![this.clone()],
+                    outputs: vec![ty.clone()],
+                },
+            ));
+            words.push((
+                format!("{}.{}!", self.name, field),
+                TypeSignature {
+                    inputs: vec![this.clone(), ty.clone()],
+                    outputs: vec![this.clone()],
+                },
+            ));
+        }
+        words
+    }
+}
+
+/// A sum type declared with `data Name = Ctor Type ... | Ctor ... ;`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDefinition {
+    /// Name of the type
+    pub name: String,
+    /// The constructors, in declaration order
+    pub constructors: Vec<DataConstructor>,
+}
+
+/// One constructor of a `data` type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataConstructor {
+    pub name: String,
+    /// The types of its arguments
+    pub fields: Vec<Type>,
+}
+
+impl DataDefinition {
+    /// The type of values built by the constructors
+    pub fn data_type(&self) -> Type {
+        Type::Composite {
+            name: self.name.clone(),
+            fields: HashMap::new(),
+        }
+    }
+
+    /// The signature of each constructor, so that `data Shape = Rect Nat Nat ;`
+    /// generates `Rect` ( Nat Nat -> Shape )
+    pub fn constructor_signatures(&self) -> Vec<(String, TypeSignature)> {
+        self.constructors
+            .iter()
+            .map(|constructor| {
+                let signature = TypeSignature {
+                    inputs: constructor.fields.clone(),
+                    outputs: vec![self.data_type()],
+                };
+                (constructor.name.clone(), signature)
+            })
+            .collect()
+    }
+
+    /// The constructor named `name`
+    pub fn constructor(&self, name: &str) -> Option<&DataConstructor> {
+        self.constructors
+            .iter()
+            .find(|constructor| constructor.name == name)
+    }
+}
+
+/// A region of source code
+///
+/// Lines and columns are 1-based and counted in characters, and kept as
+/// `u32` so that errors carrying a span stay small. `offset` is the
+/// character index of the first character, and `len` may run past the end
+/// of the line for tokens that span several lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub offset: u32,
+    pub line: u32,
+    pub column: u32,
+    pub len: u32,
+}
+
+/// A value tagged with the source span it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+/// A token together with its position in the source
+pub type SpannedToken = Spanned<Token>;
+
+impl Span {
+    /// Create a span starting at the given position
+    pub fn new(offset: u32, line: u32, column: u32, len: u32) -> Self {
+        Span {
+            offset,
+            line,
+            column,
+            len,
+        }
+    }
+
+    /// Locate a character offset within `source`
+    pub fn at_offset(source: &str, offset: u32, len: u32) -> Self {
+        let mut line = 1;
+        let mut column = 1;
+        for ch in source.chars().take(offset as usize) {
+            if ch == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        Span::new(offset, line, column, len)
+    }
+
+    /// The span covering both `self` and a later span `end`
+    pub fn to(self, end: Span) -> Span {
+        let stop = (end.offset + end.len).max(self.offset + self.len);
+        Span {
+            len: stop - self.offset,
+            ..self
+        }
+    }
+
+    /// Render the spanned line of `source` with a caret underline
+    pub fn underline(&self, source: &str) -> String {
+        let text = source.lines().nth(self.line as usize - 1).unwrap_or("");
+        let line_len = text.chars().count();
+        let available = (line_len + 1).saturating_sub(self.column as usize);
+        let width = (self.len as usize).min(available).max(1);
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        format!(
+            "{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            number,
+            text,
+            gutter,
+            " ".repeat(self.column as usize - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl<T> Spanned<T> {
+    /// Tag a value with a span
+    pub fn new(node: T, span: Span) -> Self {
+        Spanned { node, span }
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Value::Unit => write!(f, "()"),
             Value::Bool(b) => write!(f, "{}", b),
             Value::Nat(n) => write!(f, "{}", n),
+            #[cfg(feature = "bigint")]
+            Value::BigNat(n) => write!(f, "{}", n),
             Value::String(s) => write!(f, "{}", s),
             Value::Ordinal(ord) => write!(f, "{}", ord),
             Value::Quote(tokens) => {
@@ -175,6 +372,8 @@ impl fmt::Display for Value {
                 write!(f, "]")
             }
             Value::Composite { type_name, fields } => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
                 write!(f, "{}{{ ", type_name)?;
                 for (key, value) in fields {
                     write!(f, "{}:{} ", key, value)?;
@@ -199,6 +398,16 @@ impl fmt::Display for Value {
                 }
                 write!(f, "]")
             }
+            Value::Variant {
+                constructor, args, ..
+            } => {
+                write!(f, "{}", constructor)?;
+                if !args.is_empty() {
+                    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "({})", args.join(", "))?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -209,7 +418,10 @@ impl fmt::Display for OrdinalValue {
             OrdinalValue::Zero => write!(f, "0"),
             OrdinalValue::Finite(n) => write!(f, "{}", n),
             OrdinalValue::Omega => write!(f, "ω"),
-            OrdinalValue::OmegaPower(exp) => write!(f, "ω^{}", exp),
+            OrdinalValue::OmegaPower(exp) => match exp.as_ref() {
+                OrdinalValue::Sum(_) | OrdinalValue::Product(..) => write!(f, "ω^({})", exp),
+                _ => write!(f, "ω^{}", exp),
+            },
             OrdinalValue::Sum(ordinals) => {
                 for (i, ord) in ordinals.iter().enumerate() {
                     if i > 0 {
@@ -219,14 +431,31 @@ impl fmt::Display for OrdinalValue {
                 }
                 Ok(())
             }
-            OrdinalValue::Product(a, b) => write!(f, "{} * {}", a, b),
+            OrdinalValue::Product(a, b) => {
+                let factor = |f: &mut fmt::Formatter<'_>, x: &OrdinalValue| match x {
+                    OrdinalValue::Sum(_) => write!(f, "({})", x),
+                    _ => write!(f, "{}", x),
+                };
+                factor(f, a)?;
+                write!(f, " * ")?;
+                factor(f, b)
+            }
         }
     }
 }
 
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Token::Literal(Value::List(values)) => {
+                write!(f, "#{}", list_literal_source(values))
+            }
             Token::Literal(value) => write!(f, "{}", value),
             Token::Word(name) => write!(f, "{}", name),
             Token::QuoteStart => write!(f, "["),
@@ -235,7 +464,7 @@ impl fmt::Display for Token {
             Token::MatchExpression { value, arms } => {
                 write!(f, "match {} ", value)?;
                 for arm in arms {
-                    write!(f, "| {:?} -> ", arm.pattern)?;
+                    write!(f, "| {} -> ", arm.pattern)?;
                     for token in &arm.body {
                         write!(f, "{} ", token)?;
                     }
@@ -246,6 +475,42 @@ impl fmt::Display for Token {
     }
 }
 
+/// The elements of a list literal as they are written in source
+fn list_literal_source(values: &[Value]) -> String {
+    let elements: Vec<String> = values
+        .iter()
+        .map(|value| match value {
+            Value::String(s) => format!("{:?}", s),
+            Value::List(values) => list_literal_source(values),
+            value => value.to_string(),
+        })
+        .collect();
+    format!("[{}]", elements.join(", "))
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Variable(name) => write!(f, "{}", name),
+            Pattern::Literal(Value::String(s)) => write!(f, "{:?}", s),
+            Pattern::Literal(value) => write!(f, "{}", value),
+            Pattern::Constructor { name, args } => {
+                write!(f, "{}", name)?;
+                if !args.is_empty() {
+                    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "({})", args.join(", "))?;
+                }
+                Ok(())
+            }
+            Pattern::List(patterns) => {
+                let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+                write!(f, "[{}]", patterns.join(", "))
+            }
+        }
+    }
+}
+
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -255,6 +520,7 @@ impl fmt::Display for Type {
             Type::String => write!(f, "String"),
             Type::Ordinal => write!(f, "Ordinal"),
             Type::Quote => write!(f, "Quote"),
+            Type::Composite { name, fields } if fields.is_empty() => write!(f, "{}", name),
             Type::Composite { name, fields } => {
                 write!(f, "{}{{ ", name)?;
                 for (key, ty) in fields {
@@ -269,3 +535,17 @@ impl fmt::Display for Type {
         }
     }
 }
+
+impl fmt::Display for TypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for ty in &self.inputs {
+            write!(f, " {}", ty)?;
+        }
+        write!(f, " ->")?;
+        for ty in &self.outputs {
+            write!(f, " {}", ty)?;
+        }
+        write!(f, " )")
+    }
+}
@@ -3,10 +3,22 @@
 //! This module implements the stack-based virtual machine that executes C∀O code.
 //! It follows the concatenative programming paradigm with postfix notation.
 
+use crate::arithmetic::{self, NatOp};
+use crate::axiom::AxiomRegistry;
+use crate::core_lib::CoreLibrary;
+use crate::debugger::Debugger;
+use crate::observer::{ExecutionContext, ObserverId, VmObserver};
+use crate::ordinal::{self, Cnf, OrdinalError};
+use crate::property::{self, CheckFailure, Generator};
+use crate::testing::TestCase;
+use crate::theorem::{self, Counterexample, Proof, Prover, Theorem};
+use crate::type_inference::{InferenceError, MissingArms, TypeInferer, Verification};
 use crate::types::{
-    MatchArm, OrdinalValue, Pattern, Token, Type, TypeSignature, Value, WordDefinition,
+    DataConstructor, DataDefinition, MatchArm, OrdinalValue, Pattern, Token, Type, TypeDefinition,
+    TypeSignature, Value, WordDefinition,
 };
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 
 /// Errors that can occur during VM execution
@@ -20,12 +32,32 @@ pub enum VmError {
     UnknownWord(String),
     /// Division by zero
     DivisionByZero,
+    /// A `Nat` result does not fit in the machine representation
+    ArithmeticOverflow {
+        operation: String,
+        operands: Vec<String>,
+    },
     /// Invalid operation
     InvalidOperation(String),
     /// Runtime error
     RuntimeError(String),
     /// Ordinal verification failed
     OrdinalVerificationFailed(String),
+    /// A word definition does not match its declared type signature
+    TypeError {
+        word: String,
+        message: String,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    /// An `assert` word found a value other than the one expected
+    AssertionFailed {
+        word: String,
+        expected: Vec<Value>,
+        actual: Vec<Value>,
+    },
+    /// A `theorem` could not be proved, or a `lemma` was refuted
+    TheoremFailed { theorem: String, reason: String },
 }
 
 impl fmt::Display for VmError {
@@ -37,17 +69,94 @@ impl fmt::Display for VmError {
             }
             VmError::UnknownWord(word) => write!(f, "Unknown word: {}", word),
             VmError::DivisionByZero => write!(f, "Division by zero"),
+            VmError::ArithmeticOverflow {
+                operation,
+                operands,
+            } => write!(
+                f,
+                "Arithmetic overflow in '{}' with operands [{}]",
+                operation,
+                operands.join(", ")
+            ),
             VmError::InvalidOperation(msg) => write!(f, "Invalid operation: {}", msg),
             VmError::RuntimeError(msg) => write!(f, "Runtime error: {}", msg),
             VmError::OrdinalVerificationFailed(msg) => {
                 write!(f, "Ordinal verification failed: {}", msg)
             }
+            VmError::TypeError {
+                word,
+                message,
+                expected,
+                found,
+            } => {
+                write!(f, "Type error in word '{}': {}", word, message)?;
+                if let Some(expected) = expected {
+                    write!(f, " (declared {}", expected)?;
+                    if let Some(found) = found {
+                        write!(f, ", body has {}", found)?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+            VmError::AssertionFailed {
+                word,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Assertion failed in '{}': expected {}, found {}",
+                word,
+                format_values(expected),
+                format_values(actual)
+            ),
+            VmError::TheoremFailed { theorem, reason } => {
+                write!(f, "Theorem '{}' failed: {}", theorem, reason)
+            }
         }
     }
 }
 
+/// Values as a bracketed, space-separated list
+pub(crate) fn format_values(values: &[Value]) -> String {
+    let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    format!("[{}]", values.join(" "))
+}
+
 impl std::error::Error for VmError {}
 
+/// A word generated by a `type` declaration
+#[derive(Debug, Clone)]
+enum TypeWord {
+    /// `Name` builds a value from its fields
+    Constructor(String),
+    /// `Name?` tests whether a value is of the type
+    Predicate(String),
+    /// `Name.field` reads a field
+    Getter { type_name: String, field: String },
+    /// `Name.field!` replaces a field
+    Setter { type_name: String, field: String },
+    /// A constructor of a `data` type
+    Variant {
+        type_name: String,
+        constructor: String,
+        arity: usize,
+    },
+}
+
+impl TypeWord {
+    /// The type the word was generated for
+    fn type_name(&self) -> &str {
+        match self {
+            TypeWord::Constructor(type_name)
+            | TypeWord::Predicate(type_name)
+            | TypeWord::Getter { type_name, .. }
+            | TypeWord::Setter { type_name, .. }
+            | TypeWord::Variant { type_name, .. } => type_name,
+        }
+    }
+}
+
 /// The virtual machine state
 pub struct VirtualMachine {
     /// The data stack
@@ -66,8 +175,55 @@ pub struct VirtualMachine {
     current_word_name: Option<String>,
     /// Tokens accumulated for the current word definition
     current_definition: Vec<Token>,
+    /// Whether the current definition is a `test:` rather than a word
+    defining_test: bool,
+    /// Tests defined with `test:`, in definition order
+    tests: Vec<TestCase>,
     /// Whether we're currently parsing a type signature
     parsing_type_signature: bool,
+    /// Tokens accumulated for the current type signature declaration
+    current_signature: Vec<Token>,
+    /// Signatures declared with `::` whose definitions have not been seen yet
+    declared_signatures: HashMap<String, TypeSignature>,
+    /// Words whose declared signature could not be checked against the
+    /// body, with the reason
+    assumed_signatures: BTreeMap<String, String>,
+    /// Whether the signature being read belongs to an `axiom` declaration
+    declaring_axiom: bool,
+    /// Axioms declared with `axiom`
+    axioms: AxiomRegistry,
+    /// Whether we're currently reading a `type Name { ... }` declaration
+    parsing_type_definition: bool,
+    /// Tokens accumulated for the current type declaration
+    current_type_definition: Vec<Token>,
+    /// Types declared with `type`, by name
+    type_definitions: HashMap<String, TypeDefinition>,
+    /// Whether we're currently reading a `data Name = ... ;` declaration
+    parsing_data_definition: bool,
+    /// Tokens accumulated for the current data declaration
+    current_data_definition: Vec<Token>,
+    /// Types declared with `data`, by name
+    data_definitions: HashMap<String, DataDefinition>,
+    /// Words generated by the declared types
+    type_words: HashMap<String, TypeWord>,
+    /// Whether we're currently reading a `theorem` or `lemma` declaration
+    parsing_theorem: bool,
+    /// Tokens accumulated for the current theorem, starting with its keyword
+    current_theorem: Vec<Token>,
+    /// Theorems proved so far, in declaration order
+    proofs: Vec<Proof>,
+    /// Equations assumed with `lemma`, in declaration order
+    lemmas: Vec<Theorem>,
+    /// The inputs the last failed `equiv?` disagreed on
+    last_counterexample: Option<Counterexample>,
+    /// Observers notified of execution events, in registration order
+    observers: Vec<(ObserverId, Box<dyn VmObserver>)>,
+    /// Identifier for the next registered observer
+    next_observer_id: usize,
+    /// Step debugger consulted before each executed token, after the observers
+    debugger: Option<Debugger>,
+    /// Whether an error is propagating and has already been reported
+    unwinding: bool,
 }
 
 impl VirtualMachine {
@@ -82,7 +238,30 @@ impl VirtualMachine {
             defining_word: false,
             current_word_name: None,
             current_definition: Vec::new(),
+            defining_test: false,
+            tests: Vec::new(),
             parsing_type_signature: false,
+            current_signature: Vec::new(),
+            declared_signatures: HashMap::new(),
+            assumed_signatures: BTreeMap::new(),
+            declaring_axiom: false,
+            axioms: AxiomRegistry::new(),
+            parsing_type_definition: false,
+            current_type_definition: Vec::new(),
+            type_definitions: HashMap::new(),
+            parsing_data_definition: false,
+            current_data_definition: Vec::new(),
+            data_definitions: HashMap::new(),
+            type_words: HashMap::new(),
+            parsing_theorem: false,
+            current_theorem: Vec::new(),
+            proofs: Vec::new(),
+            lemmas: Vec::new(),
+            last_counterexample: None,
+            observers: Vec::new(),
+            next_observer_id: 0,
+            debugger: None,
+            unwinding: false,
         };
         vm.load_core_library();
         vm
@@ -157,15 +336,146 @@ impl VirtualMachine {
 
         // Control flow
         self.define_builtin("if", vec![Type::Bool, Type::Quote, Type::Quote], vec![]);
+        self.define_builtin("when", vec![Type::Bool, Type::Quote], vec![]);
+        self.define_builtin("unless", vec![Type::Bool, Type::Quote], vec![]);
+        self.define_builtin("times", vec![Type::Nat, Type::Quote], vec![]);
+        self.define_builtin("call", vec![Type::Quote], vec![]);
+        self.define_builtin("while", vec![Type::Nat, Type::Quote, Type::Quote], vec![]);
+
+        // Bounded iteration over lists
+        let list_of = |name: &str| Type::List(Box::new(Type::Variable(name.to_string())));
+        self.define_builtin("each", vec![list_of("a"), Type::Quote], vec![]);
+        self.define_builtin("map", vec![list_of("a"), Type::Quote], vec![list_of("b")]);
+        self.define_builtin(
+            "filter",
+            vec![list_of("a"), Type::Quote],
+            vec![list_of("a")],
+        );
+        self.define_builtin(
+            "fold",
+            vec![list_of("a"), Type::Variable("b".to_string()), Type::Quote],
+            vec![Type::Variable("b".to_string())],
+        );
+        self.define_builtin(
+            "range",
+            vec![Type::Nat, Type::Nat],
+            vec![Type::List(Box::new(Type::Nat))],
+        );
+
+        // List operations; the `?` variants return `None` where the plain
+        // word would fail
+        let a = || Type::Variable("a".to_string());
+        let option_of = |ty: Type| Type::Option(Box::new(ty));
+        self.define_builtin("cons", vec![a(), list_of("a")], vec![list_of("a")]);
+        self.define_builtin("head", vec![list_of("a")], vec![a()]);
+        self.define_builtin("head?", vec![list_of("a")], vec![option_of(a())]);
+        self.define_builtin("tail", vec![list_of("a")], vec![list_of("a")]);
+        self.define_builtin("tail?", vec![list_of("a")], vec![option_of(list_of("a"))]);
+        self.define_builtin("nth", vec![list_of("a"), Type::Nat], vec![a()]);
+        self.define_builtin("nth?", vec![list_of("a"), Type::Nat], vec![option_of(a())]);
+        self.define_builtin(
+            "append",
+            vec![list_of("a"), list_of("a")],
+            vec![list_of("a")],
+        );
+        self.define_builtin("reverse", vec![list_of("a")], vec![list_of("a")]);
+        self.define_builtin("sort", vec![list_of("a")], vec![list_of("a")]);
+        self.define_builtin(
+            "zip",
+            vec![list_of("a"), list_of("a")],
+            vec![Type::List(Box::new(list_of("a")))],
+        );
 
         // System words
         self.define_builtin(".", vec![Type::Variable("a".to_string())], vec![]);
         self.define_builtin(".s", vec![], vec![]);
+        self.define_builtin("words", vec![], vec![]);
+        self.define_builtin("see", vec![Type::String], vec![]);
+        self.define_builtin("clear", vec![], vec![]);
+        self.define_builtin("depth", vec![], vec![Type::Nat]);
+        self.define_builtin("help", vec![], vec![]);
         self.define_builtin("print", vec![Type::Variable("a".to_string())], vec![]);
         self.define_builtin("--ordinal", vec![Type::Quote], vec![Type::Ordinal]);
 
+        // Testing
+        self.define_builtin("assert", vec![Type::Bool], vec![]);
+        self.define_builtin(
+            "assert-eq",
+            vec![
+                Type::Variable("a".to_string()),
+                Type::Variable("a".to_string()),
+            ],
+            vec![],
+        );
+        self.define_builtin("assert-stack", vec![Type::Quote], vec![]);
+        self.define_builtin("equiv?", vec![Type::Quote, Type::Quote], vec![Type::Bool]);
+
+        // Ordinal arithmetic
+        self.define_builtin("omega", vec![], vec![Type::Ordinal]);
+        self.define_builtin("ω", vec![], vec![Type::Ordinal]);
+        self.define_builtin("ord", vec![Type::Nat], vec![Type::Ordinal]);
+        for word in ["ord+", "ord*", "ord^"] {
+            self.define_builtin(
+                word,
+                vec![Type::Ordinal, Type::Ordinal],
+                vec![Type::Ordinal],
+            );
+        }
+        self.define_builtin("ord<", vec![Type::Ordinal, Type::Ordinal], vec![Type::Bool]);
+
         // String operations
-        self.define_builtin("print-string", vec![Type::String], vec![]);
+        let string = || Type::String;
+        self.define_builtin("print-string", vec![string()], vec![]);
+        self.define_builtin("concat", vec![string(), string()], vec![string()]);
+        // Type inference also accepts `length` ( List<a> -> Nat )
+        self.define_builtin("length", vec![string()], vec![Type::Nat]);
+        self.define_builtin(
+            "substring",
+            vec![string(), Type::Nat, Type::Nat],
+            vec![string()],
+        );
+        self.define_builtin(
+            "split",
+            vec![string(), string()],
+            vec![Type::List(Box::new(string()))],
+        );
+        self.define_builtin(
+            "join",
+            vec![Type::List(Box::new(string())), string()],
+            vec![string()],
+        );
+        for word in ["to-upper", "to-lower", "trim"] {
+            self.define_builtin(word, vec![string()], vec![string()]);
+        }
+        self.define_builtin("contains?", vec![string(), string()], vec![Type::Bool]);
+        self.define_builtin(
+            "string->nat",
+            vec![string()],
+            vec![Type::Option(Box::new(Type::Nat))],
+        );
+        self.define_builtin("nat->string", vec![Type::Nat], vec![string()]);
+        self.define_builtin(
+            "to-string",
+            vec![Type::Variable("a".to_string())],
+            vec![string()],
+        );
+        self.define_builtin(
+            "format",
+            vec![
+                Type::List(Box::new(Type::Variable("a".to_string()))),
+                string(),
+            ],
+            vec![string()],
+        );
+
+        // Polymorphic type constructors
+        let var = |name: &str| Type::Variable(name.to_string());
+        let option_of = Type::Option(Box::new(var("a")));
+        let result_of = Type::Result(Box::new(var("a")), Box::new(var("e")));
+        self.define_builtin("Some", vec![var("a")], vec![option_of.clone()]);
+        self.define_builtin("None", vec![], vec![option_of]);
+        self.define_builtin("Ok", vec![var("a")], vec![result_of.clone()]);
+        self.define_builtin("Err", vec![var("e")], vec![result_of]);
     }
 
     /// Define a builtin word with its type signature
@@ -177,13 +487,23 @@ impl VirtualMachine {
     /// Push a value onto the stack
     pub fn push(&mut self, value: Value) {
         self.stack.push(value);
+        // Only clone the value when someone is watching
+        if !self.is_observed() {
+            return;
+        }
+        if let Some(value) = self.stack.last().cloned() {
+            self.notify(|observer, context| observer.push(&value, context));
+        }
     }
 
     /// Pop a value from the stack
     pub fn pop(&mut self) -> Result<Value, VmError> {
-        self.stack
+        let value = self
+            .stack
             .pop()
-            .ok_or_else(|| VmError::StackUnderflow("pop".to_string()))
+            .ok_or_else(|| VmError::StackUnderflow("pop".to_string()))?;
+        self.notify(|observer, context| observer.pop(&value, context));
+        Ok(value)
     }
 
     /// Peek at the top value without removing it
@@ -203,22 +523,73 @@ impl VirtualMachine {
 
     /// Define a new word
     pub fn define_word(&mut self, word_def: WordDefinition) {
+        self.declared_signatures.remove(&word_def.name);
+        self.assumed_signatures.remove(&word_def.name);
+        if !word_def.is_axiom {
+            self.axioms.remove(&word_def.name);
+        }
         self.type_signatures
             .insert(word_def.name.clone(), word_def.signature.clone());
         self.dictionary.insert(word_def.name.clone(), word_def);
     }
 
+    /// Define a word whose stack effect is unknown
+    ///
+    /// The word's signature is not used to type check later definitions.
+    pub fn define_untyped_word(&mut self, word_def: WordDefinition) {
+        self.declared_signatures.remove(&word_def.name);
+        self.assumed_signatures.remove(&word_def.name);
+        self.axioms.remove(&word_def.name);
+        self.type_signatures.remove(&word_def.name);
+        self.dictionary.insert(word_def.name.clone(), word_def);
+    }
+
+    /// The signature later definitions are checked against, if the word has one
+    pub fn word_signature(&self, name: &str) -> Option<&TypeSignature> {
+        self.type_signatures.get(name)
+    }
+
     /// Execute a single token
     pub fn execute_token(&mut self, token: &Token) -> Result<(), VmError> {
+        // If we're reading a theorem, consume tokens until ;
+        if self.parsing_theorem {
+            if token == &Token::Word(";".to_string()) {
+                self.finish_theorem()?;
+            } else {
+                self.current_theorem.push(token.clone());
+            }
+            return Ok(());
+        }
+
+        // If we're reading a data declaration, consume tokens until ;
+        if self.parsing_data_definition {
+            if token == &Token::Word(";".to_string()) {
+                self.finish_data_definition()?;
+            } else {
+                self.current_data_definition.push(token.clone());
+            }
+            return Ok(());
+        }
+
+        // If we're reading a type declaration, consume tokens until }
+        if self.parsing_type_definition {
+            if token == &Token::Word("}".to_string()) {
+                self.finish_type_definition()?;
+            } else {
+                self.current_type_definition.push(token.clone());
+            }
+            return Ok(());
+        }
+
         // If we're parsing a type signature, consume tokens until ;
         if self.parsing_type_signature {
             match token {
                 Token::Word(word) if word == ";" => {
                     // End type signature
-                    self.parsing_type_signature = false;
+                    self.finish_type_signature()?;
                 }
                 _ => {
-                    // Ignore tokens in type signature
+                    self.current_signature.push(token.clone());
                 }
             }
             return Ok(());
@@ -239,13 +610,22 @@ impl VirtualMachine {
             return Ok(());
         }
 
-        match token {
+        self.unwinding = false;
+        let result = match token {
             Token::Literal(value) => {
                 self.push(value.clone());
                 Ok(())
             }
             Token::Word(word) => self.execute_word(word),
-            Token::MatchExpression { value, arms } => self.execute_match_expression(value, arms),
+            Token::MatchExpression { value, arms } => {
+                // Matches in definitions are checked when they are defined
+                let checked = if self.call_stack.is_empty() {
+                    self.check_match_arms("match", std::slice::from_ref(token))
+                } else {
+                    Ok(())
+                };
+                checked.and_then(|()| self.execute_match_expression(value, arms))
+            }
             Token::Comment(_) => {
                 // Comments are ignored during execution
                 Ok(())
@@ -254,21 +634,32 @@ impl VirtualMachine {
                 "Cannot execute token: {:?}",
                 token
             ))),
+        };
+        if let Err(error) = &result {
+            self.report_error(error);
         }
+        result
     }
 
     /// Execute a word (builtin or user-defined)
     pub fn execute_word(&mut self, word: &str) -> Result<(), VmError> {
         // Check recursion depth
         if self.call_stack.len() >= self.max_recursion_depth {
-            return Err(VmError::InvalidOperation(format!(
+            let error = VmError::InvalidOperation(format!(
                 "Maximum recursion depth exceeded in word: {}",
                 word
-            )));
+            ));
+            self.report_error(&error);
+            return Err(error);
         }
 
         self.call_stack.push(word.to_string());
+        self.notify(|observer, context| observer.word_enter(word, context));
         let result = self.execute_word_impl(word);
+        if let Err(error) = &result {
+            self.report_error(error);
+        }
+        self.notify(|observer, context| observer.word_exit(word, context));
         self.call_stack.pop();
         result
     }
@@ -285,6 +676,35 @@ impl VirtualMachine {
                 // Start word definition
                 self.start_word_definition()
             }
+            "test:" => {
+                self.start_word_definition()?;
+                self.defining_test = true;
+                Ok(())
+            }
+            "type" => {
+                // Type declaration - read fields until }
+                self.parsing_type_definition = true;
+                self.current_type_definition.clear();
+                Ok(())
+            }
+            "data" => {
+                // Sum type declaration - read constructors until ;
+                self.parsing_data_definition = true;
+                self.current_data_definition.clear();
+                Ok(())
+            }
+            "theorem" | "lemma" => {
+                // Equation between quotations - read it until ;
+                self.parsing_theorem = true;
+                self.current_theorem = vec![Token::Word(word.to_string())];
+                Ok(())
+            }
+            "axiom" => {
+                // Axiom declaration - read the signature until ;
+                self.start_type_signature()?;
+                self.declaring_axiom = true;
+                Ok(())
+            }
 
             // Stack manipulation
             "dup" => self.builtin_dup(),
@@ -314,14 +734,86 @@ impl VirtualMachine {
 
             // Control flow
             "if" => self.builtin_if(),
+            "when" => self.builtin_when(),
+            "unless" => self.builtin_unless(),
+            "times" => self.builtin_times(),
+            "call" => self.builtin_call(),
+            "while" => self.builtin_while(),
+
+            // Bounded iteration
+            "each" => self.builtin_each(),
+            "map" => self.builtin_map(),
+            "filter" => self.builtin_filter(),
+            "fold" => self.builtin_fold(),
+            "range" => self.builtin_range(),
+
+            // Lists
+            "cons" => self.builtin_cons(),
+            "head" => self.builtin_head(),
+            "head?" => self.builtin_head_option(),
+            "tail" => self.builtin_tail(),
+            "tail?" => self.builtin_tail_option(),
+            "nth" => self.builtin_nth(),
+            "nth?" => self.builtin_nth_option(),
+            "append" => self.builtin_append(),
+            "reverse" => self.builtin_reverse(),
+            "sort" => self.builtin_sort(),
+            "zip" => self.builtin_zip(),
 
             // System
             "." => self.builtin_dot(),
             ".s" => self.builtin_dot_s(),
+            "words" => self.builtin_words(),
+            "see" => self.builtin_see(),
+            "clear" => self.builtin_clear(),
+            "depth" => self.builtin_depth(),
+            "help" => self.builtin_help(),
             "print" => self.builtin_print(),
+            "print-string" => self.builtin_print_string(),
             "quit" => self.builtin_quit(),
             "--ordinal" => self.builtin_ordinal(),
 
+            // Strings
+            "concat" => self.builtin_concat(),
+            "length" => self.builtin_length(),
+            "substring" => self.builtin_substring(),
+            "split" => self.builtin_split(),
+            "join" => self.builtin_join(),
+            "to-upper" => self.map_string("to-upper", str::to_uppercase),
+            "to-lower" => self.map_string("to-lower", str::to_lowercase),
+            "trim" => self.map_string("trim", |s| s.trim().to_string()),
+            "contains?" => self.builtin_contains(),
+            "string->nat" => self.builtin_string_to_nat(),
+            "nat->string" => self.builtin_nat_to_string(),
+            "to-string" => self.builtin_to_string(),
+            "format" => self.builtin_format(),
+
+            // Testing
+            "assert" => self.builtin_assert(),
+            "assert-eq" => self.builtin_assert_eq(),
+            "assert-stack" => self.builtin_assert_stack(),
+            "equiv?" => self.builtin_equiv(),
+
+            // Ordinal arithmetic
+            "omega" | "ω" => {
+                self.push(Value::Ordinal(OrdinalValue::Omega));
+                Ok(())
+            }
+            "ord" => {
+                let n = self.pop_nat("ord")?;
+                self.push(Value::Ordinal(OrdinalValue::Finite(n)));
+                Ok(())
+            }
+            "ord+" => self.builtin_ordinal_arithmetic("ord+", OrdinalValue::plus),
+            "ord*" => self.builtin_ordinal_arithmetic("ord*", OrdinalValue::times),
+            "ord^" => self.builtin_ordinal_arithmetic("ord^", OrdinalValue::pow),
+            "ord<" => {
+                let b = self.pop_ordinal("ord<")?;
+                let a = self.pop_ordinal("ord<")?;
+                self.push(Value::Bool(a < b));
+                Ok(())
+            }
+
             // Polymorphic type constructors
             "Some" => self.builtin_some(),
             "None" => self.builtin_none(),
@@ -332,7 +824,9 @@ impl VirtualMachine {
 
             // User-defined words
             _ => {
-                if let Some(word_def) = self.dictionary.get(word).cloned() {
+                if let Some(type_word) = self.type_words.get(word).cloned() {
+                    self.execute_type_word(word, type_word)
+                } else if let Some(word_def) = self.dictionary.get(word).cloned() {
                     if word_def.is_axiom {
                         return Err(VmError::InvalidOperation(format!(
                             "Cannot execute axiom: {}",
@@ -369,239 +863,840 @@ impl VirtualMachine {
         }
 
         self.parsing_type_signature = true;
+        self.current_signature.clear();
         Ok(())
     }
 
-    fn finish_word_definition(&mut self) -> Result<(), VmError> {
-        if !self.defining_word {
-            return Err(VmError::InvalidOperation(
-                "Not currently defining a word".to_string(),
-            ));
+    /// Record a `:: name ( inputs -> outputs ) ;` declaration for the next definition of `name`
+    fn finish_type_signature(&mut self) -> Result<(), VmError> {
+        self.parsing_type_signature = false;
+        let tokens = std::mem::take(&mut self.current_signature);
+        if std::mem::take(&mut self.declaring_axiom) {
+            return self.finish_axiom(&tokens);
         }
-
-        let word_name = self.current_word_name.take().ok_or_else(|| {
-            VmError::InvalidOperation("No word name specified for definition".to_string())
-        })?;
-
-        let definition = WordDefinition {
-            name: word_name.clone(),
-            body: self.current_definition.clone(),
-            signature: TypeSignature {
-                inputs: vec![],
-                outputs: vec![],
-            },
-            is_axiom: false,
-            ordinal_cost: OrdinalValue::Finite(1),
-        };
-
-        self.dictionary.insert(word_name, definition);
-        self.defining_word = false;
-        self.current_definition.clear();
-
+        if matches!(tokens.as_slice(), [Token::Word(_)]) {
+            // `:: name ;` declares the word without constraining it
+            return Ok(());
+        }
+        let (name, signature) = parse_signature_declaration(&tokens)?;
+        self.declared_signatures.insert(name, signature);
         Ok(())
     }
 
-    /// Execute a sequence of tokens
-    pub fn execute_tokens(&mut self, tokens: &[Token]) -> Result<(), VmError> {
-        for token in tokens {
-            // Special handling for word names in definition mode
-            if self.defining_word && self.current_word_name.is_none() {
-                if let Token::Word(name) = token {
-                    self.current_word_name = Some(name.clone());
-                    continue;
-                }
+    /// Declare the axiom read from an `axiom name ( inputs -> outputs ) ;` declaration
+    fn finish_axiom(&mut self, tokens: &[Token]) -> Result<(), VmError> {
+        let (name, signature) = match tokens {
+            // `axiom name ;` assumes the signature declared with `::`, if any
+            [Token::Word(name)] => {
+                let signature = self.declared_signatures.get(name).cloned();
+                let signature = signature.unwrap_or(TypeSignature {
+                    inputs: vec![],
+                    outputs: vec![],
+                });
+                (name.clone(), signature)
             }
-            self.execute_token(token)?;
-        }
+            _ => parse_signature_declaration(tokens)?,
+        };
+        self.define_axiom(name, signature);
         Ok(())
     }
 
-    // Builtin word implementations
-
-    fn builtin_dup(&mut self) -> Result<(), VmError> {
-        let value = self
-            .peek()
-            .ok_or_else(|| VmError::StackUnderflow("dup".to_string()))?;
-        let value = value.clone();
-        self.push(value);
-        Ok(())
+    /// Assume a word with the given signature and no body
+    ///
+    /// Later definitions are type checked against the signature, but the
+    /// axiom itself cannot be executed.
+    pub fn define_axiom(&mut self, name: String, signature: TypeSignature) {
+        self.axioms.declare(name.clone(), signature.clone());
+        self.define_word(WordDefinition {
+            name,
+            signature,
+            body: vec![],
+            is_axiom: true,
+            ordinal_cost: OrdinalValue::Zero,
+        });
     }
 
-    fn builtin_dup2(&mut self) -> Result<(), VmError> {
-        if self.stack.len() < 2 {
-            return Err(VmError::StackUnderflow("dup2".to_string()));
-        }
-        let b = self.pop()?;
-        let a = self.pop()?;
-        self.push(a.clone());
-        self.push(b.clone());
-        self.push(a);
-        self.push(b);
-        Ok(())
+    /// Axioms declared with `axiom`
+    pub fn axioms(&self) -> &AxiomRegistry {
+        &self.axioms
     }
 
-    fn builtin_drop(&mut self) -> Result<(), VmError> {
-        self.pop()?;
-        Ok(())
+    /// The axioms each user word relies on, directly or through the words it calls
+    pub fn axiom_dependencies(&self) -> BTreeMap<String, BTreeSet<String>> {
+        self.axioms.dependencies(&self.dictionary)
     }
 
-    fn builtin_swap(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        self.push(b);
-        self.push(a);
-        Ok(())
+    /// Declare the type read from a `type Name { field: Type ... }` declaration
+    fn finish_type_definition(&mut self) -> Result<(), VmError> {
+        self.parsing_type_definition = false;
+        let tokens = std::mem::take(&mut self.current_type_definition);
+        let definition = parse_type_definition(&tokens)?;
+        self.define_type(definition)
     }
 
-    fn builtin_over(&mut self) -> Result<(), VmError> {
-        if self.stack.len() < 2 {
-            return Err(VmError::StackUnderflow("over".to_string()));
+    /// Declare a composite type and generate its constructor, field
+    /// accessors and predicate; see [`TypeDefinition::word_signatures`]
+    ///
+    /// The type and its words may not take a name that is already in use,
+    /// except by an earlier declaration of the same type.
+    pub fn define_type(&mut self, definition: TypeDefinition) -> Result<(), VmError> {
+        let words = definition.word_signatures();
+        let declared_otherwise = self.data_definitions.contains_key(&definition.name);
+        self.check_type_names(
+            &definition.name,
+            declared_otherwise,
+            words.iter().map(|(word, _)| word.as_str()),
+        )?;
+
+        // Redeclaring a type replaces the words of its old fields
+        if let Some(old) = self.type_definitions.remove(&definition.name) {
+            for (word, _) in old.word_signatures() {
+                self.type_words.remove(&word);
+                self.type_signatures.remove(&word);
+            }
         }
-        let second = self.stack[self.stack.len() - 2].clone();
-        self.push(second);
+
+        let name = &definition.name;
+        for (word, signature) in words {
+            let type_word = if word == *name {
+                TypeWord::Constructor(name.clone())
+            } else if word == format!("{}?", name) {
+                TypeWord::Predicate(name.clone())
+            } else {
+                let accessor = &word[name.len() + 1..];
+                match accessor.strip_suffix('!') {
+                    Some(field) => TypeWord::Setter {
+                        type_name: name.clone(),
+                        field: field.to_string(),
+                    },
+                    None => TypeWord::Getter {
+                        type_name: name.clone(),
+                        field: accessor.to_string(),
+                    },
+                }
+            };
+            self.type_words.insert(word.clone(), type_word);
+            self.type_signatures.insert(word, signature);
+        }
+        self.type_definitions.insert(name.clone(), definition);
         Ok(())
     }
 
-    fn builtin_rot(&mut self) -> Result<(), VmError> {
-        if self.stack.len() < 3 {
-            return Err(VmError::StackUnderflow("rot".to_string()));
+    fn finish_data_definition(&mut self) -> Result<(), VmError> {
+        self.parsing_data_definition = false;
+        let tokens = std::mem::take(&mut self.current_data_definition);
+        let definition = parse_data_definition(&tokens)?;
+        self.define_data(definition)
+    }
+
+    /// Declare a sum type and generate its constructors; see
+    /// [`DataDefinition::constructor_signatures`]
+    ///
+    /// As with [`define_type`](Self::define_type), the names must be free.
+    pub fn define_data(&mut self, definition: DataDefinition) -> Result<(), VmError> {
+        let declared_otherwise = self.type_definitions.contains_key(&definition.name);
+        let constructors = definition.constructors.iter();
+        self.check_type_names(
+            &definition.name,
+            declared_otherwise,
+            constructors.map(|c| c.name.as_str()),
+        )?;
+
+        if let Some(old) = self.data_definitions.remove(&definition.name) {
+            for constructor in &old.constructors {
+                self.type_words.remove(&constructor.name);
+                self.type_signatures.remove(&constructor.name);
+            }
         }
-        let c = self.pop()?;
-        let b = self.pop()?;
-        let a = self.pop()?;
-        self.push(b);
-        self.push(c);
-        self.push(a);
+
+        for (constructor, signature) in definition.constructor_signatures() {
+            let type_word = TypeWord::Variant {
+                type_name: definition.name.clone(),
+                constructor: constructor.clone(),
+                arity: signature.inputs.len(),
+            };
+            self.type_words.insert(constructor.clone(), type_word);
+            self.type_signatures.insert(constructor, signature);
+        }
+        self.data_definitions
+            .insert(definition.name.clone(), definition);
         Ok(())
     }
 
-    fn builtin_add(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                self.push(Value::Nat(x + y));
-                Ok(())
+    /// Reject a type name, or a word generated for it, that a builtin, a
+    /// user word or another type already uses
+    ///
+    /// `declared_otherwise` says whether the name already belongs to a type
+    /// of the other kind, `type` or `data`.
+    fn check_type_names<'a>(
+        &self,
+        type_name: &str,
+        declared_otherwise: bool,
+        words: impl Iterator<Item = &'a str>,
+    ) -> Result<(), VmError> {
+        let taken = |what: &str, name: &str| {
+            VmError::InvalidOperation(format!(
+                "Cannot declare type '{}': {} '{}' is already defined",
+                type_name, what, name
+            ))
+        };
+        if BUILTIN_TYPES.contains(&type_name) || declared_otherwise {
+            return Err(taken("type", type_name));
+        }
+        for word in words {
+            let own_word = self
+                .type_words
+                .get(word)
+                .is_some_and(|type_word| type_word.type_name() == type_name);
+            if own_word {
+                continue;
+            }
+            if self.type_words.contains_key(word)
+                || self.type_signatures.contains_key(word)
+                || self.dictionary.contains_key(word)
+            {
+                return Err(taken("word", word));
             }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
-            }),
         }
+        Ok(())
     }
 
-    fn builtin_sub(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                if x >= y {
-                    self.push(Value::Nat(x - y));
-                    Ok(())
-                } else {
-                    Err(VmError::InvalidOperation(
-                        "Cannot subtract to negative result".to_string(),
-                    ))
-                }
+    fn finish_theorem(&mut self) -> Result<(), VmError> {
+        self.parsing_theorem = false;
+        let tokens = std::mem::take(&mut self.current_theorem);
+        let theorem = parse_theorem(&tokens)?;
+        self.add_theorem(theorem)
+    }
+
+    /// Prove a theorem and print its proof, or test a lemma for
+    /// counterexamples; either is then used to rewrite later theorems
+    ///
+    /// A theorem whose sides do not rewrite to the same normal form fails,
+    /// with a counterexample if running both sides on random stacks finds
+    /// one.
+    pub fn add_theorem(&mut self, theorem: Theorem) -> Result<(), VmError> {
+        // Redeclaring a theorem replaces it, so it cannot prove itself
+        self.proofs
+            .retain(|proof| proof.theorem.name != theorem.name);
+        self.lemmas.retain(|lemma| lemma.name != theorem.name);
+
+        let failed = |reason: String| VmError::TheoremFailed {
+            theorem: theorem.name.clone(),
+            reason,
+        };
+        if theorem.is_lemma {
+            if let Some(counterexample) = self.find_counterexample(&theorem) {
+                return Err(failed(format!("counterexample {}", counterexample)));
             }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
-            }),
+            self.lemmas.push(theorem);
+            return Ok(());
         }
-    }
 
-    fn builtin_mul(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                self.push(Value::Nat(x * y));
+        match self.prover().prove(&theorem) {
+            Ok(proof) => {
+                println!("{}", proof);
+                self.proofs.push(proof);
                 Ok(())
             }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
-            }),
+            Err((lhs, rhs)) => Err(failed(match self.find_counterexample(&theorem) {
+                Some(counterexample) => format!("counterexample {}", counterexample),
+                None => format!(
+                    "the sides reduce to {} and {}, and no counterexample was found on {} random stacks",
+                    theorem::format_tokens(&lhs),
+                    theorem::format_tokens(&rhs),
+                    theorem::TRIALS
+                ),
+            })),
         }
     }
 
-    fn builtin_div(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
+    /// Theorems proved so far, in declaration order
+    pub fn proofs(&self) -> &[Proof] {
+        &self.proofs
+    }
 
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                if y == 0 {
-                    Err(VmError::RuntimeError("Division by zero".to_string()))
-                } else {
-                    self.push(Value::Nat(x / y));
-                    Ok(())
-                }
-            }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
-            }),
+    /// Equations assumed with `lemma`, in declaration order
+    pub fn lemmas(&self) -> &[Theorem] {
+        &self.lemmas
+    }
+
+    /// A prover that rewrites with the lemmas, the proved theorems and the
+    /// definitions of user words
+    pub fn prover(&self) -> Prover {
+        let mut prover = Prover::new();
+        for lemma in self
+            .lemmas
+            .iter()
+            .chain(self.proofs.iter().map(|p| &p.theorem))
+        {
+            prover.add_lemma(lemma);
+        }
+        for word_def in self.dictionary.values().filter(|def| !def.is_axiom) {
+            prover.add_definition(word_def.name.clone(), word_def.body.clone());
         }
+        prover
     }
 
-    fn builtin_mod(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
+    /// Run both sides of a theorem on random stacks, looking for one where
+    /// they leave different results
+    ///
+    /// Stacks that either side fails on are skipped, since they are outside
+    /// the domain of the equation.
+    fn find_counterexample(&mut self, theorem: &Theorem) -> Option<Counterexample> {
+        theorem::random_stacks(theorem)
+            .find_map(|stack| self.disagreement(stack, &theorem.lhs, &theorem.rhs))
+    }
 
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                if y == 0 {
-                    Err(VmError::RuntimeError("Modulo by zero".to_string()))
-                } else {
-                    self.push(Value::Nat(x % y));
-                    Ok(())
-                }
-            }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
-            }),
-        }
+    /// The results of two quotations that both run on `stack` but leave
+    /// different values
+    fn disagreement(
+        &mut self,
+        stack: Vec<Value>,
+        lhs: &[Token],
+        rhs: &[Token],
+    ) -> Option<Counterexample> {
+        let lhs_result = self.run_on(stack.clone(), lhs).ok()?;
+        let rhs_result = self.run_on(stack.clone(), rhs).ok()?;
+        (lhs_result != rhs_result).then_some(Counterexample {
+            stack,
+            lhs_result,
+            rhs_result,
+        })
     }
 
-    fn builtin_eq(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        let result = a == b;
-        self.push(Value::Bool(result));
-        Ok(())
+    /// Run tokens on the given stack, returning the stack they leave and
+    /// keeping the current one
+    fn run_on(&mut self, stack: Vec<Value>, tokens: &[Token]) -> Result<Vec<Value>, VmError> {
+        let saved = std::mem::replace(&mut self.stack, stack);
+        let result = self.execute_tokens(tokens);
+        let after = std::mem::replace(&mut self.stack, saved);
+        result.map(|()| after)
     }
 
-    fn builtin_lt(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                self.push(Value::Bool(x < y));
-                Ok(())
+    /// Run a word on random stacks built from its signature's inputs,
+    /// returning the smallest stack found on which it fails or leaves values
+    /// that do not match its outputs
+    pub fn check_signature(&mut self, name: &str) -> Result<Option<CheckFailure>, VmError> {
+        let signature = self.type_signatures.get(name).cloned().ok_or_else(|| {
+            match self.dictionary.contains_key(name) {
+                true => {
+                    VmError::InvalidOperation(format!("'{}' has no type signature to check", name))
+                }
+                false => VmError::UnknownWord(name.to_string()),
             }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
+        })?;
+
+        if self.axioms.contains(name) {
+            return Err(VmError::InvalidOperation(format!(
+                "'{}' is an axiom and cannot be run",
+                name
+            )));
+        }
+
+        let mut generator = Generator::new(name, &self.type_definitions, &self.data_definitions);
+        let trials = (0..property::TRIALS)
+            .map(|_| generator.inputs(&signature.inputs))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|ty| {
+                VmError::InvalidOperation(format!(
+                    "Cannot generate random values of type {} for '{}'",
+                    ty, name
+                ))
+            })?;
+
+        let word = [Token::Word(name.to_string())];
+        let problem = |vm: &mut Self, inputs: &[Value], bindings: &HashMap<String, Type>| {
+            let outputs = match vm.run_on(inputs.to_vec(), &word) {
+                Ok(outputs) => outputs,
+                Err(error) => return Some(format!("it fails: {}", error)),
+            };
+            if outputs.len() != signature.outputs.len() {
+                return Some(format!(
+                    "it leaves {}, but {} declares {} values",
+                    format_values(&outputs),
+                    signature,
+                    signature.outputs.len()
+                ));
+            }
+            outputs
+                .iter()
+                .zip(&signature.outputs)
+                .find(|(value, ty)| !property::conforms(value, ty, bindings))
+                .map(|(value, ty)| format!("it leaves {} where {} is declared", value, ty))
+        };
+
+        for (inputs, bindings) in trials {
+            if problem(self, &inputs, &bindings).is_some() {
+                let (inputs, shrinks) =
+                    property::shrink(inputs, |inputs| problem(self, inputs, &bindings).is_some());
+                let problem = problem(self, &inputs, &bindings).unwrap_or_default();
+                return Ok(Some(CheckFailure {
+                    inputs,
+                    problem,
+                    shrinks,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The inputs the last failed `equiv?` disagreed on, clearing them
+    pub fn take_counterexample(&mut self) -> Option<Counterexample> {
+        self.last_counterexample.take()
+    }
+
+    /// Run tokens on an empty stack, keeping the current one
+    pub fn run_isolated(&mut self, tokens: &[Token]) -> Result<(), VmError> {
+        self.run_on(Vec::new(), tokens).map(|_| ())
+    }
+
+    /// A type declared with `data`
+    pub fn get_data_definition(&self, name: &str) -> Option<&DataDefinition> {
+        self.data_definitions.get(name)
+    }
+
+    /// Reject a `match` in `tokens` that leaves out constructors of a `data` type
+    fn check_match_arms(&self, word_name: &str, tokens: &[Token]) -> Result<(), VmError> {
+        match self.signature_inferer().missing_match_arms(tokens) {
+            Some(missing) => Err(match_arms_error(word_name, missing)),
+            None => Ok(()),
+        }
+    }
+
+    /// A type declared with `type`
+    pub fn get_type_definition(&self, name: &str) -> Option<&TypeDefinition> {
+        self.type_definitions.get(name)
+    }
+
+    /// Whether `word` was generated by a `type` declaration
+    pub fn is_type_word(&self, word: &str) -> bool {
+        self.type_words.contains_key(word)
+    }
+
+    fn execute_type_word(&mut self, word: &str, type_word: TypeWord) -> Result<(), VmError> {
+        match type_word {
+            TypeWord::Constructor(type_name) => {
+                let field_types = self.type_definitions[&type_name].fields.clone();
+                let mut fields = HashMap::new();
+                for (field, ty) in field_types.into_iter().rev() {
+                    let value = self.pop()?;
+                    check_field(word, &value, &ty)?;
+                    fields.insert(field, value);
+                }
+                self.push(Value::Composite { type_name, fields });
+            }
+            TypeWord::Predicate(type_name) => {
+                let value = self.pop()?;
+                let is_type = matches!(&value, Value::Composite { type_name: name, .. } if *name == type_name);
+                self.push(Value::Bool(is_type));
+            }
+            TypeWord::Getter { type_name, field } => {
+                let mut fields = self.pop_composite(word, &type_name)?;
+                let value = fields.remove(&field).ok_or_else(|| {
+                    VmError::InvalidOperation(format!("{} has no field '{}'", type_name, field))
+                })?;
+                self.push(value);
+            }
+            TypeWord::Setter { type_name, field } => {
+                let value = self.pop()?;
+                let mut fields = self.pop_composite(word, &type_name)?;
+                if let Some((_, ty)) = self.type_definitions[&type_name]
+                    .fields
+                    .iter()
+                    .find(|(name, _)| *name == field)
+                {
+                    check_field(word, &value, ty)?;
+                }
+                fields.insert(field, value);
+                self.push(Value::Composite { type_name, fields });
+            }
+            TypeWord::Variant {
+                type_name,
+                constructor,
+                arity,
+            } => {
+                if self.stack.len() < arity {
+                    return Err(VmError::StackUnderflow(constructor));
+                }
+                let args = self.stack.split_off(self.stack.len() - arity);
+                let field_types = self.data_definitions[&type_name]
+                    .constructors
+                    .iter()
+                    .find(|c| c.name == constructor)
+                    .map(|c| c.fields.as_slice())
+                    .unwrap_or_default();
+                for (value, ty) in args.iter().zip(field_types) {
+                    check_field(word, value, ty)?;
+                }
+                self.push(Value::Variant {
+                    type_name,
+                    constructor,
+                    args,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Pop a value of the composite type `type_name`, returning its fields
+    fn pop_composite(
+        &mut self,
+        op: &str,
+        type_name: &str,
+    ) -> Result<HashMap<String, Value>, VmError> {
+        match self.pop()? {
+            Value::Composite {
+                type_name: name,
+                fields,
+            } if name == type_name => Ok(fields),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("{} for '{}'", type_name, op),
+                found: format!("{}", other),
             }),
         }
     }
 
-    fn builtin_gt(&mut self) -> Result<(), VmError> {
-        let b = self.pop()?;
-        let a = self.pop()?;
-        match (a, b) {
-            (Value::Nat(x), Value::Nat(y)) => {
-                self.push(Value::Bool(x > y));
-                Ok(())
+    fn finish_word_definition(&mut self) -> Result<(), VmError> {
+        if !self.defining_word {
+            return Err(VmError::InvalidOperation(
+                "Not currently defining a word".to_string(),
+            ));
+        }
+
+        let word_name = self.current_word_name.take().ok_or_else(|| {
+            VmError::InvalidOperation("No word name specified for definition".to_string())
+        })?;
+
+        let body = std::mem::take(&mut self.current_definition);
+        self.defining_word = false;
+        if let Err(error) = self.check_match_arms(&word_name, &body) {
+            self.defining_test = false;
+            return Err(error);
+        }
+
+        if std::mem::take(&mut self.defining_test) {
+            self.define_test(TestCase {
+                name: word_name,
+                body,
+            });
+            return Ok(());
+        }
+
+        // A declared signature must be satisfied by the body; otherwise keep
+        // whatever can be inferred so later declarations can check against it
+        let mut assumed = None;
+        let signature = match self.declared_signatures.remove(&word_name) {
+            Some(declared) => {
+                if let Verification::Assumed(reason) =
+                    self.check_declared_signature(&word_name, &declared, &body)?
+                {
+                    assumed = Some(reason);
+                }
+                Some(declared)
             }
-            _ => Err(VmError::TypeMismatch {
-                expected: "Nat Nat".to_string(),
-                found: "other types".to_string(),
+            None => self.signature_inferer().infer_word_type(&body).ok(),
+        };
+
+        let ordinal_cost = self.ordinal_cost(&body);
+        let definition = WordDefinition {
+            name: word_name.clone(),
+            body,
+            signature: signature.clone().unwrap_or(TypeSignature {
+                inputs: vec![],
+                outputs: vec![],
             }),
+            is_axiom: false,
+            ordinal_cost,
+        };
+
+        if signature.is_some() {
+            self.define_word(definition);
+        } else {
+            self.define_untyped_word(definition);
+        }
+        if let Some(reason) = assumed {
+            self.assume_signature(word_name, reason);
+        }
+
+        Ok(())
+    }
+
+    /// Record that the declared signature of a defined word was assumed
+    /// rather than checked
+    pub fn assume_signature(&mut self, name: String, reason: String) {
+        self.assumed_signatures.insert(name, reason);
+    }
+
+    /// Words whose declared signature could not be checked against their
+    /// body, with the reason, in name order
+    pub fn assumed_signatures(&self) -> &BTreeMap<String, String> {
+        &self.assumed_signatures
+    }
+
+    /// Record a test, replacing any earlier test of the same name
+    pub fn define_test(&mut self, test: TestCase) {
+        match self
+            .tests
+            .iter_mut()
+            .find(|existing| existing.name == test.name)
+        {
+            Some(existing) => *existing = test,
+            None => self.tests.push(test),
+        }
+    }
+
+    /// Tests defined with `test:`, in definition order
+    pub fn tests(&self) -> &[TestCase] {
+        &self.tests
+    }
+
+    /// A type inferer that knows every builtin, typed word and pending declaration
+    pub fn signature_inferer(&self) -> TypeInferer {
+        let mut inferer = TypeInferer::new();
+        for (name, signature) in self.type_signatures.iter().chain(&self.declared_signatures) {
+            inferer.add_word_signature(name.clone(), signature.clone());
+        }
+        for definition in self.data_definitions.values() {
+            inferer.add_data_type(definition.clone());
         }
+        inferer
+    }
+
+    /// The signature declared with `::` for a word that has not been defined yet
+    pub fn declared_signature(&self, name: &str) -> Option<&TypeSignature> {
+        self.declared_signatures.get(name)
+    }
+
+    /// All signatures declared with `::` whose definitions have not been seen yet
+    pub fn declared_signatures(&self) -> &HashMap<String, TypeSignature> {
+        &self.declared_signatures
+    }
+
+    /// Record a `::` signature for the next definition of `name`
+    pub fn declare_signature(&mut self, name: String, signature: TypeSignature) {
+        self.declared_signatures.insert(name, signature);
+    }
+
+    /// Check a definition body against its declared signature
+    fn check_declared_signature(
+        &self,
+        word_name: &str,
+        declared: &TypeSignature,
+        body: &[Token],
+    ) -> Result<Verification, VmError> {
+        self.signature_inferer()
+            .check_definition(word_name, declared, body)
+            .map_err(|e| definition_type_error(word_name, declared, e))
+    }
+
+    /// Execute a sequence of tokens
+    pub fn execute_tokens(&mut self, tokens: &[Token]) -> Result<(), VmError> {
+        for token in tokens {
+            // Special handling for word names in definition mode
+            if self.defining_word && self.current_word_name.is_none() {
+                if let Token::Word(name) = token {
+                    self.current_word_name = Some(name.clone());
+                    continue;
+                }
+            }
+            if !self.defining_word
+                && !self.parsing_type_signature
+                && !self.parsing_type_definition
+                && !self.parsing_data_definition
+                && !self.parsing_theorem
+            {
+                self.enter_token(token)?;
+            }
+            self.execute_token(token)?;
+        }
+        Ok(())
+    }
+
+    /// Show a token to the observers and the debugger before it runs
+    fn enter_token(&mut self, token: &Token) -> Result<(), VmError> {
+        let context = ExecutionContext {
+            call_stack: &self.call_stack,
+            stack: &self.stack,
+        };
+        let result = self
+            .observers
+            .iter_mut()
+            .map(|(_, observer)| observer.as_mut())
+            .chain(self.debugger.as_mut().map(|d| d as &mut dyn VmObserver))
+            .try_for_each(|observer| observer.token_enter(token, &context));
+        if let Err(error) = &result {
+            self.report_error(error);
+        }
+        result
+    }
+
+    /// Whether any observer or the debugger is attached
+    fn is_observed(&self) -> bool {
+        !self.observers.is_empty() || self.debugger.is_some()
+    }
+
+    /// Notify every observer, then the debugger, of an event
+    fn notify(&mut self, mut event: impl FnMut(&mut dyn VmObserver, &ExecutionContext<'_>)) {
+        if !self.is_observed() {
+            return;
+        }
+        let context = ExecutionContext {
+            call_stack: &self.call_stack,
+            stack: &self.stack,
+        };
+        for (_, observer) in &mut self.observers {
+            event(observer.as_mut(), &context);
+        }
+        if let Some(debugger) = self.debugger.as_mut() {
+            event(debugger, &context);
+        }
+    }
+
+    /// Report an error to the observers where it is raised, but not again
+    /// as it propagates out of the enclosing words
+    fn report_error(&mut self, error: &VmError) {
+        if !self.unwinding {
+            self.unwinding = true;
+            self.notify(|observer, context| observer.error(error, context));
+        }
+    }
+
+    /// Register an observer to be notified of execution events
+    pub fn add_observer(&mut self, observer: Box<dyn VmObserver>) -> ObserverId {
+        let id = ObserverId(self.next_observer_id);
+        self.next_observer_id += 1;
+        self.observers.push((id, observer));
+        id
+    }
+
+    /// Unregister an observer, returning it
+    pub fn remove_observer(&mut self, id: ObserverId) -> Option<Box<dyn VmObserver>> {
+        let index = self.observers.iter().position(|(other, _)| *other == id)?;
+        Some(self.observers.remove(index).1)
+    }
+
+    /// Attach a step debugger, or detach it with `None`
+    ///
+    /// Returns the previously attached debugger.
+    pub fn set_debugger(&mut self, debugger: Option<Debugger>) -> Option<Debugger> {
+        std::mem::replace(&mut self.debugger, debugger)
+    }
+
+    /// The words currently executing, outermost first
+    pub fn call_stack(&self) -> &[String] {
+        &self.call_stack
+    }
+
+    // Builtin word implementations
+
+    fn builtin_dup(&mut self) -> Result<(), VmError> {
+        let value = self
+            .peek()
+            .ok_or_else(|| VmError::StackUnderflow("dup".to_string()))?;
+        let value = value.clone();
+        self.push(value);
+        Ok(())
+    }
+
+    fn builtin_dup2(&mut self) -> Result<(), VmError> {
+        if self.stack.len() < 2 {
+            return Err(VmError::StackUnderflow("dup2".to_string()));
+        }
+        let b = self.pop()?;
+        let a = self.pop()?;
+        self.push(a.clone());
+        self.push(b.clone());
+        self.push(a);
+        self.push(b);
+        Ok(())
+    }
+
+    fn builtin_drop(&mut self) -> Result<(), VmError> {
+        self.pop()?;
+        Ok(())
+    }
+
+    fn builtin_swap(&mut self) -> Result<(), VmError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        self.push(b);
+        self.push(a);
+        Ok(())
+    }
+
+    fn builtin_over(&mut self) -> Result<(), VmError> {
+        if self.stack.len() < 2 {
+            return Err(VmError::StackUnderflow("over".to_string()));
+        }
+        let second = self.stack[self.stack.len() - 2].clone();
+        self.push(second);
+        Ok(())
+    }
+
+    fn builtin_rot(&mut self) -> Result<(), VmError> {
+        if self.stack.len() < 3 {
+            return Err(VmError::StackUnderflow("rot".to_string()));
+        }
+        let c = self.pop()?;
+        let b = self.pop()?;
+        let a = self.pop()?;
+        self.push(b);
+        self.push(c);
+        self.push(a);
+        Ok(())
+    }
+
+    fn builtin_add(&mut self) -> Result<(), VmError> {
+        self.builtin_arithmetic(NatOp::Add)
+    }
+
+    fn builtin_sub(&mut self) -> Result<(), VmError> {
+        self.builtin_arithmetic(NatOp::Sub)
+    }
+
+    fn builtin_mul(&mut self) -> Result<(), VmError> {
+        self.builtin_arithmetic(NatOp::Mul)
+    }
+
+    fn builtin_div(&mut self) -> Result<(), VmError> {
+        self.builtin_arithmetic(NatOp::Div)
+    }
+
+    fn builtin_mod(&mut self) -> Result<(), VmError> {
+        self.builtin_arithmetic(NatOp::Mod)
+    }
+
+    /// Pop two naturals and push `a op b`, checking for overflow
+    fn builtin_arithmetic(&mut self, op: NatOp) -> Result<(), VmError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let result = arithmetic::apply(op, a, b)?;
+        self.push(result);
+        Ok(())
+    }
+
+    fn builtin_eq(&mut self) -> Result<(), VmError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let result = a == b;
+        self.push(Value::Bool(result));
+        Ok(())
+    }
+
+    fn builtin_lt(&mut self) -> Result<(), VmError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let ordering = arithmetic::compare(&a, &b)?;
+        self.push(Value::Bool(ordering == std::cmp::Ordering::Less));
+        Ok(())
+    }
+
+    fn builtin_gt(&mut self) -> Result<(), VmError> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let ordering = arithmetic::compare(&a, &b)?;
+        self.push(Value::Bool(ordering == std::cmp::Ordering::Greater));
+        Ok(())
     }
 
     fn builtin_if(&mut self) -> Result<(), VmError> {
@@ -637,6 +1732,322 @@ impl VirtualMachine {
         }
     }
 
+    /// Pop a quote from the stack, reporting `op` on a type mismatch
+    fn pop_quote(&mut self, op: &str) -> Result<Vec<Token>, VmError> {
+        match self.pop()? {
+            Value::Quote(tokens) => Ok(tokens),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("Quote for '{}'", op),
+                found: format!("{}", other),
+            }),
+        }
+    }
+
+    /// Pop a string from the stack, reporting `op` on a type mismatch
+    fn pop_string(&mut self, op: &str) -> Result<String, VmError> {
+        match self.pop()? {
+            Value::String(s) => Ok(s),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("String for '{}'", op),
+                found: format!("{}", other),
+            }),
+        }
+    }
+
+    /// Pop a boolean from the stack, reporting `op` on a type mismatch
+    fn pop_bool(&mut self, op: &str) -> Result<bool, VmError> {
+        match self.pop()? {
+            Value::Bool(b) => Ok(b),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("Bool for '{}'", op),
+                found: format!("{}", other),
+            }),
+        }
+    }
+
+    fn builtin_when(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("when")?;
+        if self.pop_bool("when")? {
+            self.execute_tokens(&quote)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn builtin_unless(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("unless")?;
+        if self.pop_bool("unless")? {
+            Ok(())
+        } else {
+            self.execute_tokens(&quote)
+        }
+    }
+
+    fn builtin_times(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("times")?;
+        let count = match self.pop()? {
+            Value::Nat(n) => n,
+            other => {
+                return Err(VmError::TypeMismatch {
+                    expected: "Nat for 'times'".to_string(),
+                    found: format!("{}", other),
+                })
+            }
+        };
+
+        for _ in 0..count {
+            self.execute_tokens(&quote)?;
+        }
+        Ok(())
+    }
+
+    fn builtin_call(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("call")?;
+        self.execute_tokens(&quote)
+    }
+
+    /// Pop a natural number from the stack, reporting `op` on a type mismatch
+    fn pop_nat(&mut self, op: &str) -> Result<u64, VmError> {
+        match self.pop()? {
+            Value::Nat(n) => Ok(n),
+            #[cfg(feature = "bigint")]
+            Value::BigNat(n) => Err(VmError::ArithmeticOverflow {
+                operation: op.to_string(),
+                operands: vec![n.to_string()],
+            }),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("Nat for '{}'", op),
+                found: format!("{}", other),
+            }),
+        }
+    }
+
+    /// Pop a list from the stack, reporting `op` on a type mismatch
+    fn pop_list(&mut self, op: &str) -> Result<Vec<Value>, VmError> {
+        match self.pop()? {
+            Value::List(values) => Ok(values),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("List for '{}'", op),
+                found: format!("{}", other),
+            }),
+        }
+    }
+
+    /// Bounded loop: `fuel [ cond ] [ body ] while`
+    ///
+    /// The condition is re-evaluated before every iteration. The fuel is the
+    /// termination certificate: if the condition still holds once `fuel`
+    /// iterations have run, the loop fails instead of running forever.
+    fn builtin_while(&mut self) -> Result<(), VmError> {
+        let body = self.pop_quote("while")?;
+        let condition = self.pop_quote("while")?;
+        let fuel = self.pop_nat("while")?;
+
+        let mut remaining = fuel;
+        loop {
+            self.execute_tokens(&condition)?;
+            if !self.pop_bool("while")? {
+                return Ok(());
+            }
+            if remaining == 0 {
+                return Err(VmError::RuntimeError(format!(
+                    "while exhausted its fuel of {} iterations",
+                    fuel
+                )));
+            }
+            remaining -= 1;
+            self.execute_tokens(&body)?;
+        }
+    }
+
+    fn builtin_each(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("each")?;
+        let values = self.pop_list("each")?;
+
+        for value in values {
+            self.push(value);
+            self.execute_tokens(&quote)?;
+        }
+        Ok(())
+    }
+
+    fn builtin_map(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("map")?;
+        let values = self.pop_list("map")?;
+
+        let mut mapped = Vec::with_capacity(values.len());
+        for value in values {
+            self.push(value);
+            self.execute_tokens(&quote)?;
+            mapped.push(
+                self.pop()
+                    .map_err(|_| VmError::StackUnderflow("map".to_string()))?,
+            );
+        }
+        self.push(Value::List(mapped));
+        Ok(())
+    }
+
+    fn builtin_filter(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("filter")?;
+        let values = self.pop_list("filter")?;
+
+        let mut kept = Vec::new();
+        for value in values {
+            self.push(value.clone());
+            self.execute_tokens(&quote)?;
+            if self.pop_bool("filter")? {
+                kept.push(value);
+            }
+        }
+        self.push(Value::List(kept));
+        Ok(())
+    }
+
+    /// Left fold: `list init [ quote ] fold` runs `acc elem quote` per element
+    fn builtin_fold(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("fold")?;
+        let mut accumulator = self.pop()?;
+        let values = self.pop_list("fold")?;
+
+        for value in values {
+            self.push(accumulator);
+            self.push(value);
+            self.execute_tokens(&quote)?;
+            accumulator = self
+                .pop()
+                .map_err(|_| VmError::StackUnderflow("fold".to_string()))?;
+        }
+        self.push(accumulator);
+        Ok(())
+    }
+
+    /// Half-open range: `start end range` produces `[start, ..., end - 1]`
+    fn builtin_range(&mut self) -> Result<(), VmError> {
+        let end = self.pop_nat("range")?;
+        let start = self.pop_nat("range")?;
+        if end.saturating_sub(start) > MAX_RANGE_LENGTH {
+            return Err(VmError::InvalidOperation(format!(
+                "range {}..{} is longer than the limit of {} elements",
+                start, end, MAX_RANGE_LENGTH
+            )));
+        }
+        self.push(Value::List((start..end).map(Value::Nat).collect()));
+        Ok(())
+    }
+
+    /// `cons` ( a List<a> -> List<a> ): prepend a value
+    fn builtin_cons(&mut self) -> Result<(), VmError> {
+        let mut values = self.pop_list("cons")?;
+        let value = self.pop()?;
+        values.insert(0, value);
+        self.push(Value::List(values));
+        Ok(())
+    }
+
+    fn builtin_head(&mut self) -> Result<(), VmError> {
+        let values = self.pop_list("head")?;
+        let head = values
+            .into_iter()
+            .next()
+            .ok_or_else(|| VmError::InvalidOperation("head of an empty list".to_string()))?;
+        self.push(head);
+        Ok(())
+    }
+
+    fn builtin_head_option(&mut self) -> Result<(), VmError> {
+        let values = self.pop_list("head?")?;
+        self.push(Value::Option(values.into_iter().next().map(Box::new)));
+        Ok(())
+    }
+
+    fn builtin_tail(&mut self) -> Result<(), VmError> {
+        let values = self.pop_list("tail")?;
+        if values.is_empty() {
+            return Err(VmError::InvalidOperation(
+                "tail of an empty list".to_string(),
+            ));
+        }
+        self.push(Value::List(values[1..].to_vec()));
+        Ok(())
+    }
+
+    fn builtin_tail_option(&mut self) -> Result<(), VmError> {
+        let values = self.pop_list("tail?")?;
+        let tail = (!values.is_empty()).then(|| Box::new(Value::List(values[1..].to_vec())));
+        self.push(Value::Option(tail));
+        Ok(())
+    }
+
+    /// `nth` ( List<a> Nat -> a ): the element at a zero-based index
+    fn builtin_nth(&mut self) -> Result<(), VmError> {
+        let index = self.pop_nat("nth")?;
+        let values = self.pop_list("nth")?;
+        let len = values.len();
+        let value = values.into_iter().nth(index as usize).ok_or_else(|| {
+            VmError::InvalidOperation(format!(
+                "nth index {} is out of bounds for a list of length {}",
+                index, len
+            ))
+        })?;
+        self.push(value);
+        Ok(())
+    }
+
+    fn builtin_nth_option(&mut self) -> Result<(), VmError> {
+        let index = self.pop_nat("nth?")?;
+        let values = self.pop_list("nth?")?;
+        let value = values.into_iter().nth(index as usize).map(Box::new);
+        self.push(Value::Option(value));
+        Ok(())
+    }
+
+    fn builtin_append(&mut self) -> Result<(), VmError> {
+        let second = self.pop_list("append")?;
+        let mut first = self.pop_list("append")?;
+        first.extend(second);
+        self.push(Value::List(first));
+        Ok(())
+    }
+
+    fn builtin_reverse(&mut self) -> Result<(), VmError> {
+        let mut values = self.pop_list("reverse")?;
+        values.reverse();
+        self.push(Value::List(values));
+        Ok(())
+    }
+
+    /// `sort` ( List<a> -> List<a> ): ascending order; the elements must
+    /// all be naturals, strings, booleans, ordinals or lists of those
+    fn builtin_sort(&mut self) -> Result<(), VmError> {
+        let mut values = self.pop_list("sort")?;
+
+        // Every element is checked before sorting, so that any two of them compare
+        if let Err(value) = SortKind::common(&values) {
+            return Err(VmError::InvalidOperation(format!(
+                "sort needs elements of one comparable type, but {} does not fit the others",
+                value
+            )));
+        }
+        values.sort_by(|a, b| compare_values(a, b).unwrap_or(Ordering::Equal));
+        self.push(Value::List(values));
+        Ok(())
+    }
+
+    /// `zip` ( List<a> List<a> -> List<List<a>> ): pair up elements as
+    /// two-element lists, stopping at the end of the shorter list
+    fn builtin_zip(&mut self) -> Result<(), VmError> {
+        let second = self.pop_list("zip")?;
+        let first = self.pop_list("zip")?;
+        let pairs = first
+            .into_iter()
+            .zip(second)
+            .map(|(a, b)| Value::List(vec![a, b]))
+            .collect();
+        self.push(Value::List(pairs));
+        Ok(())
+    }
+
     fn builtin_dot(&mut self) -> Result<(), VmError> {
         let value = self.pop()?;
         println!("{}", value);
@@ -665,13 +2076,338 @@ impl VirtualMachine {
         Ok(())
     }
 
+    /// List every word known to the VM, builtins first
+    fn builtin_words(&mut self) -> Result<(), VmError> {
+        let mut builtins: Vec<&String> = self
+            .type_signatures
+            .keys()
+            .filter(|name| !self.dictionary.contains_key(*name))
+            .collect();
+        builtins.sort();
+
+        let mut user_words: Vec<&String> = self.dictionary.keys().collect();
+        user_words.sort();
+
+        println!(
+            "Builtins: {}",
+            builtins
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        if !user_words.is_empty() {
+            println!(
+                "User words: {}",
+                user_words
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Show the definition of a word named by a string or a single-word quote
+    fn builtin_see(&mut self) -> Result<(), VmError> {
+        let name = match self.pop()? {
+            Value::String(name) => name,
+            Value::Quote(tokens) => match tokens.as_slice() {
+                [Token::Word(name)] => name.clone(),
+                _ => {
+                    return Err(VmError::InvalidOperation(
+                        "see expects a quote containing a single word".to_string(),
+                    ))
+                }
+            },
+            other => {
+                return Err(VmError::TypeMismatch {
+                    expected: "String or Quote for 'see'".to_string(),
+                    found: format!("{}", other),
+                })
+            }
+        };
+
+        println!("{}", self.describe_word(&name)?);
+        Ok(())
+    }
+
+    /// Render a word as source (user words) or as its stack effect (builtins)
+    pub fn describe_word(&self, name: &str) -> Result<String, VmError> {
+        if let Some(word_def) = self.dictionary.get(name) {
+            let body = word_def
+                .body
+                .iter()
+                .map(|token| token.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Ok(format!(": {} {} ;", name, body))
+        } else if let Some(signature) = self.type_signatures.get(name) {
+            Ok(format!("{} {} ( builtin )", name, signature))
+        } else {
+            Err(VmError::UnknownWord(name.to_string()))
+        }
+    }
+
+    fn builtin_clear(&mut self) -> Result<(), VmError> {
+        self.clear_stack();
+        Ok(())
+    }
+
+    fn builtin_depth(&mut self) -> Result<(), VmError> {
+        let depth = self.stack.len() as u64;
+        self.push(Value::Nat(depth));
+        Ok(())
+    }
+
+    fn builtin_print_string(&mut self) -> Result<(), VmError> {
+        let s = self.pop_string("print-string")?;
+        println!("{}", s);
+        Ok(())
+    }
+
+    fn builtin_concat(&mut self) -> Result<(), VmError> {
+        let b = self.pop_string("concat")?;
+        let a = self.pop_string("concat")?;
+        self.push(Value::String(a + &b));
+        Ok(())
+    }
+
+    /// `length` ( String -> Nat ): the number of characters, or of
+    /// elements for a list
+    fn builtin_length(&mut self) -> Result<(), VmError> {
+        let length = match self.pop()? {
+            Value::String(s) => s.chars().count(),
+            Value::List(values) => values.len(),
+            other => {
+                return Err(VmError::TypeMismatch {
+                    expected: "String or List for 'length'".to_string(),
+                    found: format!("{}", other),
+                })
+            }
+        };
+        self.push(Value::Nat(length as u64));
+        Ok(())
+    }
+
+    /// `substring` ( String start end -> String ): the characters from
+    /// `start` up to, not including, `end`
+    fn builtin_substring(&mut self) -> Result<(), VmError> {
+        let end = self.pop_nat("substring")? as usize;
+        let start = self.pop_nat("substring")? as usize;
+        let s = self.pop_string("substring")?;
+
+        let len = s.chars().count();
+        if start > end || end > len {
+            return Err(VmError::InvalidOperation(format!(
+                "substring {}..{} is out of bounds for a string of length {}",
+                start, end, len
+            )));
+        }
+        self.push(Value::String(
+            s.chars().skip(start).take(end - start).collect(),
+        ));
+        Ok(())
+    }
+
+    /// `split` ( String separator -> List<String> ); an empty separator
+    /// splits the string into its characters
+    fn builtin_split(&mut self) -> Result<(), VmError> {
+        let separator = self.pop_string("split")?;
+        let s = self.pop_string("split")?;
+
+        let parts: Vec<Value> = if separator.is_empty() {
+            s.chars().map(|ch| Value::String(ch.to_string())).collect()
+        } else {
+            s.split(separator.as_str())
+                .map(|part| Value::String(part.to_string()))
+                .collect()
+        };
+        self.push(Value::List(parts));
+        Ok(())
+    }
+
+    /// `join` ( List<String> separator -> String )
+    fn builtin_join(&mut self) -> Result<(), VmError> {
+        let separator = self.pop_string("join")?;
+        let parts = self
+            .pop_list("join")?
+            .into_iter()
+            .map(|value| match value {
+                Value::String(s) => Ok(s),
+                other => Err(VmError::TypeMismatch {
+                    expected: "List<String> for 'join'".to_string(),
+                    found: format!("{}", other),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.push(Value::String(parts.join(&separator)));
+        Ok(())
+    }
+
+    /// Replace the string on top of the stack with `f` applied to it
+    fn map_string(&mut self, op: &str, f: impl Fn(&str) -> String) -> Result<(), VmError> {
+        let s = self.pop_string(op)?;
+        self.push(Value::String(f(&s)));
+        Ok(())
+    }
+
+    /// `contains?` ( String needle -> Bool )
+    fn builtin_contains(&mut self) -> Result<(), VmError> {
+        let needle = self.pop_string("contains?")?;
+        let s = self.pop_string("contains?")?;
+        self.push(Value::Bool(s.contains(&needle)));
+        Ok(())
+    }
+
+    /// `string->nat` ( String -> Option<Nat> ): `None` unless the whole
+    /// string is a natural number
+    fn builtin_string_to_nat(&mut self) -> Result<(), VmError> {
+        let s = self.pop_string("string->nat")?;
+        let parsed = s.parse::<u64>().ok().map(|n| Box::new(Value::Nat(n)));
+        self.push(Value::Option(parsed));
+        Ok(())
+    }
+
+    fn builtin_nat_to_string(&mut self) -> Result<(), VmError> {
+        let n = self.pop_nat("nat->string")?;
+        self.push(Value::String(n.to_string()));
+        Ok(())
+    }
+
+    /// `to-string` ( a -> String ): the value as `.` would print it
+    fn builtin_to_string(&mut self) -> Result<(), VmError> {
+        let value = self.pop()?;
+        self.push(Value::String(value.to_string()));
+        Ok(())
+    }
+
+    /// `format` ( List<a> template -> String ): fill each `{}` in the
+    /// template with the next value of the list
+    fn builtin_format(&mut self) -> Result<(), VmError> {
+        let template = self.pop_string("format")?;
+        let values = self.pop_list("format")?;
+
+        let pieces: Vec<&str> = template.split("{}").collect();
+        if pieces.len() - 1 != values.len() {
+            return Err(VmError::InvalidOperation(format!(
+                "format template has {} placeholders but {} values were given",
+                pieces.len() - 1,
+                values.len()
+            )));
+        }
+
+        let mut formatted = pieces[0].to_string();
+        for (value, piece) in values.iter().zip(&pieces[1..]) {
+            formatted.push_str(&value.to_string());
+            formatted.push_str(piece);
+        }
+        self.push(Value::String(formatted));
+        Ok(())
+    }
+
+    /// `assert` ( Bool -> ): fail unless the value is true
+    fn builtin_assert(&mut self) -> Result<(), VmError> {
+        if self.pop_bool("assert")? {
+            return Ok(());
+        }
+        Err(VmError::AssertionFailed {
+            word: "assert".to_string(),
+            expected: vec![Value::Bool(true)],
+            actual: vec![Value::Bool(false)],
+        })
+    }
+
+    /// `assert-eq` ( actual expected -> ): fail unless the values are equal
+    fn builtin_assert_eq(&mut self) -> Result<(), VmError> {
+        let expected = self.pop()?;
+        let actual = self.pop()?;
+        if actual == expected {
+            return Ok(());
+        }
+        Err(VmError::AssertionFailed {
+            word: "assert-eq".to_string(),
+            expected: vec![expected],
+            actual: vec![actual],
+        })
+    }
+
+    /// `assert-stack` ( ... Quote -> ... ): fail unless the stack holds
+    /// exactly what the quote pushes when run on an empty stack
+    fn builtin_assert_stack(&mut self) -> Result<(), VmError> {
+        let quote = self.pop_quote("assert-stack")?;
+        let actual = std::mem::take(&mut self.stack);
+        let result = self.execute_tokens(&quote);
+        let expected = std::mem::replace(&mut self.stack, actual);
+        result?;
+
+        if self.stack == expected {
+            return Ok(());
+        }
+        Err(VmError::AssertionFailed {
+            word: "assert-stack".to_string(),
+            expected,
+            actual: self.stack.clone(),
+        })
+    }
+
+    /// `equiv?` ( Quote Quote -> Bool ): whether both quotes leave the
+    /// same values on random stacks of the first quote's input types
+    ///
+    /// When they disagree, the smallest stack found is kept for
+    /// [`take_counterexample`](Self::take_counterexample).
+    fn builtin_equiv(&mut self) -> Result<(), VmError> {
+        let rhs = self.pop_quote("equiv?")?;
+        let lhs = self.pop_quote("equiv?")?;
+        let signature = self
+            .signature_inferer()
+            .infer_word_type(&lhs)
+            .map_err(|e| VmError::TypeError {
+                word: "equiv?".to_string(),
+                message: e.to_string(),
+                expected: None,
+                found: None,
+            })?;
+
+        let seed = Value::Quote(lhs.clone()).to_string();
+        let mut generator = Generator::new(&seed, &self.type_definitions, &self.data_definitions);
+        let stacks = (0..property::TRIALS)
+            .map(|_| generator.inputs(&signature.inputs).map(|(stack, _)| stack))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|ty| {
+                VmError::InvalidOperation(format!(
+                    "Cannot generate random values of type {} for 'equiv?'",
+                    ty
+                ))
+            })?;
+
+        self.last_counterexample = None;
+        for stack in stacks {
+            if self.disagreement(stack.clone(), &lhs, &rhs).is_some() {
+                let (stack, _) = property::shrink(stack, |stack| {
+                    self.disagreement(stack.to_vec(), &lhs, &rhs).is_some()
+                });
+                self.last_counterexample = self.disagreement(stack, &lhs, &rhs);
+                break;
+            }
+        }
+        let equivalent = self.last_counterexample.is_none();
+        self.push(Value::Bool(equivalent));
+        Ok(())
+    }
+
+    fn builtin_help(&mut self) -> Result<(), VmError> {
+        print!("{}", CoreLibrary::new().show_help());
+        Ok(())
+    }
+
     fn builtin_ordinal(&mut self) -> Result<(), VmError> {
         let quote = self.pop()?;
         match quote {
-            Value::Quote(_tokens) => {
-                // For now, just return a simple ordinal
-                // In a full implementation, this would analyze the quote for termination
-                self.push(Value::Ordinal(OrdinalValue::Finite(1)));
+            Value::Quote(tokens) => {
+                let cost = self.ordinal_cost(&tokens);
+                self.push(Value::Ordinal(cost));
                 Ok(())
             }
             _ => Err(VmError::TypeMismatch {
@@ -681,6 +2417,44 @@ impl VirtualMachine {
         }
     }
 
+    fn builtin_ordinal_arithmetic(
+        &mut self,
+        op: &str,
+        apply: fn(&OrdinalValue, &OrdinalValue) -> Result<OrdinalValue, OrdinalError>,
+    ) -> Result<(), VmError> {
+        let b = self.pop_ordinal(op)?;
+        let a = self.pop_ordinal(op)?;
+        let result = apply(&a, &b).map_err(|e| VmError::InvalidOperation(e.to_string()))?;
+        self.push(Value::Ordinal(result));
+        Ok(())
+    }
+
+    /// Pop an ordinal from the stack, reporting `op` on a type mismatch
+    fn pop_ordinal(&mut self, op: &str) -> Result<OrdinalValue, VmError> {
+        match self.pop()? {
+            Value::Ordinal(ordinal) => Ok(ordinal),
+            other => Err(VmError::TypeMismatch {
+                expected: format!("Ordinal for '{}'", op),
+                found: format!("{}", other),
+            }),
+        }
+    }
+
+    /// Compute an ordinal bound on the number of steps a token sequence takes
+    ///
+    /// Every primitive step costs 1. Iteration combinators multiply the cost of
+    /// their quote by the iteration count when it is a literal in the code
+    /// (`5 [ ... ] times`, `0 10 range [ ... ] map`), and by ω when the count
+    /// is only known at runtime.
+    pub fn ordinal_cost(&self, tokens: &[Token]) -> OrdinalValue {
+        let words = |word: &str| {
+            self.dictionary
+                .get(word)
+                .map(|word_def| Cnf::cost_of(&word_def.ordinal_cost))
+        };
+        ordinal::token_cost(tokens, &words).to_ordinal()
+    }
+
     /// Get a word definition from the dictionary
     pub fn get_word_definition(&self, name: &str) -> Option<&WordDefinition> {
         self.dictionary.get(name)
@@ -792,22 +2566,27 @@ impl VirtualMachine {
         value_token: &Token,
         arms: &[MatchArm],
     ) -> Result<(), VmError> {
-        // First execute the value expression to get the value to match
-        self.execute_token(value_token)?;
+        // First execute the value expression to get the value to match.
+        // A quote is run rather than pushed, so `match [ ]` matches the top of the stack.
+        match value_token {
+            Token::Literal(Value::Quote(tokens)) => self.execute_tokens(tokens)?,
+            _ => self.execute_token(value_token)?,
+        }
         let value = self.pop()?;
 
         // Try each arm in order
         for arm in arms {
             if let Some(bindings) = self.match_pattern(&arm.pattern, &value)? {
-                // Pattern matched - push bindings to stack and execute body
-                for (_var_name, bound_value) in bindings {
-                    self.push(bound_value);
-                }
-                return self.execute_tokens(&arm.body);
+                // Pattern matched - pattern variables act as words inside the body
+                let body = bind_pattern_variables(&arm.body, &bindings);
+                return self.execute_tokens(&body);
             }
         }
 
-        Err(VmError::InvalidOperation("No pattern matched".to_string()))
+        Err(VmError::InvalidOperation(format!(
+            "No pattern matched value {}",
+            value
+        )))
     }
 
     /// Check if a pattern matches a value, returning bindings if successful
@@ -864,6 +2643,70 @@ impl VirtualMachine {
                             Ok(None)
                         }
                     }
+                    (_, Value::Composite { type_name, fields }) if name == type_name => {
+                        // `Name` alone matches any value of the type; otherwise
+                        // the arguments match the fields in declaration order
+                        if args.is_empty() {
+                            return Ok(Some(vec![]));
+                        }
+                        let Some(definition) = self.type_definitions.get(type_name) else {
+                            return Ok(None);
+                        };
+                        if args.len() != definition.fields.len() {
+                            return Err(VmError::InvalidOperation(format!(
+                                "pattern {} has {} arguments but {} has {} fields",
+                                pattern,
+                                args.len(),
+                                type_name,
+                                definition.fields.len()
+                            )));
+                        }
+                        let field_values: Vec<Value> = definition
+                            .fields
+                            .iter()
+                            .map(|(field, _)| fields.get(field).cloned().unwrap_or(Value::Unit))
+                            .collect();
+
+                        let mut all_bindings = vec![];
+                        for (pattern, value) in args.iter().zip(&field_values) {
+                            match self.match_pattern(pattern, value)? {
+                                Some(bindings) => all_bindings.extend(bindings),
+                                None => return Ok(None),
+                            }
+                        }
+                        Ok(Some(all_bindings))
+                    }
+                    (
+                        _,
+                        Value::Variant {
+                            constructor,
+                            args: values,
+                            ..
+                        },
+                    ) if name == constructor => {
+                        // `Ctor` alone matches any value built by the constructor
+                        if args.is_empty() {
+                            return Ok(Some(vec![]));
+                        }
+                        if args.len() != values.len() {
+                            return Err(VmError::InvalidOperation(format!(
+                                "pattern {} has {} arguments but {} takes {}",
+                                pattern,
+                                args.len(),
+                                constructor,
+                                values.len()
+                            )));
+                        }
+
+                        let mut all_bindings = vec![];
+                        for (pattern, value) in args.iter().zip(values) {
+                            match self.match_pattern(pattern, value)? {
+                                Some(bindings) => all_bindings.extend(bindings),
+                                None => return Ok(None),
+                            }
+                        }
+                        Ok(Some(all_bindings))
+                    }
                     _ => Ok(None),
                 }
             }
@@ -909,7 +2752,11 @@ impl VirtualMachine {
         let arms = vec![
             MatchArm {
                 pattern: some_pattern,
-                body: vec![Token::Word("dup".to_string()), Token::Word("+".to_string())],
+                body: vec![
+                    Token::Word("x".to_string()),
+                    Token::Word("x".to_string()),
+                    Token::Word("+".to_string()),
+                ],
             },
             MatchArm {
                 pattern: none_pattern,
@@ -926,6 +2773,476 @@ impl VirtualMachine {
 
     /// Get all user-defined words from the dictionary
     pub fn get_user_words(&self) -> Vec<String> {
-        self.dictionary.keys().cloned().collect()
+        self.dictionary
+            .keys()
+            .chain(self.type_words.keys())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Describe why a definition was rejected against its declared signature
+pub(crate) fn definition_type_error(
+    word_name: &str,
+    declared: &TypeSignature,
+    error: InferenceError,
+) -> VmError {
+    match error {
+        InferenceError::SignatureMismatch { inferred, .. } => VmError::TypeError {
+            word: word_name.to_string(),
+            message: "body does not match its declared signature".to_string(),
+            expected: Some(declared.to_string()),
+            found: Some(inferred.to_string()),
+        },
+        other => VmError::TypeError {
+            word: word_name.to_string(),
+            message: other.to_string(),
+            expected: Some(declared.to_string()),
+            found: None,
+        },
+    }
+}
+
+/// Describe a `match` that leaves out constructors of a `data` type
+pub(crate) fn match_arms_error(word_name: &str, missing: MissingArms) -> VmError {
+    VmError::TypeError {
+        word: word_name.to_string(),
+        message: missing.to_string(),
+        expected: Some(format!(
+            "an arm for each constructor of {}",
+            missing.type_name
+        )),
+        found: None,
+    }
+}
+
+/// Reject a value given to the generated word `op` for a field of type `ty`
+fn check_field(op: &str, value: &Value, ty: &Type) -> Result<(), VmError> {
+    if property::conforms(value, ty, &HashMap::new()) {
+        Ok(())
+    } else {
+        Err(VmError::TypeMismatch {
+            expected: format!("{} for '{}'", ty, op),
+            found: format!("{}", value),
+        })
+    }
+}
+
+/// The most elements `range` builds, so a huge range fails instead of
+/// exhausting memory
+const MAX_RANGE_LENGTH: u64 = 1 << 24;
+
+/// The types `parse_type` knows without a declaration
+const BUILTIN_TYPES: [&str; 9] = [
+    "Unit", "Bool", "Nat", "String", "Ordinal", "Quote", "Option", "List", "Result",
+];
+
+/// Parse the tokens of a `data Name = Ctor Type ... | Ctor ... ;`
+/// declaration, after `data` and without the closing `;`
+fn parse_data_definition(tokens: &[Token]) -> Result<DataDefinition, VmError> {
+    let name = match tokens.first() {
+        Some(Token::Word(name)) if name.starts_with(|c: char| c.is_uppercase()) => name.clone(),
+        _ => {
+            return Err(VmError::InvalidOperation(
+                "Expected a capitalized type name after 'data'".to_string(),
+            ))
+        }
+    };
+    let malformed = |detail: &str| {
+        VmError::InvalidOperation(format!("Malformed data type '{}': {}", name, detail))
+    };
+
+    if tokens.get(1) != Some(&Token::Word("=".to_string())) {
+        return Err(malformed("expected '='"));
+    }
+
+    let mut constructors: Vec<DataConstructor> = Vec::new();
+    let mut pos = 2;
+    loop {
+        let constructor = match tokens.get(pos) {
+            Some(Token::Word(w)) if w.starts_with(|c: char| c.is_uppercase()) => w.clone(),
+            Some(token) => {
+                return Err(malformed(&format!(
+                    "expected a constructor name, found {}",
+                    token
+                )))
+            }
+            None => return Err(malformed("expected a constructor name")),
+        };
+        if constructors
+            .iter()
+            .any(|existing| existing.name == constructor)
+        {
+            return Err(malformed(&format!(
+                "duplicate constructor '{}'",
+                constructor
+            )));
+        }
+        pos += 1;
+
+        let mut fields = Vec::new();
+        while tokens
+            .get(pos)
+            .is_some_and(|token| token != &Token::Word("|".to_string()))
+        {
+            fields.push(parse_type(tokens, &mut pos).map_err(|detail| malformed(&detail))?);
+        }
+        constructors.push(DataConstructor {
+            name: constructor,
+            fields,
+        });
+
+        if tokens.get(pos).is_none() {
+            break;
+        }
+        pos += 1;
+    }
+
+    Ok(DataDefinition { name, constructors })
+}
+
+/// Parse the tokens of a `type Name { field: Type ... }` declaration, after
+/// `type` and without the closing `}`
+fn parse_type_definition(tokens: &[Token]) -> Result<TypeDefinition, VmError> {
+    let name = match tokens.first() {
+        Some(Token::Word(name)) if name.starts_with(|c: char| c.is_uppercase()) => name.clone(),
+        _ => {
+            return Err(VmError::InvalidOperation(
+                "Expected a capitalized type name after 'type'".to_string(),
+            ))
+        }
+    };
+    let malformed =
+        |detail: &str| VmError::InvalidOperation(format!("Malformed type '{}': {}", name, detail));
+
+    if tokens.get(1) != Some(&Token::Word("{".to_string())) {
+        return Err(malformed("expected '{'"));
+    }
+
+    // `field::Type` lexes as one qualified name
+    let tokens: Vec<Token> = tokens[2..]
+        .iter()
+        .flat_map(|token| match token {
+            Token::Word(word) if word.contains("::") => {
+                let (field, ty) = word.split_once("::").unwrap();
+                [field, "::", ty]
+                    .map(|w| Token::Word(w.to_string()))
+                    .to_vec()
+            }
+            token => vec![token.clone()],
+        })
+        .collect();
+
+    let mut fields: Vec<(String, Type)> = Vec::new();
+    let mut pos = 0;
+    while let Some(token) = tokens.get(pos) {
+        let Token::Word(field) = token else {
+            return Err(malformed(&format!(
+                "expected a field name, found {}",
+                token
+            )));
+        };
+        if !matches!(tokens.get(pos + 1), Some(Token::Word(w)) if w == ":" || w == "::") {
+            return Err(malformed(&format!("expected ':' after field '{}'", field)));
+        }
+        if fields.iter().any(|(existing, _)| existing == field) {
+            return Err(malformed(&format!("duplicate field '{}'", field)));
+        }
+        pos += 2;
+        let ty = parse_type(&tokens, &mut pos).map_err(|detail| malformed(&detail))?;
+        fields.push((field.clone(), ty));
+
+        if tokens.get(pos) == Some(&Token::Word(",".to_string())) {
+            pos += 1;
+        }
+    }
+
+    let constructor_signature = TypeSignature {
+        inputs: fields.iter().map(|(_, ty)| ty.clone()).collect(),
+        outputs: vec![Type::Composite {
+            name: name.clone(),
+            fields: fields.iter().cloned().collect(),
+        }],
+    };
+    Ok(TypeDefinition {
+        name,
+        fields,
+        constructor_signature,
+    })
+}
+
+/// Parse the tokens of a `theorem name: [ lhs ] == [ rhs ]` or `lemma`
+/// declaration, starting with its keyword and without the closing `;`
+fn parse_theorem(tokens: &[Token]) -> Result<Theorem, VmError> {
+    let is_lemma = tokens.first() == Some(&Token::Word("lemma".to_string()));
+    let name = match tokens.get(1) {
+        Some(Token::Word(name)) => name.strip_suffix(':').unwrap_or(name).to_string(),
+        _ => {
+            return Err(VmError::InvalidOperation(
+                "Expected a theorem name".to_string(),
+            ))
+        }
+    };
+    let colon = usize::from(tokens.get(2) == Some(&Token::Word(":".to_string())));
+    match &tokens[2 + colon..] {
+        [Token::Literal(Value::Quote(lhs)), Token::Word(eq), Token::Literal(Value::Quote(rhs))]
+            if eq == "==" =>
+        {
+            Ok(Theorem {
+                name,
+                lhs: lhs.clone(),
+                rhs: rhs.clone(),
+                is_lemma,
+            })
+        }
+        _ => Err(VmError::InvalidOperation(format!(
+            "Malformed theorem '{}': expected '{}: [ ... ] == [ ... ] ;'",
+            name, name
+        ))),
+    }
+}
+
+/// Parse the tokens of a `:: name ( inputs -> outputs )` declaration
+fn parse_signature_declaration(tokens: &[Token]) -> Result<(String, TypeSignature), VmError> {
+    let name = match tokens.first() {
+        Some(Token::Word(name)) => name.clone(),
+        _ => {
+            return Err(VmError::InvalidOperation(
+                "Expected a word name after '::'".to_string(),
+            ))
+        }
+    };
+    let malformed = |detail: &str| {
+        VmError::InvalidOperation(format!(
+            "Malformed type signature for '{}': {}",
+            name, detail
+        ))
+    };
+
+    if tokens.get(1) != Some(&Token::Word("(".to_string())) {
+        return Err(malformed("expected '('"));
+    }
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut found_arrow = false;
+    let mut pos = 2;
+    loop {
+        match tokens.get(pos) {
+            Some(Token::Word(w)) if w == ")" => {
+                pos += 1;
+                break;
+            }
+            Some(Token::Word(w)) if w == "->" && !found_arrow => {
+                found_arrow = true;
+                pos += 1;
+            }
+            Some(_) => {
+                let ty = parse_type(tokens, &mut pos).map_err(|detail| malformed(&detail))?;
+                if found_arrow {
+                    outputs.push(ty);
+                } else {
+                    inputs.push(ty);
+                }
+            }
+            None => return Err(malformed("expected ')'")),
+        }
+    }
+
+    if !found_arrow {
+        return Err(malformed("expected '->'"));
+    }
+    if pos != tokens.len() {
+        return Err(malformed("unexpected tokens after ')'"));
+    }
+
+    Ok((name, TypeSignature { inputs, outputs }))
+}
+
+/// Order two values of the same comparable kind, comparing lists
+/// element by element
+fn compare_values(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Nat(a), Value::Nat(b)) => Some(a.cmp(b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Ordinal(a), Value::Ordinal(b)) => Some(a.cmp(b)),
+        (Value::List(a), Value::List(b)) => {
+            for (a, b) in a.iter().zip(b) {
+                match compare_values(a, b)? {
+                    Ordering::Equal => continue,
+                    ordering => return Some(ordering),
+                }
+            }
+            Some(a.len().cmp(&b.len()))
+        }
+        _ => None,
+    }
+}
+
+/// The values `sort` can order, which must all be of one kind
+#[derive(Debug, Clone, PartialEq)]
+enum SortKind {
+    Nat,
+    String,
+    Bool,
+    Ordinal,
+    /// A list of elements of one kind, unknown while the list is empty
+    List(Option<Box<SortKind>>),
+}
+
+impl SortKind {
+    /// The kind of a value, or `None` if it cannot be sorted
+    fn of(value: &Value) -> Option<SortKind> {
+        match value {
+            Value::Nat(_) => Some(SortKind::Nat),
+            Value::String(_) => Some(SortKind::String),
+            Value::Bool(_) => Some(SortKind::Bool),
+            Value::Ordinal(_) => Some(SortKind::Ordinal),
+            Value::List(items) => {
+                let element = SortKind::common(items).ok()?;
+                Some(SortKind::List(element.map(Box::new)))
+            }
+            _ => None,
+        }
+    }
+
+    /// The kind shared by all of `values`, `None` if there are none, or the
+    /// first value that differs from those before it
+    fn common(values: &[Value]) -> Result<Option<SortKind>, &Value> {
+        let mut kind: Option<SortKind> = None;
+        for value in values {
+            let next = SortKind::of(value).ok_or(value)?;
+            kind = Some(match kind {
+                Some(kind) => kind.merge(next).ok_or(value)?,
+                None => next,
+            });
+        }
+        Ok(kind)
+    }
+
+    /// The kind of both `self` and `other`, or `None` if they differ
+    fn merge(self, other: SortKind) -> Option<SortKind> {
+        match (self, other) {
+            (SortKind::List(None), list @ SortKind::List(_))
+            | (list @ SortKind::List(_), SortKind::List(None)) => Some(list),
+            (SortKind::List(Some(a)), SortKind::List(Some(b))) => {
+                Some(SortKind::List(Some(Box::new(a.merge(*b)?))))
+            }
+            (a, b) => (a == b).then_some(a),
+        }
+    }
+}
+
+/// Parse one type such as `Nat`, `a`, `List<Nat>` or `Result<Nat, String>`
+fn parse_type(tokens: &[Token], pos: &mut usize) -> Result<Type, String> {
+    let name = match tokens.get(*pos) {
+        Some(Token::Word(name)) => name.clone(),
+        Some(token) => return Err(format!("unexpected '{}' in type", token)),
+        None => return Err("expected a type".to_string()),
+    };
+    *pos += 1;
+
+    let mut params = Vec::new();
+    if tokens.get(*pos) == Some(&Token::Word("<".to_string())) {
+        *pos += 1;
+        loop {
+            params.push(parse_type(tokens, pos)?);
+            match tokens.get(*pos) {
+                Some(Token::Word(w)) if w == "," => *pos += 1,
+                Some(Token::Word(w)) if w == ">" => {
+                    *pos += 1;
+                    break;
+                }
+                _ => return Err(format!("expected ',' or '>' in parameters of {}", name)),
+            }
+        }
+    }
+
+    let mut params = params.into_iter();
+    let ty = match (name.as_str(), params.len()) {
+        ("Unit", 0) => Type::Unit,
+        ("Bool", 0) => Type::Bool,
+        ("Nat", 0) => Type::Nat,
+        ("String", 0) => Type::String,
+        ("Ordinal", 0) => Type::Ordinal,
+        ("Quote", 0) => Type::Quote,
+        ("Option", 1) => Type::Option(Box::new(params.next().unwrap())),
+        ("List", 1) => Type::List(Box::new(params.next().unwrap())),
+        ("Result", 2) => Type::Result(
+            Box::new(params.next().unwrap()),
+            Box::new(params.next().unwrap()),
+        ),
+        (_, 0) if name.starts_with(|c: char| c.is_lowercase()) => Type::Variable(name),
+        (_, 0) if name.starts_with(|c: char| c.is_uppercase()) => Type::Composite {
+            name,
+            fields: HashMap::new(),
+        },
+        _ => return Err(format!("unknown type '{}'", name)),
+    };
+    Ok(ty)
+}
+
+/// Replace pattern variables in a match arm body with the values they bound
+///
+/// Substitution reaches into nested quotes and match expressions, stopping
+/// wherever an inner pattern rebinds the same name.
+fn bind_pattern_variables(tokens: &[Token], bindings: &[(String, Value)]) -> Vec<Token> {
+    if bindings.is_empty() {
+        return tokens.to_vec();
+    }
+
+    tokens
+        .iter()
+        .map(|token| match token {
+            Token::Word(word) => match bindings.iter().find(|(name, _)| name == word) {
+                Some((_, value)) => Token::Literal(value.clone()),
+                None => token.clone(),
+            },
+            Token::Literal(Value::Quote(inner)) => {
+                Token::Literal(Value::Quote(bind_pattern_variables(inner, bindings)))
+            }
+            Token::MatchExpression { value, arms } => Token::MatchExpression {
+                value: Box::new(
+                    bind_pattern_variables(std::slice::from_ref(value.as_ref()), bindings)
+                        .remove(0),
+                ),
+                arms: arms
+                    .iter()
+                    .map(|arm| {
+                        let mut shadowed = Vec::new();
+                        pattern_variables(&arm.pattern, &mut shadowed);
+                        let visible: Vec<(String, Value)> = bindings
+                            .iter()
+                            .filter(|(name, _)| !shadowed.contains(name))
+                            .cloned()
+                            .collect();
+                        MatchArm {
+                            pattern: arm.pattern.clone(),
+                            body: bind_pattern_variables(&arm.body, &visible),
+                        }
+                    })
+                    .collect(),
+            },
+            _ => token.clone(),
+        })
+        .collect()
+}
+
+/// Collect the variable names bound by a pattern
+fn pattern_variables(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Variable(name) => names.push(name.clone()),
+        Pattern::Constructor { args, .. } => {
+            for arg in args {
+                pattern_variables(arg, names);
+            }
+        }
+        Pattern::List(patterns) => {
+            for pattern in patterns {
+                pattern_variables(pattern, names);
+            }
+        }
+        Pattern::Wildcard | Pattern::Literal(_) => {}
     }
 }
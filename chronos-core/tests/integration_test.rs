@@ -3,7 +3,10 @@
 //! These tests verify that the public API works correctly for common use cases
 //! and that the core language functionality operates as expected.
 
-use chronos_core::{ChronosCore, Value};
+use chronos_core::{
+    ChronosCore, ChronosError, OrdinalValue, Statement, Type, TypeSignature, Value,
+    WordDefinition,
+};
 
 #[test]
 fn test_basic_language_operations() {
@@ -291,3 +294,849 @@ fn test_comprehensive_workflow() {
     // 5. Final state should be clean
     assert_eq!(core.stack_depth(), 0);
 }
+
+#[test]
+fn test_control_flow_combinators() {
+    let mut core = ChronosCore::new();
+
+    // when / unless only run their quote on the matching condition
+    core.eval_source("1 true [ 1 + ] when").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(2));
+    core.eval_source("1 false [ 1 + ] when").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(1));
+    core.eval_source("1 false [ 1 + ] unless").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(2));
+    core.eval_source("1 true [ 1 + ] unless").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(1));
+
+    // times runs the quote n times, including zero
+    core.eval_source("0 5 [ 2 + ] times").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(10));
+    core.eval_source("7 0 [ drop ] times").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(7));
+
+    // call executes a quote, including nested quotes
+    core.eval_source("3 [ dup * ] call").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(9));
+    core.eval_source("[ [ 4 ] call ] call").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(4));
+    assert_eq!(core.stack_depth(), 0);
+
+    // Wrong operand types are rejected
+    assert!(core.eval_source("true 5 times").is_err());
+    assert!(core.eval_source("5 [ 1 ] when").is_err());
+}
+
+#[test]
+fn test_system_words() {
+    let mut core = ChronosCore::new();
+
+    core.eval_source("1 2 3 depth").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(3));
+
+    core.eval_source("clear depth").unwrap();
+    assert_eq!(core.get_stack(), vec![Value::Nat(0)]);
+    core.clear_stack();
+
+    core.eval_source(": square dup * ;").unwrap();
+    core.eval_source("\"square\" see [ dup ] see words help").unwrap();
+    assert_eq!(core.stack_depth(), 0);
+    assert!(core.eval_source("\"no-such-word\" see").is_err());
+    // `see` takes a word name, not a value of any type
+    assert!(core.eval(":: show ( -> ) ; : show \"square\" see ;").is_ok());
+    assert!(core.eval(":: bad ( -> ) ; : bad 3 see ;").is_err());
+
+    assert!(core.is_word_defined("times"));
+    assert!(core.is_word_defined("depth"));
+}
+
+#[test]
+fn test_bounded_iteration() {
+    let mut core = ChronosCore::new();
+
+    core.eval_source("1 4 range").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![Value::Nat(1), Value::Nat(2), Value::Nat(3)])
+    );
+
+    // Ranges too long to build fail instead of aborting
+    let error = core.eval_source("0 18446744073709551615 range").unwrap_err();
+    assert!(error.to_string().contains("longer than the limit"));
+    core.clear_stack();
+    core.eval_source("5 2 range").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::List(vec![]));
+
+    core.eval_source("0 1 4 range [ + ] each").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(6));
+
+    core.eval_source("0 3 range [ dup * ] map").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![Value::Nat(0), Value::Nat(1), Value::Nat(4)])
+    );
+
+    core.eval_source("0 6 range [ 3 < ] filter").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![Value::Nat(0), Value::Nat(1), Value::Nat(2)])
+    );
+
+    core.eval_source("1 5 range 1 [ * ] fold").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(24));
+    assert_eq!(core.stack_depth(), 0);
+
+    // while stops as soon as the condition fails
+    core.eval_source("0 10 [ dup 5 < ] [ 1 + ] while").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(5));
+
+    // ...and fails once its fuel runs out
+    core.clear_stack();
+    assert!(core.eval_source("0 3 [ dup 5 < ] [ 1 + ] while").is_err());
+
+    core.clear_stack();
+    assert!(core.eval_source("5 [ 1 + ] map").is_err());
+}
+
+#[test]
+fn test_iteration_ordinal_cost() {
+    let mut core = ChronosCore::new();
+
+    // Straight-line code has a finite cost
+    core.eval_source("[ 1 2 + ] --ordinal").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Ordinal(OrdinalValue::Finite(3)));
+
+    // A literal iteration count keeps the bound finite
+    core.eval_source("[ 3 [ 1 + ] times ] --ordinal").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Ordinal(OrdinalValue::Finite(9)));
+
+    // An unknown list length makes the bound transfinite
+    core.eval_source("[ [ 1 + ] map ] --ordinal").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Ordinal(OrdinalValue::Omega));
+
+    // Work after the loop is still counted
+    core.eval_source("[ [ 1 + ] map 2 ] --ordinal").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::Ordinal(OrdinalValue::Sum(vec![
+            OrdinalValue::Omega,
+            OrdinalValue::Finite(1)
+        ]))
+    );
+
+    // Nested unbounded loops climb to ω^2
+    core.eval_source("[ [ [ 1 + ] map ] map ] --ordinal").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::Ordinal(OrdinalValue::OmegaPower(Box::new(OrdinalValue::Finite(2))))
+    );
+
+    // User words carry the cost of their bodies
+    core.eval_source(": bump 3 [ 1 + ] times ;").unwrap();
+    core.eval_source("[ bump ] --ordinal").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Ordinal(OrdinalValue::Finite(9)));
+}
+
+#[test]
+fn test_match_expressions() {
+    let mut core = ChronosCore::new();
+
+    // Option patterns bind their payload as a word inside the arm
+    core.eval("match [ 41 Some ] | Some(x) -> x 1 + | None -> 0").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(42));
+    core.eval("match None | Some(x) -> x 1 + | None -> 0").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(0));
+
+    // Result patterns
+    core.eval("match [ 7 Ok ] | Ok(v) -> v v * | Err(_) -> 0").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(49));
+    core.eval("match [ \"boom\" Err ] | Ok(_) -> 1 | Err(e) -> e").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::String("boom".to_string()));
+
+    // Literal and list patterns, with `match [ ]` matching the stack top
+    core.eval("3 match [ ] | 0 -> \"zero\" | 3 -> \"three\" | _ -> \"many\"").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::String("three".to_string()));
+    core.eval("match [ 1 3 range ] | [] -> 0 | [a, b] -> a b + | _ -> 99").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(3));
+
+    // Inside word definitions the arms end at ';'
+    core.eval(": unwrap-or-zero match [ ] | Some(x) -> x | None -> 0 ;").unwrap();
+    core.eval("5 Some unwrap-or-zero None unwrap-or-zero").unwrap();
+    assert_eq!(core.get_stack(), vec![Value::Nat(5), Value::Nat(0)]);
+    core.clear_stack();
+
+    // Non-exhaustive matches fail at runtime, malformed ones at parse time
+    assert!(core.eval("match [ 1 ] | 0 -> 0").is_err());
+    core.clear_stack();
+    assert!(core.eval("match [ 1 ] | Some(x) x").is_err());
+}
+
+#[test]
+fn test_declared_signatures() {
+    let mut core = ChronosCore::new();
+
+    // Matching declarations are accepted and recorded
+    core.eval(":: square ( Nat -> Nat ) ; : square dup * ;").unwrap();
+    core.eval(":: twin ( a -> a a ) ; : twin dup ;").unwrap();
+    core.eval(":: quad ( Nat -> Nat ) ; : quad square square ;").unwrap();
+    core.eval("3 quad").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(81));
+
+    // Quotations passed to control flow words are checked too
+    core.eval(":: pick ( Bool -> Nat ) ; : pick [ 1 ] [ 2 ] if ;").unwrap();
+    core.eval(":: bump ( Nat -> Nat ) ; : bump 3 [ 1 + ] times ;").unwrap();
+
+    // Mismatches are type errors naming the word, and the word is not defined
+    let err = core
+        .eval(":: wrong ( Nat -> Bool ) ; : wrong 1 + ;")
+        .unwrap_err();
+    match err {
+        ChronosError::TypeError { word_name, .. } => {
+            assert_eq!(word_name, Some("wrong".to_string()))
+        }
+        other => panic!("Expected a type error, got {:?}", other),
+    }
+    assert!(!core.is_word_defined("wrong"));
+
+    // Declared type variables must stay polymorphic
+    assert!(matches!(
+        core.eval(":: id ( a -> a ) ; : id 1 + ;"),
+        Err(ChronosError::TypeError { .. })
+    ));
+    assert!(matches!(
+        core.eval(":: short ( Nat Nat -> Nat ) ; : short + + ;"),
+        Err(ChronosError::TypeError { .. })
+    ));
+    assert!(matches!(
+        core.eval(":: branches ( Bool -> Nat ) ; : branches [ 1 ] [ ] if ;"),
+        Err(ChronosError::TypeError { .. })
+    ));
+
+    // The list combinators are checked through their quotation's effect
+    core.eval(":: count ( List<Nat> -> Nat ) ; : count 0 swap [ drop 1 + ] each ;")
+        .unwrap();
+    core.eval(":: total ( List<Nat> -> Nat ) ; : total 0 [ + ] fold ;").unwrap();
+    core.eval(":: small ( List<Nat> -> List<Nat> ) ; : small [ 3 < ] filter ;").unwrap();
+    assert!(matches!(
+        core.eval(":: flags ( List<Nat> -> List<Bool> ) ; : flags [ 1 + ] map ;"),
+        Err(ChronosError::TypeError { .. })
+    ));
+    assert!(core.assumed_signatures().is_empty());
+
+    // Bodies whose effect cannot be inferred are accepted but reported
+    core.eval(":: unwrap ( Option<Nat> -> Nat ) ; : unwrap match [ ] | Some(x) -> x | None -> 0 ;")
+        .unwrap();
+    core.eval_source(":: apply ( Nat Quote -> Nat ) ; : apply call ;").unwrap();
+    let assumed: Vec<&String> = core.assumed_signatures().keys().collect();
+    assert_eq!(assumed, vec!["apply", "unwrap"]);
+    core.eval(":: unwrap ( Option<Nat> -> Nat ) ; : unwrap drop 0 ;").unwrap();
+    assert!(!core.assumed_signatures().contains_key("unwrap"));
+}
+
+#[test]
+fn test_compile_pipeline() {
+    let mut core = ChronosCore::new();
+
+    let program = core
+        .compile(":: square ( Nat -> Nat ) ; : square dup * ; 5 square")
+        .unwrap();
+    assert_eq!(program.statements.len(), 3);
+    match &program.statements[1] {
+        Statement::WordDefinition(word_def) => {
+            assert_eq!(word_def.signature.inputs, vec![Type::Nat]);
+            assert_eq!(word_def.ordinal_cost, OrdinalValue::Finite(2));
+        }
+        other => panic!("Expected a word definition, got {:?}", other),
+    }
+
+    // Compiling does not touch the runtime; running does
+    assert_eq!(core.stack_depth(), 0);
+    core.run(&program).unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(25));
+
+    // Undeclared definitions get an inferred signature
+    let program = core.compile(": inc 1 + ;").unwrap();
+    match &program.statements[0] {
+        Statement::WordDefinition(word_def) => {
+            assert_eq!(word_def.signature.inputs, vec![Type::Nat]);
+            assert_eq!(word_def.signature.outputs, vec![Type::Nat]);
+        }
+        other => panic!("Expected a word definition, got {:?}", other),
+    }
+
+    // Each stage reports its own error kind
+    assert!(matches!(
+        core.compile("[ 1 2"),
+        Err(ChronosError::ParseError { .. })
+    ));
+    assert!(matches!(
+        core.compile(":: bad ( Nat -> Bool ) ; : bad 1 + ;"),
+        Err(ChronosError::TypeError { .. })
+    ));
+    let looping = ": spin spin spin spin spin spin spin spin spin spin spin spin ;";
+    assert!(matches!(
+        core.compile(looping),
+        Err(ChronosError::OrdinalError { .. })
+    ));
+    assert!(core.eval(looping).is_err());
+    assert!(!core.is_word_defined("spin"));
+
+    // Termination is checked by the strict analysis, which certifies every word
+    assert!(matches!(
+        core.compile(": spin spin ;"),
+        Err(ChronosError::OrdinalError { .. })
+    ));
+    let program = core
+        .compile(": fact dup 1 > [ dup 1 - fact * ] when ; : fact2 fact fact ;")
+        .unwrap();
+    let words: Vec<&str> = program
+        .certificates
+        .iter()
+        .map(|certificate| certificate.word.as_str())
+        .collect();
+    assert_eq!(words, vec!["fact", "fact2"]);
+    assert!(program.certificates.iter().all(|c| c.is_accepted()));
+    assert_eq!(program.certificates[0].components.len(), 1);
+}
+
+#[test]
+fn test_error_spans() {
+    let mut core = ChronosCore::new();
+
+    // Runtime errors point at the token that raised them
+    let source = "1 2 +\n3 frob";
+    let error = core.eval_source(source).unwrap_err();
+    let span = error.span().expect("runtime error should carry a span");
+    assert_eq!((span.line, span.column, span.len), (2, 3, 4));
+    assert!(error.render(source).ends_with("2 | 3 frob\n  |   ^^^^"));
+
+    // Lexical errors point at the offending character
+    let error = core.eval_source("1 2 €").unwrap_err();
+    assert!(matches!(error, ChronosError::LexError { position: Some(4), .. }));
+    assert_eq!(error.span().map(|s| s.column), Some(5));
+
+    // Unbalanced quotes point at the bracket
+    let error = core.eval_source("1 ] 2").unwrap_err();
+    assert_eq!(error.span().map(|s| s.column), Some(3));
+
+    // Type errors in definitions cover the whole definition
+    let source = ":: bad ( Nat -> Bool ) ;\n: bad 1 + ;";
+    let error = core.eval_source(source).unwrap_err();
+    let span = error.span().unwrap();
+    assert_eq!((span.line, span.column, span.len), (2, 1, 11));
+
+    // The compile pipeline locates errors too
+    let error = core.compile("dup\n: 1 ;").unwrap_err();
+    assert!(matches!(
+        error,
+        ChronosError::ParseError {
+            token_position: Some(2),
+            ..
+        }
+    ));
+    assert_eq!(error.span().map(|s| (s.line, s.column)), Some((2, 3)));
+
+    core.clear_stack();
+    let error = core.eval(": one 1 ;\nswap").unwrap_err();
+    assert_eq!(error.span().map(|s| (s.line, s.column)), Some((2, 1)));
+}
+
+#[cfg(not(feature = "bigint"))]
+#[test]
+fn test_checked_arithmetic() {
+    let mut core = ChronosCore::new();
+
+    let source = "4294967296 4294967296 *";
+    match core.eval_source(source) {
+        Err(ChronosError::ArithmeticError {
+            operation,
+            operands,
+            span,
+            ..
+        }) => {
+            assert_eq!(operation, "*");
+            assert_eq!(operands, vec!["4294967296", "4294967296"]);
+            assert_eq!(span.map(|s| s.column), Some(23));
+        }
+        other => panic!("Expected an arithmetic error, got {:?}", other),
+    }
+
+    core.clear_stack();
+    assert!(matches!(
+        core.eval("18446744073709551615 1 +"),
+        Err(ChronosError::ArithmeticError { .. })
+    ));
+
+    // Results that fit are unaffected
+    core.clear_stack();
+    core.eval_source("18446744073709551614 1 +").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(u64::MAX));
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_bigint_arithmetic() {
+    let mut core = ChronosCore::new();
+
+    core.eval_source(": fact dup 1 > [ dup 1 - fact * ] when ; 25 fact")
+        .unwrap();
+    assert_eq!(
+        core.pop().unwrap().to_string(),
+        "15511210043330985984000000"
+    );
+
+    // Big literals are read directly, and shrink back to Nat when they fit
+    core.eval_source("18446744073709551616 1 - 18446744073709551615 =")
+        .unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn test_termination_certificates() {
+    let mut core = ChronosCore::new();
+
+    core.eval_source(": fact dup 1 > [ dup 1 - fact * ] when ;")
+        .unwrap();
+    let certificate = core.certify_termination("fact").unwrap();
+    assert_eq!(certificate.bound, Some(OrdinalValue::Omega));
+
+    // Rejected words are not defined, and the error explains why
+    let error = core.eval_source(": loop dup loop ;").unwrap_err();
+    assert!(error.to_string().contains("loop -> loop"));
+    assert!(!core.is_word_defined("loop"));
+
+    // Words defined without the checks can still be certified
+    let mut define_unchecked = |name: &str, body: &str| {
+        let body = core.parse(body).unwrap();
+        core.define_untyped_word(WordDefinition {
+            name: name.to_string(),
+            signature: TypeSignature {
+                inputs: vec![],
+                outputs: vec![],
+            },
+            body,
+            is_axiom: false,
+            ordinal_cost: OrdinalValue::Zero,
+        })
+        .unwrap();
+    };
+    define_unchecked("loop", "dup loop");
+    // The decrement is outweighed by the increment before it
+    define_unchecked("f", "dup 0 = [ ] [ 2 + 1 - f ] if");
+    let certificate = core.certify_termination("loop").unwrap();
+    assert!(!certificate.is_accepted());
+    assert!(certificate.to_string().contains("loop -> loop"));
+    let certificate = core.certify_termination("f").unwrap();
+    assert!(!certificate.is_accepted());
+    assert!(certificate.to_string().contains("f -> f: no decreasing argument"));
+
+    assert!(core.certify_termination("missing").is_err());
+}
+
+#[test]
+fn test_ordinal_words() {
+    let mut core = ChronosCore::new();
+
+    core.eval_source("1 ord omega ord+ omega 1 ord ord+").unwrap();
+    assert_eq!(core.pop().unwrap().to_string(), "ω + 1");
+    assert_eq!(core.pop().unwrap(), Value::Ordinal(OrdinalValue::Omega));
+
+    core.eval_source("omega 2 ord ord* omega omega ord^ ord<").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Bool(true));
+
+    core.eval_source(":: succ ( Ordinal -> Ordinal ) ; : succ 1 ord ord+ ; omega succ")
+        .unwrap();
+    assert_eq!(core.pop().unwrap().to_string(), "ω + 1");
+
+    assert!(core.eval("1 omega ord+").is_err());
+}
+
+#[test]
+fn test_container_signatures() {
+    let mut core = ChronosCore::new();
+
+    core.eval(":: wrap ( a -> Option<a> ) ; : wrap Some ;").unwrap();
+    core.eval(":: pair ( Nat Nat -> List<Nat> ) ; : pair 2 list ;")
+        .unwrap();
+    core.eval(":: fail ( String -> Result<Nat, String> ) ; : fail Err ;")
+        .unwrap();
+    core.eval("3 4 pair").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![Value::Nat(3), Value::Nat(4)])
+    );
+
+    // Container element types are checked against the declaration
+    assert!(matches!(
+        core.eval(":: bad ( Nat -> Option<Bool> ) ; : bad Some ;"),
+        Err(ChronosError::TypeError { .. })
+    ));
+}
+
+#[test]
+fn test_string_words() {
+    let mut core = ChronosCore::new();
+    let string = |s: &str| Value::String(s.to_string());
+
+    core.eval_source("\"ab\" \"cd\" concat").unwrap();
+    assert_eq!(core.pop().unwrap(), string("abcd"));
+    core.eval_source("\"héllo\" length").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(5));
+    core.eval_source("\"chronos\" 1 4 substring").unwrap();
+    assert_eq!(core.pop().unwrap(), string("hro"));
+    assert!(core.eval_source("\"abc\" 2 4 substring").is_err());
+    core.clear_stack();
+
+    core.eval_source("\"a,b,c\" \",\" split").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![string("a"), string("b"), string("c")])
+    );
+    core.eval_source("\"a,b,c\" \",\" split \"-\" join").unwrap();
+    assert_eq!(core.pop().unwrap(), string("a-b-c"));
+
+    core.eval_source("\"  Mixed Case  \" trim to-upper").unwrap();
+    assert_eq!(core.pop().unwrap(), string("MIXED CASE"));
+    core.eval_source("\"ordinal\" \"din\" contains?").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Bool(true));
+
+    core.eval_source("\"42\" string->nat").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Option(Some(Box::new(Value::Nat(42)))));
+    core.eval_source("\"4x\" string->nat").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Option(None));
+    core.eval_source("7 nat->string \"!\" concat").unwrap();
+    assert_eq!(core.pop().unwrap(), string("7!"));
+    core.eval_source("true to-string").unwrap();
+    assert_eq!(core.pop().unwrap(), string("true"));
+
+    core.eval_source("1 3 range \"{} to {}\" format").unwrap();
+    assert_eq!(core.pop().unwrap(), string("1 to 2"));
+    assert!(core.eval_source("1 3 range \"{}\" format").is_err());
+    assert!(core.eval_source("3 length").is_err());
+
+    // `length` takes a String or a List, and definitions are checked for it
+    assert!(core
+        .eval_source(":: bad ( -> Nat ) ; : bad true length ;")
+        .is_err());
+    core.eval_source(":: pair-length ( -> Nat ) ; : pair-length #[1, 2] length ; pair-length")
+        .unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(2));
+}
+
+#[test]
+fn test_list_words() {
+    let mut core = ChronosCore::new();
+    let nats = |values: &[u64]| Value::List(values.iter().copied().map(Value::Nat).collect());
+    let some = |value: Value| Value::Option(Some(Box::new(value)));
+
+    core.eval_source("#[3, 1, 2]").unwrap();
+    assert_eq!(core.pop().unwrap(), nats(&[3, 1, 2]));
+    core.eval_source("0 #[1, 2] cons").unwrap();
+    assert_eq!(core.pop().unwrap(), nats(&[0, 1, 2]));
+    core.eval_source("#[1, 2] #[3] append reverse").unwrap();
+    assert_eq!(core.pop().unwrap(), nats(&[3, 2, 1]));
+    core.eval_source("#[3, 1, 2] sort").unwrap();
+    assert_eq!(core.pop().unwrap(), nats(&[1, 2, 3]));
+    core.eval_source("#[\"b\", \"a\"] sort length").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(2));
+    core.eval_source("#[1, 2, 3] #[4, 5] zip").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![nats(&[1, 4]), nats(&[2, 5])])
+    );
+
+    core.eval_source("#[7, 8, 9] head").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(7));
+    core.eval_source("#[7, 8, 9] tail").unwrap();
+    assert_eq!(core.pop().unwrap(), nats(&[8, 9]));
+    core.eval_source("#[7, 8, 9] 2 nth").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(9));
+
+    // The partial words fail where their `?` variants return None
+    core.eval_source("#[7, 8] head? #[7, 8] tail? #[7, 8] 1 nth?").unwrap();
+    assert_eq!(core.pop().unwrap(), some(Value::Nat(8)));
+    assert_eq!(core.pop().unwrap(), some(nats(&[8])));
+    assert_eq!(core.pop().unwrap(), some(Value::Nat(7)));
+    core.eval_source("#[] head? #[] tail? #[7] 1 nth?").unwrap();
+    for _ in 0..3 {
+        assert_eq!(core.pop().unwrap(), Value::Option(None));
+    }
+    assert!(core.eval_source("#[] head").is_err());
+    assert!(core.eval_source("#[] tail").is_err());
+    assert!(core.eval_source("#[7] 1 nth").is_err());
+    // Literals cannot mix types, but `cons` does not check its arguments
+    assert!(core.eval_source("#[1, \"a\"] sort").is_err());
+    assert!(core.eval_source("1 #[\"a\"] cons sort").is_err());
+    core.clear_stack();
+    // The whole list is checked, not only neighbouring elements
+    assert!(core.eval_source("#[1] \"a\" #[2] cons #[] cons cons sort").is_err());
+    core.clear_stack();
+    core.eval_source("#[#[2], #[], #[1, 3]] sort").unwrap();
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::List(vec![nats(&[]), nats(&[1, 3]), nats(&[2])])
+    );
+    core.clear_stack();
+
+    // List literals work in definitions and combine with iteration
+    core.eval_source(": total 0 [ + ] fold ; #[1, 2, 3] total").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(6));
+}
+
+#[test]
+fn test_composite_types() {
+    let mut core = ChronosCore::new();
+    core.eval_source("type Point { x: Nat, y: Nat }").unwrap();
+    assert!(core.is_word_defined("Point.x"));
+    assert!(core.is_word_defined("Point.y!"));
+    assert!(core.is_word_defined("Point?"));
+
+    core.eval_source("3 4 Point dup Point.y swap Point.x").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(3));
+    assert_eq!(core.pop().unwrap(), Value::Nat(4));
+
+    core.eval_source("3 4 Point 9 Point.x! Point.x").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(9));
+    core.eval_source("1 2 Point Point? 7 Point?").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Bool(false));
+    assert_eq!(core.pop().unwrap(), Value::Bool(true));
+    assert!(core.eval_source("7 Point.x").is_err());
+    core.clear_stack();
+
+    // Field values are checked against the declared field types
+    let error = core.eval_source("3 true Point").unwrap_err();
+    assert!(error.to_string().contains("Nat for 'Point'"));
+    core.clear_stack();
+    assert!(core.eval_source("3 4 Point true Point.y!").is_err());
+    core.clear_stack();
+
+    // Constructor patterns bind the fields in declaration order
+    core.eval_source(": sum-xy match [ ] | Point(x, y) -> x y + | _ -> 0 ; 5 6 Point sum-xy")
+        .unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(11));
+    core.eval_source(": point? match [ ] | Point -> true | _ -> false ; 1 2 Point point?")
+        .unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Bool(true));
+
+    // The generated words have signatures that typed definitions check against
+    assert_eq!(
+        core.word_signature("Point.x").unwrap().outputs,
+        vec![Type::Nat]
+    );
+    core.eval(":: norm ( Point -> Nat ) ; : norm dup Point.x swap Point.y + ;").unwrap();
+    core.eval("2 3 Point norm").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(5));
+    assert!(core.eval(":: bad ( Point -> Bool ) ; : bad Point.x ;").is_err());
+
+    // Both evaluation paths accept `field::Type` too
+    core.eval("type Goal { name::String, done::Bool }").unwrap();
+    core.eval("\"prove\" false Goal true Goal.done! Goal.done").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Bool(true));
+    assert_eq!(
+        core.get_type_definition("Goal").unwrap().fields,
+        vec![("name".to_string(), Type::String), ("done".to_string(), Type::Bool)]
+    );
+}
+
+#[test]
+fn test_data_types() {
+    let mut core = ChronosCore::new();
+    core.eval_source("data Shape = Circle Nat | Rect Nat Nat | Empty ;").unwrap();
+    assert_eq!(
+        core.word_signature("Rect").unwrap().to_string(),
+        "( Nat Nat -> Shape )"
+    );
+
+    core.eval_source("2 3 Rect Empty").unwrap();
+    assert_eq!(core.pop().unwrap().to_string(), "Empty");
+    assert_eq!(
+        core.pop().unwrap(),
+        Value::Variant {
+            type_name: "Shape".to_string(),
+            constructor: "Rect".to_string(),
+            args: vec![Value::Nat(2), Value::Nat(3)],
+        }
+    );
+
+    core.eval_source(
+        ": area match [ ] | Circle(r) -> r r * 3 * | Rect(w, h) -> w h * | Empty -> 0 ;",
+    )
+    .unwrap();
+    core.eval_source("2 Circle area 2 3 Rect area Empty area").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Nat(0));
+    assert_eq!(core.pop().unwrap(), Value::Nat(6));
+    assert_eq!(core.pop().unwrap(), Value::Nat(12));
+
+    // A match that leaves out constructors is rejected on both paths
+    let error = core
+        .eval_source(": sides match [ ] | Rect(w, h) -> 4 | Circle(_) -> 0 ;")
+        .unwrap_err();
+    assert!(error.to_string().contains("match over Shape has no arm for Empty"));
+    assert!(core.get_word_definition("sides").is_none());
+    assert!(core
+        .eval(": sides match [ ] | Rect(1, h) -> 4 | Circle(_) -> 0 | Empty -> 0 ;")
+        .is_err());
+    assert!(core.eval_source("Empty match [ ] | Empty -> 0").is_err());
+    assert!(core
+        .eval(": sides match [ ] | Rect(w, h) -> 4 | _ -> 0 ;")
+        .is_ok());
+
+    // Constructors are typed, so definitions using them are checked
+    core.eval("data Tree = Leaf | Node Tree Nat Tree ;").unwrap();
+    core.eval(":: single ( Nat -> Tree ) ; : single Leaf swap Leaf Node ;").unwrap();
+    core.eval("5 single").unwrap();
+    assert_eq!(core.pop().unwrap().to_string(), "Node(Leaf, 5, Leaf)");
+    assert!(core.eval(":: bad ( Nat -> Shape ) ; : bad Leaf swap Leaf Node ;").is_err());
+
+    // Constructor arguments are checked against their declared types
+    let error = core.eval_source("true Circle").unwrap_err();
+    assert!(error.to_string().contains("Nat for 'Circle'"));
+    core.clear_stack();
+
+    // Names already in use are rejected, but a type may be redeclared
+    assert!(core.eval_source("data T = Some Nat ;").is_err());
+    core.eval_source("3 Some").unwrap();
+    assert_eq!(core.pop().unwrap(), Value::Option(Some(Box::new(Value::Nat(3)))));
+    assert!(core.eval_source("data Nat = Zero | Succ Nat ;").is_err());
+    assert!(core.eval_source("data Other = Leaf ;").is_err());
+    assert!(core.eval("type Circle { r: Nat }").is_err());
+    core.eval(": Double 2 * ;").unwrap();
+    assert!(core.eval("data Twice = Double ;").is_err());
+    core.eval("data Shape = Circle Nat | Square Nat ;").unwrap();
+    assert!(core.is_word_defined("Square"));
+    assert!(!core.is_word_defined("Rect"));
+    assert!(core.eval("type Tree { size: Nat }").is_err());
+}
+
+#[test]
+fn test_axioms() {
+    let mut core = ChronosCore::new();
+
+    // Axioms are assumed on both paths and type check the words that use them
+    core.eval("axiom excluded-middle ( Bool -> Bool ) ;")
+        .unwrap();
+    core.eval_source(":: choice ( -> Nat ) ; axiom choice ;")
+        .unwrap();
+    assert_eq!(
+        core.axioms().get("choice").unwrap().to_string(),
+        "( -> Nat )"
+    );
+    core.eval(":: decide ( Bool -> Bool ) ; : decide excluded-middle not ;")
+        .unwrap();
+    core.eval_source(": pick choice 1 + ; : both decide pick ; : plain 1 2 + ;")
+        .unwrap();
+    assert!(core
+        .eval(":: wrong ( Nat -> Nat ) ; : wrong excluded-middle ;")
+        .is_err());
+
+    // Dependencies are transitive
+    let dependencies = core.axiom_dependencies();
+    let axioms = |word: &str| -> Vec<String> { dependencies[word].iter().cloned().collect() };
+    assert_eq!(axioms("decide"), vec!["excluded-middle"]);
+    assert_eq!(axioms("pick"), vec!["choice"]);
+    assert_eq!(axioms("both"), vec!["choice", "excluded-middle"]);
+    assert!(!dependencies.contains_key("plain"));
+
+    // Axioms cannot run, and defining the word retires the axiom
+    assert!(core.eval("true excluded-middle").is_err());
+    core.clear_stack();
+    core.eval(": choice 7 ;").unwrap();
+    assert!(core.axioms().get("choice").is_none());
+    assert!(!core.axiom_dependencies().contains_key("pick"));
+}
+#[test]
+fn test_theorems() {
+    let mut core = ChronosCore::new();
+
+    // Theorems are proved by rewriting on both paths
+    core.eval("theorem swap-twice: [ swap swap ] == [ ] ;")
+        .unwrap();
+    core.eval_source(": nip swap drop ; theorem dup-nip: [ dup nip ] == [ ] ;")
+        .unwrap();
+    let proof = &core.proofs()[1];
+    assert_eq!(proof.theorem.name, "dup-nip");
+    let rules: Vec<&str> = proof
+        .lhs_steps
+        .iter()
+        .map(|step| step.rule.as_str())
+        .collect();
+    assert_eq!(rules, vec!["definition of nip", "dup-swap", "dup-drop"]);
+    assert!(proof.normal_form().is_empty());
+
+    // False theorems are refuted with a stack the sides disagree on
+    let error = core.eval("theorem bad: [ swap ] == [ ] ;").unwrap_err();
+    assert!(error.to_string().contains("counterexample on the stack"));
+    assert!(core
+        .eval_source("lemma wrong: [ 1 + ] == [ 2 + ] ;")
+        .is_err());
+
+    // Lemmas are assumed, and later proofs rewrite with them
+    let unproved = core
+        .eval("theorem commutes: [ swap + ] == [ + ] ;")
+        .unwrap_err();
+    assert!(unproved.to_string().contains("no counterexample"));
+    core.eval("lemma commutes: [ swap + ] == [ + ] ;").unwrap();
+    core.eval("theorem rot-commutes: [ rot rot rot swap + ] == [ + ] ;")
+        .unwrap();
+    assert_eq!(core.lemmas().len(), 1);
+    assert_eq!(core.proofs().len(), 3);
+
+    // Checking a theorem leaves the stack alone
+    core.eval("1 2 theorem dup-drop: [ dup drop ] == [ ] ;")
+        .unwrap();
+    assert_eq!(core.get_stack(), vec![Value::Nat(1), Value::Nat(2)]);
+}
+#[test]
+fn test_check_word() {
+    let mut core = ChronosCore::new();
+    core.eval(":: double ( Nat -> Nat ) ; : double dup + ;")
+        .unwrap();
+    core.eval("test: doubles [ double ] [ 2 * ] equiv? assert ;")
+        .unwrap();
+    core.eval("test: triples [ double ] [ 3 * ] equiv? assert ;")
+        .unwrap();
+    core.eval("1 2").unwrap();
+
+    // The word passes its signature; the properties that use it are run
+    let report = core.check_word("double").unwrap();
+    assert!(report.failure.is_none());
+    assert_eq!(report.properties.len(), 2);
+    assert!(report.properties[0].passed());
+    assert!(!report.properties[1].passed());
+    let counterexample = report.properties[1].counterexample.as_ref().unwrap();
+    assert_eq!(counterexample.stack, vec![Value::Nat(1)]);
+    assert!(!report.passed());
+
+    // Failing inputs are shrunk to a minimal one
+    core.eval(":: inverse ( Nat -> Nat ) ; : inverse 100 swap / ;")
+        .unwrap();
+    let failure = core.check_word("inverse").unwrap().failure.unwrap();
+    assert_eq!(failure.inputs, vec![Value::Nat(0)]);
+    core.eval(":: grow ( Nat -> Nat ) ; : grow dup * dup * dup * dup * ;")
+        .unwrap();
+    // Only fixed-width naturals overflow
+    #[cfg(not(feature = "bigint"))]
+    {
+        let failure = core.check_word("grow").unwrap().failure.unwrap();
+        assert_eq!(failure.inputs, vec![Value::Nat(16)]);
+        assert!(failure.to_string().contains("overflow"));
+    }
+    #[cfg(feature = "bigint")]
+    assert!(core.check_word("grow").unwrap().failure.is_none());
+
+    // Polymorphic signatures and data types are generated too
+    core.eval(":: keep ( a b -> a ) ; : keep drop ;").unwrap();
+    assert!(core.check_word("keep").unwrap().passed());
+    core.eval("data Shape = Circle Nat | Rect Nat Nat ;")
+        .unwrap();
+    core.eval(
+        ":: area ( Shape -> Nat ) ; : area match [ ] | Circle(r) -> r r * | Rect(w, h) -> w h * ;",
+    )
+    .unwrap();
+    assert!(core.check_word("area").unwrap().passed());
+
+    // Checking leaves the stack alone
+    assert_eq!(core.get_stack(), vec![Value::Nat(1), Value::Nat(2)]);
+    assert!(core.check_word("missing").is_err());
+    assert!(core.check_word("if").is_err());
+}
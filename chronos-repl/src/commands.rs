@@ -21,6 +21,12 @@ pub enum ReplCommand {
     /// List all defined words
     Words,
 
+    /// List the axioms and the words that depend on them
+    Axioms,
+
+    /// List the proved theorems and assumed lemmas
+    Theorems,
+
     /// Clear the stack
     Clear,
 
@@ -36,6 +42,9 @@ pub enum ReplCommand {
     /// Load session from file
     Load(String),
 
+    /// Evaluate a C∀O source file
+    LoadFile(String),
+
     /// Show performance metrics
     Metrics,
 
@@ -48,6 +57,18 @@ pub enum ReplCommand {
     /// Benchmark code execution
     Benchmark(String),
 
+    /// Run code under the step debugger
+    Debug(String),
+
+    /// Profile code execution per word
+    Profile(String),
+
+    /// Check a word against its signature on random inputs
+    Check(String),
+
+    /// Export the last profile to a file
+    ProfileExport(String),
+
     /// Set configuration option
     Set(String, String),
 
@@ -115,6 +136,51 @@ pub enum ReplCommand {
     Unknown(String),
 }
 
+/// Names of the REPL commands, without the leading `.`, as offered by
+/// tab completion
+pub const COMMAND_NAMES: &[&str] = &[
+    "help",
+    "stack",
+    "words",
+    "axioms",
+    "theorems",
+    "clear",
+    "reset",
+    "quit",
+    "exit",
+    "about",
+    "metrics",
+    "history",
+    "trace",
+    "save",
+    "load",
+    "load-file",
+    "trace-show",
+    "benchmark",
+    "debug",
+    "profile",
+    "profile-export",
+    "check",
+    "set",
+    "colors",
+    "types",
+    "compact",
+    "syntax",
+    "unicode",
+    "ca-rule",
+    "ca-simple",
+    "ca",
+    "ca-rules",
+    "codd-simple",
+    "codd",
+    "codd-patterns",
+    "codd-enhanced",
+    "codd-enhanced-patterns",
+    "codd-enhanced-challenges",
+    "codd-enhanced-challenge",
+    "codd-enhanced-interactive",
+];
+
 /// Parse a command string into a ReplCommand
 pub fn parse_command(input: &str) -> ReplCommand {
     let input = input.trim();
@@ -132,6 +198,8 @@ pub fn parse_command(input: &str) -> ReplCommand {
         "help" | "h" => ReplCommand::Help,
         "stack" | "s" => ReplCommand::Stack,
         "words" | "w" => ReplCommand::Words,
+        "axioms" => ReplCommand::Axioms,
+        "theorems" => ReplCommand::Theorems,
         "clear" | "c" => ReplCommand::Clear,
         "reset" | "r" => ReplCommand::Reset,
         "quit" | "q" | "exit" => ReplCommand::Quit,
@@ -167,6 +235,14 @@ pub fn parse_command(input: &str) -> ReplCommand {
             }
         }
 
+        "load-file" => {
+            if parts.len() > 1 {
+                ReplCommand::LoadFile(parts[1..].join(" "))
+            } else {
+                ReplCommand::Unknown(input.to_string())
+            }
+        }
+
         "trace-show" | "showtrace" => {
             if parts.len() > 1 {
                 if let Ok(count) = parts[1].parse::<usize>() {
@@ -187,6 +263,38 @@ pub fn parse_command(input: &str) -> ReplCommand {
             }
         }
 
+        "debug" | "dbg" => {
+            if parts.len() > 1 {
+                ReplCommand::Debug(parts[1..].join(" "))
+            } else {
+                ReplCommand::Unknown(input.to_string())
+            }
+        }
+
+        "profile" | "prof" => {
+            if parts.len() > 1 {
+                ReplCommand::Profile(parts[1..].join(" "))
+            } else {
+                ReplCommand::Unknown(input.to_string())
+            }
+        }
+
+        "check" => {
+            if parts.len() > 1 {
+                ReplCommand::Check(parts[1].to_string())
+            } else {
+                ReplCommand::Unknown(input.to_string())
+            }
+        }
+
+        "profile-export" => {
+            if parts.len() > 1 {
+                ReplCommand::ProfileExport(parts[1..].join(" "))
+            } else {
+                ReplCommand::Unknown(input.to_string())
+            }
+        }
+
         "set" => {
             if parts.len() >= 3 {
                 ReplCommand::Set(parts[1].to_string(), parts[2..].join(" "))
@@ -522,6 +630,16 @@ pub fn execute_command(
             Ok(format!("Benchmark for '{}' not yet implemented", code))
         }
 
+        ReplCommand::Axioms
+        | ReplCommand::Theorems
+        | ReplCommand::LoadFile(_)
+        | ReplCommand::Debug(_)
+        | ReplCommand::Profile(_)
+        | ReplCommand::ProfileExport(_)
+        | ReplCommand::Check(_) => Err(ReplError::command(
+            "This command needs the REPL's virtual machine",
+        )),
+
         ReplCommand::Set(key, value) => match key.as_str() {
             "trace" => match value.as_str() {
                 "true" | "on" | "1" => {
@@ -707,6 +825,8 @@ Basic Commands:
   .help, .h          - Show this help message
   .stack, .s         - Show current stack contents
   .words, .w         - List all defined words
+  .axioms            - List axioms and the words that use them
+  .theorems          - List proved theorems and assumed lemmas
   .clear, .c         - Clear the stack
   .reset, .r         - Reset the REPL state
   .quit, .q          - Exit the REPL
@@ -714,6 +834,7 @@ Basic Commands:
 Session Management:
   .save [file]       - Save current session (default: session.json)
   .load <file>       - Load a session from file
+  .load-file <file>  - Evaluate a .cao source file
 
 Debugging & Analysis:
   .trace, .t         - Toggle execution tracing
@@ -721,6 +842,10 @@ Debugging & Analysis:
   .metrics, .m       - Show performance metrics
   .history           - Show command history
   .showtrace [n]     - Show last n trace entries (default: 10)
+  .debug <code>      - Step through code with breakpoints
+  .profile <code>    - Time each word, nested through the call stack
+  .profile-export <file> - Save the last profile (.json, else folded stacks)
+  .check <word>      - Run a word on random inputs against its signature
 
 Display Options:
   .colors [on/off]   - Toggle colored output
@@ -802,6 +927,10 @@ mod tests {
             parse_command(".load test.json"),
             ReplCommand::Load("test.json".to_string())
         );
+        assert_eq!(
+            parse_command(".load-file lib/math.cao"),
+            ReplCommand::LoadFile("lib/math.cao".to_string())
+        );
     }
 
     #[test]
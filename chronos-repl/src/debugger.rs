@@ -0,0 +1,239 @@
+//! Interactive step debugging for the REPL
+//!
+//! `.debug <code>` runs code with a [`Debugger`](chronos_core::Debugger)
+//! attached to the virtual machine. Each pause shows the next token, the call
+//! stack and the data stack, then reads debugger commands until one resumes
+//! execution.
+
+use chronos_core::{Breakpoint, DebugAction, DebugHandler, Pause, PauseReason, Value};
+use std::io::{self, BufRead, Write};
+
+/// Help shown by the `help` debugger command
+const DEBUGGER_HELP: &str = "\
+Debugger commands:
+  s, step            - Step into the next token
+  n, next            - Step over the next word or quote
+  f, finish          - Run until the current word returns
+  c, continue        - Run until a breakpoint
+  q, quit            - Abort execution
+  b, break <spec>    - Break on a word, `stack > n` or `calls >= n`
+  d, delete <spec>   - Remove a breakpoint
+  bl, breakpoints    - List breakpoints
+  bt, where          - Show the call stack
+  st, stack          - Show the data stack
+  <enter>            - Repeat the last step command";
+
+/// A debug handler that talks to the user through a reader and a writer
+pub struct ConsoleDebugger<R, W> {
+    input: R,
+    output: W,
+    last_action: DebugAction,
+}
+
+impl ConsoleDebugger<io::BufReader<io::Stdin>, io::Stdout> {
+    /// Create a debugger on standard input and output
+    pub fn stdio() -> Self {
+        // Standard input is already buffered; a second buffer here would
+        // swallow lines meant for the REPL once debugging ends
+        Self::new(io::BufReader::with_capacity(1, io::stdin()), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> ConsoleDebugger<R, W> {
+    /// Create a debugger reading commands from `input`
+    pub fn new(input: R, output: W) -> Self {
+        Self {
+            input,
+            output,
+            last_action: DebugAction::StepInto,
+        }
+    }
+
+    /// Show where execution paused
+    fn show_pause(&mut self, pause: &Pause<'_>) -> io::Result<()> {
+        match &pause.reason {
+            PauseReason::Step => write!(self.output, "paused")?,
+            PauseReason::Breakpoint(breakpoint) => {
+                write!(self.output, "breakpoint '{}' hit", breakpoint)?
+            }
+        }
+        writeln!(self.output, " before {}", pause.token)?;
+        self.show_call_stack(pause.call_stack)?;
+        self.show_stack(pause.stack)
+    }
+
+    fn show_call_stack(&mut self, call_stack: &[String]) -> io::Result<()> {
+        if call_stack.is_empty() {
+            writeln!(self.output, "  in: <top level>")
+        } else {
+            writeln!(self.output, "  in: {}", call_stack.join(" > "))
+        }
+    }
+
+    fn show_stack(&mut self, stack: &[Value]) -> io::Result<()> {
+        let values: Vec<String> = stack.iter().map(|value| value.to_string()).collect();
+        writeln!(self.output, "  stack: [{}]", values.join(" "))
+    }
+
+    /// Read commands until one resumes execution
+    fn prompt(
+        &mut self,
+        pause: &Pause<'_>,
+        breakpoints: &mut Vec<Breakpoint>,
+    ) -> io::Result<DebugAction> {
+        self.show_pause(pause)?;
+
+        loop {
+            write!(self.output, "debug> ")?;
+            self.output.flush()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                // Nobody left to ask, so run to completion
+                return Ok(DebugAction::Continue);
+            }
+
+            let line = line.trim();
+            let (command, argument) = match line.split_once(char::is_whitespace) {
+                Some((command, argument)) => (command, argument.trim()),
+                None => (line, ""),
+            };
+
+            let action = match command {
+                "" => self.last_action,
+                "s" | "step" => DebugAction::StepInto,
+                "n" | "next" => DebugAction::StepOver,
+                "f" | "finish" => DebugAction::Finish,
+                "c" | "continue" => DebugAction::Continue,
+                "q" | "quit" => return Ok(DebugAction::Abort),
+                "b" | "break" => {
+                    match argument.parse::<Breakpoint>() {
+                        Ok(breakpoint) if breakpoints.contains(&breakpoint) => {
+                            writeln!(self.output, "Breakpoint '{}' already set", breakpoint)?
+                        }
+                        Ok(breakpoint) => {
+                            writeln!(self.output, "Breakpoint '{}' set", breakpoint)?;
+                            breakpoints.push(breakpoint);
+                        }
+                        Err(e) => writeln!(self.output, "{}", e)?,
+                    }
+                    continue;
+                }
+                "d" | "delete" => {
+                    match argument.parse::<Breakpoint>() {
+                        Ok(breakpoint) if breakpoints.contains(&breakpoint) => {
+                            breakpoints.retain(|existing| existing != &breakpoint);
+                            writeln!(self.output, "Breakpoint '{}' removed", breakpoint)?;
+                        }
+                        Ok(breakpoint) => writeln!(self.output, "No breakpoint '{}'", breakpoint)?,
+                        Err(e) => writeln!(self.output, "{}", e)?,
+                    }
+                    continue;
+                }
+                "bl" | "breakpoints" => {
+                    if breakpoints.is_empty() {
+                        writeln!(self.output, "No breakpoints")?;
+                    }
+                    for breakpoint in breakpoints.iter() {
+                        writeln!(self.output, "  {}", breakpoint)?;
+                    }
+                    continue;
+                }
+                "bt" | "where" => {
+                    self.show_call_stack(pause.call_stack)?;
+                    continue;
+                }
+                "st" | "stack" => {
+                    self.show_stack(pause.stack)?;
+                    continue;
+                }
+                "h" | "help" => {
+                    writeln!(self.output, "{}", DEBUGGER_HELP)?;
+                    continue;
+                }
+                _ => {
+                    writeln!(
+                        self.output,
+                        "Unknown debugger command: {} (try 'help')",
+                        command
+                    )?;
+                    continue;
+                }
+            };
+
+            self.last_action = action;
+            return Ok(action);
+        }
+    }
+}
+
+impl<R: BufRead + Send, W: Write + Send> DebugHandler for ConsoleDebugger<R, W> {
+    fn pause(&mut self, pause: &Pause<'_>, breakpoints: &mut Vec<Breakpoint>) -> DebugAction {
+        // A broken terminal cannot answer, so stop rather than run unattended
+        self.prompt(pause, breakpoints)
+            .unwrap_or(DebugAction::Abort)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chronos_core::{ChronosCore, Debugger};
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    /// Output that the test can read after the debugger is dropped
+    #[derive(Clone, Default)]
+    struct Transcript(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Transcript {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn debug(code: &str, commands: &str) -> (ChronosCore, String) {
+        let mut core = ChronosCore::new();
+        core.eval_source(": square dup * ; : quad square square ;")
+            .unwrap();
+
+        let transcript = Transcript::default();
+        let handler = ConsoleDebugger::new(Cursor::new(commands.to_string()), transcript.clone());
+        core.set_debugger(Some(Debugger::new(Box::new(handler))));
+        let _ = core.eval_source(code);
+        core.set_debugger(None);
+
+        let output = String::from_utf8(transcript.0.lock().unwrap().clone()).unwrap();
+        (core, output)
+    }
+
+    #[test]
+    fn test_steps_through_nested_words() {
+        let (core, output) = debug("3 quad", "s\n\n\n\nbt\nc\n");
+        assert!(output.contains("paused before 3\n  in: <top level>\n  stack: []"));
+        assert!(output.contains("paused before dup\n  in: quad > square\n  stack: [3]"));
+        assert!(output.contains("  stack: [3 3]\ndebug>   in: quad > square"));
+        assert_eq!(core.get_stack(), vec![Value::Nat(81)]);
+    }
+
+    #[test]
+    fn test_breakpoints_from_the_prompt() {
+        let (_, output) = debug("2 quad", "b *\nbl\nc\nst\nd *\nc\n");
+        assert!(output.contains("Breakpoint '*' set"));
+        assert!(output.contains("breakpoint '*' hit before *\n  in: quad > square\n  stack: [2 2]"));
+        assert!(output.contains("  stack: [2 2]\ndebug>   stack: [2 2]"));
+        assert!(output.contains("Breakpoint '*' removed"));
+        assert_eq!(output.matches("hit before").count(), 1);
+    }
+
+    #[test]
+    fn test_quit_aborts_execution() {
+        let (core, output) = debug("1 2 +", "n\nq\n");
+        assert!(output.contains("paused before 2"));
+        assert_eq!(core.get_stack(), vec![Value::Nat(1)]);
+    }
+}
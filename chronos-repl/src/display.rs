@@ -3,9 +3,10 @@
 //! This module provides rich visual formatting capabilities for both human and machine consumption,
 //! including syntax highlighting, colorized output, and structured formatting.
 
-use chronos_core::Value;
+use chronos_core::{AxiomRegistry, Proof, Theorem, Value};
 use console::{style, Color};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
 /// Configuration for display formatting
@@ -182,11 +183,15 @@ fn format_value_colored(value: &Value, config: &DisplayConfig, scheme: &ColorSch
     match value {
         Value::Unit => display_str,
         Value::Nat(_) => style(display_str).fg(scheme.number).to_string(),
+        #[cfg(feature = "bigint")]
+        Value::BigNat(_) => style(display_str).fg(scheme.number).to_string(),
         Value::Bool(_) => style(display_str).fg(scheme.keyword).to_string(),
         Value::String(_) => style(display_str).fg(scheme.string).to_string(),
         Value::Quote(_) => style(display_str).fg(scheme.string).to_string(),
         Value::Ordinal(_) => style(display_str).fg(scheme.number).to_string(),
-        Value::Composite { .. } => style(display_str).fg(scheme.type_info).to_string(),
+        Value::Composite { .. } | Value::Variant { .. } => {
+            style(display_str).fg(scheme.type_info).to_string()
+        }
         Value::Option(_) => style(display_str).fg(scheme.type_info).to_string(),
         Value::Result(_) => style(display_str).fg(scheme.type_info).to_string(),
         Value::List(_) => style(display_str).fg(scheme.string).to_string(),
@@ -199,10 +204,12 @@ fn get_value_type_string(value: &Value) -> String {
         Value::Unit => "Unit".to_string(),
         Value::Bool(_) => "Bool".to_string(),
         Value::Nat(_) => "Nat".to_string(),
+        #[cfg(feature = "bigint")]
+        Value::BigNat(_) => "Nat".to_string(),
         Value::String(_) => "String".to_string(),
         Value::Quote(_) => "Quote".to_string(),
         Value::Ordinal(_) => "Ordinal".to_string(),
-        Value::Composite { type_name, .. } => type_name.clone(),
+        Value::Composite { type_name, .. } | Value::Variant { type_name, .. } => type_name.clone(),
         Value::Option(_) => "Option".to_string(),
         Value::Result(_) => "Result".to_string(),
         Value::List(_) => "List".to_string(),
@@ -403,6 +410,88 @@ pub fn format_word_list(words: &[String], config: &DisplayConfig) -> String {
     result
 }
 
+/// Format the declared axioms and the user words that rely on them
+pub fn format_axioms(
+    axioms: &AxiomRegistry,
+    dependencies: &BTreeMap<String, BTreeSet<String>>,
+    config: &DisplayConfig,
+) -> String {
+    let scheme = ColorScheme::default();
+    let paint = |text: &str, color: Color| {
+        if config.use_colors {
+            style(text).fg(color).to_string()
+        } else {
+            text.to_string()
+        }
+    };
+
+    if axioms.is_empty() {
+        return paint("No axioms declared", scheme.stack_index);
+    }
+
+    let mut result = paint("Axioms:", scheme.info);
+    for (name, signature) in axioms.iter() {
+        result.push_str(&format!(
+            "\n  {} {}",
+            paint(name, scheme.keyword),
+            paint(&signature.to_string(), scheme.type_info)
+        ));
+    }
+
+    if dependencies.is_empty() {
+        result.push_str(&format!(
+            "\n{}",
+            paint("No words depend on them", scheme.stack_index)
+        ));
+    } else {
+        result.push_str(&format!("\n{}", paint("Depending words:", scheme.info)));
+        for (word, used) in dependencies {
+            let used: Vec<&str> = used.iter().map(String::as_str).collect();
+            result.push_str(&format!("\n  {}: {}", word, used.join(", ")));
+        }
+    }
+
+    result
+}
+
+/// Format the proved theorems and the assumed lemmas
+pub fn format_theorems(proofs: &[Proof], lemmas: &[Theorem], config: &DisplayConfig) -> String {
+    let scheme = ColorScheme::default();
+    let paint = |text: &str, color: Color| {
+        if config.use_colors {
+            style(text).fg(color).to_string()
+        } else {
+            text.to_string()
+        }
+    };
+
+    if proofs.is_empty() && lemmas.is_empty() {
+        return paint("No theorems proved", scheme.stack_index);
+    }
+
+    let mut sections = Vec::new();
+    for (title, theorems) in [
+        (
+            "Theorems:",
+            proofs
+                .iter()
+                .map(|proof| &proof.theorem)
+                .collect::<Vec<_>>(),
+        ),
+        ("Lemmas:", lemmas.iter().collect()),
+    ] {
+        if theorems.is_empty() {
+            continue;
+        }
+        let mut section = paint(title, scheme.info);
+        for theorem in theorems {
+            section.push_str(&format!("\n  {}", theorem));
+        }
+        sections.push(section);
+    }
+    sections.join("\n")
+}
+
 /// Format error messages with enhanced styling
 pub fn format_error(message: &str, config: &DisplayConfig) -> String {
     let scheme = ColorScheme::default();
@@ -477,6 +566,8 @@ pub fn format_help(config: &DisplayConfig) -> String {
                 (".help", "Show this help message"),
                 (".stack", "Show current stack contents"),
                 (".words", "List all defined words"),
+                (".axioms", "List axioms and the words that use them"),
+                (".theorems", "List proved theorems and assumed lemmas"),
                 (".clear", "Clear the stack"),
                 (".reset", "Reset the REPL state"),
                 (".quit", "Exit the REPL"),
@@ -487,6 +578,7 @@ pub fn format_help(config: &DisplayConfig) -> String {
             vec![
                 (".save [file]", "Save current session"),
                 (".load <file>", "Load a session"),
+                (".load-file <file>", "Evaluate a .cao source file"),
                 (".history", "Show command history"),
             ],
         ),
@@ -496,6 +588,10 @@ pub fn format_help(config: &DisplayConfig) -> String {
                 (".trace", "Toggle execution tracing"),
                 (".metrics", "Show performance metrics"),
                 (".showtrace [n]", "Show last n trace entries"),
+                (".debug <code>", "Step through code with breakpoints"),
+                (".profile <code>", "Time each word through the call stack"),
+                (".profile-export <file>", "Save the last profile"),
+                (".check <word>", "Test a word on random inputs"),
             ],
         ),
         (
@@ -683,6 +779,42 @@ mod tests {
         assert!(formatted.contains("μs") || formatted.contains("ns"));
     }
 
+    #[test]
+    fn test_axiom_formatting() {
+        let config = DisplayConfig {
+            use_colors: false,
+            ..DisplayConfig::default()
+        };
+        let mut core = chronos_core::ChronosCore::new();
+        assert!(
+            format_axioms(core.axioms(), &core.axiom_dependencies(), &config).contains("No axioms")
+        );
+
+        core.eval("axiom lem ( Bool -> Bool ) ; : decide lem not ;")
+            .unwrap();
+        let formatted = format_axioms(core.axioms(), &core.axiom_dependencies(), &config);
+        assert!(formatted.contains("lem ( Bool -> Bool )"));
+        assert!(formatted.contains("decide: lem"));
+    }
+
+    #[test]
+    fn test_theorem_formatting() {
+        let config = DisplayConfig {
+            use_colors: false,
+            ..DisplayConfig::default()
+        };
+        let mut core = chronos_core::ChronosCore::new();
+        assert!(format_theorems(core.proofs(), core.lemmas(), &config).contains("No theorems"));
+
+        core.eval(
+            "theorem swap-twice: [ swap swap ] == [ ] ; lemma commutes: [ swap + ] == [ + ] ;",
+        )
+        .unwrap();
+        let formatted = format_theorems(core.proofs(), core.lemmas(), &config);
+        assert!(formatted.contains("theorem swap-twice: [ swap swap ] == [ ]"));
+        assert!(formatted.contains("lemma commutes: [ swap + ] == [ + ]"));
+    }
+
     #[test]
     fn test_legacy_format_stack() {
         let stack = vec![Value::Nat(42), Value::Bool(true), Value::Nat(7)];
@@ -0,0 +1,314 @@
+//! Line editing for the interactive REPL
+//!
+//! This module wraps `rustyline` to give the REPL arrow-key editing, history
+//! with reverse search, and tab completion for dictionary words, `.commands`
+//! and session files. Input keeps reading continuation lines while a `:`
+//! definition or a `[` quotation is still open.
+
+use crate::commands::COMMAND_NAMES;
+use crate::error::{ReplError, Result};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Editor, Helper};
+use std::fs;
+use std::path::PathBuf;
+
+/// Commands whose argument is a session file
+const SESSION_COMMANDS: &[&str] = &[".save", ".load"];
+
+/// Completion and validation for the REPL line editor
+#[derive(Debug, Clone)]
+pub struct ReplHelper {
+    /// Words in the dictionary, built-in and user-defined
+    words: Vec<String>,
+
+    /// Directory searched for session files
+    session_dir: PathBuf,
+}
+
+impl ReplHelper {
+    /// Create a helper that completes session files in the current directory
+    pub fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            session_dir: PathBuf::from("."),
+        }
+    }
+
+    /// Replace the dictionary words offered for completion
+    pub fn set_words(&mut self, mut words: Vec<String>) {
+        words.sort();
+        words.dedup();
+        self.words = words;
+    }
+
+    /// Set the directory searched for session files
+    pub fn set_session_dir<P: Into<PathBuf>>(&mut self, dir: P) {
+        self.session_dir = dir.into();
+    }
+
+    /// Completion candidates for the word ending at `pos`, with the position
+    /// the word starts at
+    pub fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .rfind(|c: char| c.is_whitespace() || c == '[' || c == ']')
+            .map_or(0, |i| i + 1);
+        let prefix = &before[start..];
+        let preceding: Vec<&str> = before[..start].split_whitespace().collect();
+
+        let matches = match preceding.as_slice() {
+            [] if prefix.starts_with('.') => COMMAND_NAMES
+                .iter()
+                .map(|name| format!(".{}", name))
+                .filter(|command| command.starts_with(prefix))
+                .collect(),
+            [command] if SESSION_COMMANDS.contains(command) => self.session_names(prefix),
+            _ => self
+                .words
+                .iter()
+                .filter(|word| word.starts_with(prefix))
+                .cloned()
+                .collect(),
+        };
+
+        (start, matches)
+    }
+
+    /// Session files in the session directory starting with `prefix`
+    fn session_names(&self, prefix: &str) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.session_dir) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with(prefix))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+impl Default for ReplHelper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, matches) = self.candidates(line, pos);
+        let pairs = matches
+            .into_iter()
+            .map(|candidate| Pair {
+                display: candidate.clone(),
+                replacement: candidate,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        if is_incomplete(ctx.input()) {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+}
+
+impl Helper for ReplHelper {}
+
+/// Check whether `input` ends inside a `:` definition or a `[` quotation
+///
+/// Strings and comments are skipped, and `.commands` are always complete.
+pub fn is_incomplete(input: &str) -> bool {
+    if input.trim_start().starts_with('.') {
+        return false;
+    }
+
+    let mut in_definition = false;
+    let mut quote_depth = 0usize;
+    let mut comment_depth = 0usize;
+    let mut chars = input.chars().peekable();
+    let mut previous = ' ';
+
+    while let Some(ch) = chars.next() {
+        if comment_depth > 0 {
+            match ch {
+                '(' => comment_depth += 1,
+                ')' => comment_depth -= 1,
+                _ => {}
+            }
+        } else {
+            match ch {
+                '"' => {
+                    let mut escaped = false;
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        match c {
+                            '\\' if !escaped => escaped = true,
+                            '"' if !escaped => {
+                                closed = true;
+                                break;
+                            }
+                            _ => escaped = false,
+                        }
+                    }
+                    if !closed {
+                        return true;
+                    }
+                }
+                '(' => comment_depth += 1,
+                '[' => quote_depth += 1,
+                ']' => quote_depth = quote_depth.saturating_sub(1),
+                ';' => in_definition = false,
+                ':' if previous.is_whitespace()
+                    && chars.peek().is_none_or(|&c| c.is_whitespace() || c == ':') =>
+                {
+                    in_definition = true;
+                }
+                _ => {}
+            }
+        }
+        previous = ch;
+    }
+
+    in_definition || quote_depth > 0 || comment_depth > 0
+}
+
+/// A `rustyline` editor configured for the REPL
+pub struct LineEditor {
+    editor: Editor<ReplHelper, DefaultHistory>,
+}
+
+impl LineEditor {
+    /// Create a line editor on the terminal
+    pub fn new() -> Result<Self> {
+        let mut editor = Editor::new().map_err(|e| ReplError::io(e.to_string()))?;
+        editor.set_helper(Some(ReplHelper::new()));
+        Ok(Self { editor })
+    }
+
+    /// Replace the editor history, oldest entry first
+    pub fn load_history(&mut self, entries: &[String]) -> Result<()> {
+        self.editor
+            .clear_history()
+            .map_err(|e| ReplError::io(e.to_string()))?;
+        for entry in entries {
+            self.add_history(entry)?;
+        }
+        Ok(())
+    }
+
+    /// Add an entry to the editor history
+    pub fn add_history(&mut self, entry: &str) -> Result<()> {
+        self.editor
+            .add_history_entry(entry)
+            .map_err(|e| ReplError::io(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Replace the dictionary words offered for completion
+    pub fn set_words(&mut self, words: Vec<String>) {
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.set_words(words);
+        }
+    }
+
+    /// Read one complete input, which may span several lines
+    ///
+    /// Returns `None` at end of input. Interrupting with Ctrl-C discards the
+    /// current input and returns an empty string.
+    pub fn read_input(&mut self, prompt: &str) -> Result<Option<String>> {
+        match self.editor.readline(prompt) {
+            Ok(line) => Ok(Some(line)),
+            Err(ReadlineError::Interrupted) => Ok(Some(String::new())),
+            Err(ReadlineError::Eof) => Ok(None),
+            Err(e) => Err(ReplError::io(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_definitions_and_quotations_continue() {
+        assert!(is_incomplete(": square"));
+        assert!(is_incomplete(": square dup\n"));
+        assert!(!is_incomplete(": square dup * ;"));
+        assert!(is_incomplete(":: square ( Nat"));
+        assert!(!is_incomplete(":: square ( Nat -> Nat ) ;"));
+        assert!(is_incomplete("[ 1 2"));
+        assert!(is_incomplete("[ 1 [ 2 ]"));
+        assert!(!is_incomplete("[ 1 [ 2 ] ] call"));
+        assert!(!is_incomplete("2 3 +"));
+    }
+
+    #[test]
+    fn test_strings_comments_and_commands_do_not_continue() {
+        assert!(!is_incomplete("\"a [ in : a string\" print"));
+        assert!(!is_incomplete("( a [ comment ) 1"));
+        assert!(is_incomplete("\"unterminated"));
+        assert!(!is_incomplete(".save session.json"));
+    }
+
+    #[test]
+    fn test_completes_words_and_commands() {
+        let mut helper = ReplHelper::new();
+        helper.set_words(vec!["dup".into(), "drop".into(), "swap".into()]);
+
+        assert_eq!(
+            helper.candidates("1 d", 3),
+            (2, vec!["drop".into(), "dup".into()])
+        );
+        assert_eq!(helper.candidates("[ sw", 4), (2, vec!["swap".into()]));
+
+        let (start, commands) = helper.candidates(".sta", 4);
+        assert_eq!(start, 0);
+        assert_eq!(commands, vec![".stack".to_string()]);
+    }
+
+    #[test]
+    fn test_completes_session_names() {
+        let dir = tempfile::tempdir().unwrap();
+        File::create(dir.path().join("proofs.json")).unwrap();
+        File::create(dir.path().join("notes.txt")).unwrap();
+
+        let mut helper = ReplHelper::new();
+        helper.set_session_dir(dir.path());
+        assert_eq!(
+            helper.candidates(".load pr", 8),
+            (6, vec!["proofs.json".into()])
+        );
+        assert_eq!(
+            helper.candidates(".save ", 6),
+            (6, vec!["proofs.json".into()])
+        );
+    }
+}
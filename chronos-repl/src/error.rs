@@ -16,6 +16,12 @@ pub enum ReplError {
     /// Error from the core Chronos language system
     Core(ChronosError),
 
+    /// Core language error raised while evaluating a known piece of source
+    Source {
+        error: Box<ChronosError>,
+        source: String,
+    },
+
     /// Session management error
     Session(SessionError),
 
@@ -55,12 +61,18 @@ pub enum SessionError {
 
     /// Session corruption detected
     Corrupted(String),
+
+    /// Saved words that are already defined differently
+    WordConflict(Vec<String>),
 }
 
 impl fmt::Display for ReplError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ReplError::Core(e) => write!(f, "Core language error: {}", e),
+            ReplError::Source { error, source } => {
+                write!(f, "Core language error: {}", error.render(source))
+            }
             ReplError::Session(e) => write!(f, "Session error: {}", e),
             ReplError::Io(msg) => write!(f, "I/O error: {}", msg),
             ReplError::Command(msg) => write!(f, "Command error: {}", msg),
@@ -86,6 +98,9 @@ impl fmt::Display for SessionError {
             }
             SessionError::MissingData(field) => write!(f, "Missing session data: {}", field),
             SessionError::Corrupted(msg) => write!(f, "Session corrupted: {}", msg),
+            SessionError::WordConflict(words) => {
+                write!(f, "Session redefines existing words: {}", words.join(", "))
+            }
         }
     }
 }
@@ -94,6 +109,7 @@ impl std::error::Error for ReplError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             ReplError::Core(e) => Some(e),
+            ReplError::Source { error, .. } => Some(error.as_ref()),
             _ => None,
         }
     }
@@ -136,6 +152,16 @@ impl From<serde_json::Error> for SessionError {
 // Helper functions for creating specific errors
 
 impl ReplError {
+    /// Wrap a core error raised while evaluating `source`
+    ///
+    /// The error is displayed with the offending source line underlined.
+    pub fn in_source<S: Into<String>>(error: ChronosError, source: S) -> Self {
+        ReplError::Source {
+            error: Box::new(error),
+            source: source.into(),
+        }
+    }
+
     /// Create a command error
     pub fn command<S: Into<String>>(msg: S) -> Self {
         ReplError::Command(msg.into())
@@ -190,11 +216,21 @@ impl SessionError {
     pub fn corrupted<S: Into<String>>(msg: S) -> Self {
         SessionError::Corrupted(msg.into())
     }
+
+    /// Create a word conflict error
+    pub fn word_conflict<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        SessionError::WordConflict(words.into_iter().map(Into::into).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chronos_core::ChronosCore;
 
     #[test]
     fn test_error_display() {
@@ -224,6 +260,17 @@ mod tests {
         matches!(repl_error, ReplError::Session(_));
     }
 
+    #[test]
+    fn test_source_error_display() {
+        let source = "1 2 ] +";
+        let core_error = ChronosCore::new().eval_source(source).unwrap_err();
+        let error = ReplError::in_source(core_error, source);
+
+        let message = error.to_string();
+        assert!(message.contains("at line 1, column 5"));
+        assert!(message.ends_with("1 | 1 2 ] +\n  |     ^"));
+    }
+
     #[test]
     fn test_error_creation_helpers() {
         let error = ReplError::command("test");
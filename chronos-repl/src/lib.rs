@@ -63,8 +63,11 @@ pub mod cellular_automata;
 pub mod codd_ca;
 pub mod codd_game;
 pub mod commands;
+pub mod debugger;
 pub mod display;
+pub mod editor;
 pub mod error;
+pub mod profiler;
 pub mod repl;
 pub mod session;
 pub mod tracing;
@@ -76,7 +79,10 @@ pub use chronos_core::{ChronosCore, ChronosError, Token, Value};
 pub use cellular_automata::{CAEnvironment, ElementaryCA, ElementaryRule};
 pub use codd_ca::{CoddCA, CoddEnvironment, CoddPatternType, CoddState};
 pub use codd_game::{Challenge, CoddGameEnvironment, DrawTool, GameMode, GameStats, Pattern};
+pub use debugger::ConsoleDebugger;
+pub use editor::{LineEditor, ReplHelper};
 pub use error::{ReplError, Result};
+pub use profiler::{ProfileReport, Profiler};
 pub use repl::EnhancedRepl;
 pub use session::{Session, SessionData};
 pub use tracing::{ExecutionTrace, TraceEntry};
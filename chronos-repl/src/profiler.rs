@@ -0,0 +1,315 @@
+//! Hierarchical profiling for the Chronos REPL
+//!
+//! The [`Profiler`] is a [`VmObserver`] that times every word the virtual
+//! machine enters, nested through the call stack. Each word gets a call
+//! count, its inclusive time (including the words it calls) and its exclusive
+//! time (its own body only). Time is also recorded per call path, which is
+//! exported in the folded-stack format read by flame graph tools.
+
+use crate::display::{format_duration, DisplayConfig};
+use chronos_core::{ExecutionContext, VmObserver};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Timing for one word across a profiling run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordProfile {
+    /// The word
+    pub name: String,
+
+    /// Number of times the word was entered
+    pub calls: u64,
+
+    /// Time spent in the word and everything it called, in nanoseconds;
+    /// recursive calls are only counted once
+    pub inclusive_ns: u64,
+
+    /// Time spent in the word's own body, in nanoseconds
+    pub exclusive_ns: u64,
+}
+
+/// Exclusive time spent along one call path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FoldedStack {
+    /// Words from outermost to innermost, separated by `;`
+    pub stack: String,
+
+    /// Exclusive time of the innermost word on this path, in nanoseconds
+    pub exclusive_ns: u64,
+}
+
+/// The result of a profiling run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileReport {
+    /// Wall-clock time of the whole run, in nanoseconds
+    pub total_ns: u64,
+
+    /// Per-word timing, slowest inclusive time first
+    pub words: Vec<WordProfile>,
+
+    /// Per-path timing, in path order
+    pub stacks: Vec<FoldedStack>,
+}
+
+impl ProfileReport {
+    /// Keep only the words, and the paths ending in words, that satisfy `keep`
+    pub fn retain_words<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        self.words.retain(|word| keep(&word.name));
+        self.stacks
+            .retain(|stack| stack.stack.rsplit(';').next().is_some_and(&keep));
+    }
+
+    /// The report in folded-stack format, one `path count` line per call
+    /// path with the count in nanoseconds
+    pub fn to_folded(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| format!("{} {}\n", stack.stack, stack.exclusive_ns))
+            .collect()
+    }
+
+    /// The report as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Profile ({} total)", format_ns(self.total_ns))?;
+        if self.words.is_empty() {
+            return write!(f, "  No words were called");
+        }
+
+        let width = self
+            .words
+            .iter()
+            .map(|word| word.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("word".len());
+        write!(
+            f,
+            "  {:<width$}  {:>8}  {:>10}  {:>10}",
+            "word", "calls", "inclusive", "exclusive"
+        )?;
+        for word in &self.words {
+            write!(
+                f,
+                "\n  {:<width$}  {:>8}  {:>10}  {:>10}",
+                word.name,
+                word.calls,
+                format_ns(word.inclusive_ns),
+                format_ns(word.exclusive_ns)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a nanosecond count with a readable unit
+fn format_ns(nanos: u64) -> String {
+    let plain = DisplayConfig {
+        use_colors: false,
+        ..DisplayConfig::default()
+    };
+    format_duration(Duration::from_nanos(nanos), &plain)
+}
+
+/// A word being executed
+#[derive(Debug)]
+struct Frame {
+    name: String,
+    entered: Instant,
+    children: Duration,
+}
+
+/// Accumulated timing for one word
+#[derive(Debug, Default)]
+struct WordTotals {
+    calls: u64,
+    inclusive: Duration,
+    exclusive: Duration,
+}
+
+/// A VM observer that times words through the call stack
+#[derive(Debug)]
+pub struct Profiler {
+    started: Instant,
+    frames: Vec<Frame>,
+    words: HashMap<String, WordTotals>,
+    stacks: HashMap<String, Duration>,
+}
+
+impl Profiler {
+    /// Create a profiler; the total time is measured from now
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            frames: Vec::new(),
+            words: HashMap::new(),
+            stacks: HashMap::new(),
+        }
+    }
+
+    /// Summarize everything observed so far
+    pub fn report(&self) -> ProfileReport {
+        let mut words: Vec<WordProfile> = self
+            .words
+            .iter()
+            .map(|(name, totals)| WordProfile {
+                name: name.clone(),
+                calls: totals.calls,
+                inclusive_ns: nanos(totals.inclusive),
+                exclusive_ns: nanos(totals.exclusive),
+            })
+            .collect();
+        words.sort_by(|a, b| {
+            b.inclusive_ns
+                .cmp(&a.inclusive_ns)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let mut stacks: Vec<FoldedStack> = self
+            .stacks
+            .iter()
+            .map(|(stack, exclusive)| FoldedStack {
+                stack: stack.clone(),
+                exclusive_ns: nanos(*exclusive),
+            })
+            .collect();
+        stacks.sort_by(|a, b| a.stack.cmp(&b.stack));
+
+        ProfileReport {
+            total_ns: nanos(self.started.elapsed()),
+            words,
+            stacks,
+        }
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VmObserver for Profiler {
+    fn word_enter(&mut self, word: &str, _context: &ExecutionContext<'_>) {
+        self.frames.push(Frame {
+            name: word.to_string(),
+            entered: Instant::now(),
+            children: Duration::ZERO,
+        });
+    }
+
+    fn word_exit(&mut self, _word: &str, _context: &ExecutionContext<'_>) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        let elapsed = frame.entered.elapsed();
+        let exclusive = elapsed.saturating_sub(frame.children);
+        if let Some(caller) = self.frames.last_mut() {
+            caller.children += elapsed;
+        }
+
+        let path = self
+            .frames
+            .iter()
+            .map(|caller| caller.name.as_str())
+            .chain(Some(frame.name.as_str()))
+            .collect::<Vec<_>>()
+            .join(";");
+        *self.stacks.entry(path).or_default() += exclusive;
+
+        // An outer call of the same word already covers this one
+        let recursive = self.frames.iter().any(|caller| caller.name == frame.name);
+        let totals = self.words.entry(frame.name).or_default();
+        totals.calls += 1;
+        totals.exclusive += exclusive;
+        if !recursive {
+            totals.inclusive += elapsed;
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chronos_core::ChronosCore;
+    use std::sync::{Arc, Mutex};
+
+    fn profile(code: &str) -> ProfileReport {
+        let mut core = ChronosCore::new();
+        core.eval_source(
+            ": square dup * ; : quad square square ; \
+             : countdown dup 0 = [ drop ] [ 1 - countdown ] if ;",
+        )
+        .unwrap();
+
+        let profiler = Arc::new(Mutex::new(Profiler::new()));
+        let id = core.add_observer(Box::new(profiler.clone()));
+        core.eval_source(code).unwrap();
+        core.remove_observer(id);
+
+        let profiler = profiler.lock().unwrap();
+        profiler.report()
+    }
+
+    fn word<'a>(report: &'a ProfileReport, name: &str) -> &'a WordProfile {
+        report.words.iter().find(|word| word.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_counts_nested_calls() {
+        let report = profile("2 quad 3 square");
+        assert_eq!(word(&report, "quad").calls, 1);
+        assert_eq!(word(&report, "square").calls, 3);
+        assert_eq!(word(&report, "dup").calls, 3);
+
+        let quad = word(&report, "quad");
+        assert!(quad.inclusive_ns >= quad.exclusive_ns);
+        assert!(quad.inclusive_ns >= word(&report, "square").exclusive_ns / 3);
+
+        let stacks: Vec<&str> = report
+            .stacks
+            .iter()
+            .map(|stack| stack.stack.as_str())
+            .collect();
+        assert!(stacks.contains(&"quad;square;dup"));
+        assert!(stacks.contains(&"square;dup"));
+    }
+
+    #[test]
+    fn test_recursion_is_counted_once() {
+        let report = profile("3 countdown");
+        let countdown = word(&report, "countdown");
+        assert_eq!(countdown.calls, 4);
+        assert!(countdown.inclusive_ns <= report.total_ns);
+        assert!(report
+            .stacks
+            .iter()
+            .any(|stack| stack.stack == "countdown;if;countdown;if;countdown"));
+    }
+
+    #[test]
+    fn test_exports() {
+        let mut report = profile("2 quad");
+        report.retain_words(|name| name == "quad" || name == "square");
+        assert_eq!(report.words.len(), 2);
+
+        let folded = report.to_folded();
+        assert!(folded.lines().any(|line| line.starts_with("quad;square ")));
+        assert!(!folded.contains("dup"));
+
+        let json: ProfileReport = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json, report);
+        assert!(report.to_string().contains("square"));
+    }
+}
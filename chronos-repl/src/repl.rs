@@ -5,17 +5,21 @@
 //! tracing, performance monitoring, and enhanced user experience features.
 
 use crate::commands::{execute_command, parse_command, ReplCommand};
+use crate::debugger::ConsoleDebugger;
 use crate::display::{
-    format_duration, format_error, format_help, format_info, format_stack_rich, DisplayConfig,
+    format_axioms, format_duration, format_error, format_help, format_info, format_stack_rich,
+    format_theorems, DisplayConfig,
 };
-use crate::error::{ReplError, Result};
+use crate::editor::LineEditor;
+use crate::error::{ReplError, Result, SessionError};
+use crate::profiler::{ProfileReport, Profiler};
 use crate::session::Session;
 use crate::tracing::{ExecutionTrace, OperationCategory, TraceContext, TraceEntry};
-use chronos_core::{ChronosCore, Token, Value};
+use chronos_core::{Breakpoint, ChronosCore, DebugHandler, Debugger, Token, Value};
 use serde::{Deserialize, Serialize};
 
-use std::io::{self, Write};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Configuration for the Enhanced REPL
@@ -137,6 +141,12 @@ pub struct EnhancedRepl {
 
     /// Last auto-save time
     last_auto_save: Instant,
+
+    /// Breakpoints kept between `.debug` runs
+    breakpoints: Vec<Breakpoint>,
+
+    /// Report from the last `.profile` run
+    last_profile: Option<ProfileReport>,
 }
 
 impl EnhancedRepl {
@@ -157,6 +167,8 @@ impl EnhancedRepl {
             nesting_level: 0,
             should_exit: false,
             last_auto_save: Instant::now(),
+            breakpoints: Vec::new(),
+            last_profile: None,
         }
     }
 
@@ -166,6 +178,10 @@ impl EnhancedRepl {
             self.show_welcome();
         }
 
+        let mut editor = LineEditor::new()?;
+        editor.load_history(self.session.history())?;
+        let mut session_id = self.session.data().session_id.clone();
+
         loop {
             // Handle auto-save
             if self.should_auto_save() {
@@ -175,43 +191,43 @@ impl EnhancedRepl {
                 }
             }
 
-            // Show prompt
-            print!("{}", self.config.prompt);
-            io::stdout()
-                .flush()
-                .map_err(|e| ReplError::io(e.to_string()))?;
-
-            // Read input
-            let mut input = String::new();
-            match io::stdin().read_line(&mut input) {
-                Ok(0) => {
+            // Read input, continuing across lines while a definition is open
+            editor.set_words(self.words());
+            let input = match editor.read_input(&self.config.prompt) {
+                Ok(Some(input)) => input,
+                Ok(None) => {
                     // EOF reached - exit gracefully
                     println!("\nFarewell! May your axioms remain consistent.");
                     break;
                 }
-                Ok(_) => {
-                    let input = input.trim();
-
-                    if input.is_empty() {
-                        continue;
-                    }
-
-                    // Handle the input
-                    if let Err(e) = self.handle_input(input) {
-                        eprintln!("{}", format_error(&e.to_string(), &self.config.display));
-                        self.metrics.error_count += 1;
-                    }
-
-                    // Check if we should exit
-                    if self.should_exit {
-                        break;
-                    }
-                }
                 Err(e) => {
                     let error_msg = format!("Error reading input: {}", e);
                     eprintln!("{}", format_error(&error_msg, &self.config.display));
                     break;
                 }
+            };
+
+            let input = input.trim();
+            if input.is_empty() {
+                continue;
+            }
+            editor.add_history(input)?;
+
+            // Handle the input
+            if let Err(e) = self.handle_input(input) {
+                eprintln!("{}", format_error(&e.to_string(), &self.config.display));
+                self.metrics.error_count += 1;
+            }
+
+            // A loaded session brings its own history
+            if self.session.data().session_id != session_id {
+                session_id = self.session.data().session_id.clone();
+                editor.load_history(self.session.history())?;
+            }
+
+            // Check if we should exit
+            if self.should_exit {
+                break;
             }
         }
 
@@ -304,6 +320,46 @@ impl EnhancedRepl {
             _ => {}
         }
 
+        match &command {
+            ReplCommand::LoadFile(file) => {
+                self.load_file(file)?;
+                let message = format!("Loaded {}", file);
+                println!("{}", format_info(&message, &self.config.display));
+                return Ok(());
+            }
+            ReplCommand::Debug(code) => return self.debug(code),
+            ReplCommand::Axioms => {
+                let dependencies = self.core.axiom_dependencies();
+                let axioms = format_axioms(self.core.axioms(), &dependencies, &self.config.display);
+                println!("{}", axioms);
+                return Ok(());
+            }
+            ReplCommand::Theorems => {
+                let theorems =
+                    format_theorems(self.core.proofs(), self.core.lemmas(), &self.config.display);
+                println!("{}", theorems);
+                return Ok(());
+            }
+            ReplCommand::Profile(code) => {
+                let mut report = self.profile(code)?;
+                report.retain_words(|name| self.core.get_word_definition(name).is_some());
+                println!("{}", report);
+                return Ok(());
+            }
+            ReplCommand::Check(word) => {
+                let report = self.core.check_word(word)?;
+                println!("{}", report);
+                return Ok(());
+            }
+            ReplCommand::ProfileExport(file) => {
+                self.export_profile(file)?;
+                let message = format!("Profile saved to {}", file);
+                println!("{}", format_info(&message, &self.config.display));
+                return Ok(());
+            }
+            _ => {}
+        }
+
         // Special handling for help command to use rich formatting
         if matches!(command, ReplCommand::Help) {
             let help_text = format_help(&self.config.display);
@@ -352,7 +408,7 @@ impl EnhancedRepl {
                         println!("Farewell! May your axioms remain consistent.");
                         return Ok(());
                     } else {
-                        return Err(ReplError::from(e));
+                        return Err(ReplError::in_source(e, input));
                     }
                 }
             }
@@ -389,6 +445,78 @@ impl EnhancedRepl {
         Ok(())
     }
 
+    /// Evaluate a C∀O source file, as `include "<file>"` would
+    ///
+    /// Relative paths are looked up in the working directory, then in the
+    /// core's search paths.
+    pub fn load_file(&mut self, path: &str) -> Result<()> {
+        self.core.include_file(path)?;
+        self.session.update_stack(self.core.get_stack());
+        self.sync_user_words();
+        Ok(())
+    }
+
+    /// Evaluate code under the step debugger, pausing before its first token
+    pub fn debug(&mut self, input: &str) -> Result<()> {
+        self.debug_with(input, ConsoleDebugger::stdio())
+    }
+
+    /// Evaluate code under the step debugger, letting `handler` decide how
+    /// to resume at each pause
+    ///
+    /// Breakpoints set during the run are kept for the next one.
+    pub fn debug_with<H: DebugHandler + 'static>(&mut self, input: &str, handler: H) -> Result<()> {
+        let mut debugger = Debugger::new(Box::new(handler));
+        for breakpoint in &self.breakpoints {
+            debugger.add_breakpoint(breakpoint.clone());
+        }
+
+        self.core.set_debugger(Some(debugger));
+        let result = self.eval(input);
+        if let Some(debugger) = self.core.set_debugger(None) {
+            self.breakpoints = debugger.breakpoints().to_vec();
+        }
+        result
+    }
+
+    /// Evaluate code while timing every word it calls
+    ///
+    /// The report covers builtin and user-defined words alike and is kept for
+    /// [`EnhancedRepl::export_profile`].
+    pub fn profile(&mut self, input: &str) -> Result<ProfileReport> {
+        let profiler = Arc::new(Mutex::new(Profiler::new()));
+        let id = self.core.add_observer(Box::new(profiler.clone()));
+        let result = self.eval(input);
+        self.core.remove_observer(id);
+        result?;
+
+        let report = profiler
+            .lock()
+            .map_err(|_| ReplError::performance("Profiler state was poisoned"))?
+            .report();
+        self.last_profile = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Save the last profile: a JSON report for `.json` files, folded stacks
+    /// for flame graph tools otherwise
+    pub fn export_profile<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let report = self
+            .last_profile
+            .as_ref()
+            .ok_or_else(|| ReplError::command("No profile yet; run .profile <code> first"))?;
+
+        let path = path.as_ref();
+        let contents = if path.extension().is_some_and(|ext| ext == "json") {
+            report
+                .to_json()
+                .map_err(|e| ReplError::performance(e.to_string()))?
+        } else {
+            report.to_folded()
+        };
+        std::fs::write(path, contents).map_err(|e| ReplError::io(e.to_string()))
+    }
+
     /// Evaluate code with detailed tracing
     pub fn eval_with_trace(&mut self, input: &str) -> Result<EvalResult> {
         let start_time = Instant::now();
@@ -405,7 +533,9 @@ impl EnhancedRepl {
             }
             entries
         } else {
-            self.core.eval(input).map_err(ReplError::from)?;
+            self.core
+                .eval(input)
+                .map_err(|e| ReplError::in_source(e, input))?;
             Vec::new()
         };
 
@@ -425,11 +555,19 @@ impl EnhancedRepl {
 
     /// Evaluate with detailed tracing enabled
     fn eval_with_tracing(&mut self, input: &str) -> Result<()> {
-        // Parse the input with quote processing
-        let tokens = self.core.parse(input).map_err(ReplError::from)?;
+        // Run any imports first, then parse the rest with quote processing
+        let source = self
+            .core
+            .load_modules(input)
+            .map_err(|e| ReplError::in_source(e, input))?;
+        let tokens = self
+            .core
+            .parse_spanned(&source)
+            .map_err(|e| ReplError::in_source(e, input))?;
 
         // Execute each token with tracing
-        for token in tokens {
+        for spanned in tokens {
+            let token = spanned.node;
             let stack_before = self.core.get_stack();
             let start_time = Instant::now();
 
@@ -450,7 +588,7 @@ impl EnhancedRepl {
                 .trace_execution(token, stack_before, stack_after, duration, error, context);
 
             // Propagate any error
-            result.map_err(ReplError::from)?;
+            result.map_err(|e| ReplError::in_source(e.with_span(spanned.span), input))?;
         }
 
         Ok(())
@@ -472,6 +610,11 @@ impl EnhancedRepl {
         }
     }
 
+    /// Get the names of all words in the dictionary, built-in and user-defined
+    pub fn words(&self) -> Vec<String> {
+        self.core.get_words()
+    }
+
     /// Get current stack contents
     pub fn stack(&self) -> Vec<Value> {
         self.core.get_stack()
@@ -488,24 +631,52 @@ impl EnhancedRepl {
     }
 
     /// Load session from file
+    ///
+    /// The saved stack replaces the current one and the saved words and
+    /// declarations are defined again. Nothing changes if a saved word is
+    /// already defined here with a different body or signature.
     pub fn load_session<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
-        self.session = Session::load(path)?;
+        let session = Session::load(path)?;
+
+        let mut conflicts: Vec<&str> = session
+            .user_words()
+            .iter()
+            .filter(|(name, saved)| match self.core.get_word_definition(name) {
+                Some(existing) => {
+                    existing.body != saved.body
+                        || self.core.word_signature(name) != session.signatures().get(*name)
+                }
+                None => false,
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !conflicts.is_empty() {
+            conflicts.sort_unstable();
+            return Err(SessionError::word_conflict(conflicts).into());
+        }
 
         // Restore the stack
-        let stack = self.session.data().stack.clone();
         self.core.clear_stack();
-        for value in stack {
-            self.core.push(value);
+        for value in &session.data().stack {
+            self.core.push(value.clone());
         }
 
-        // Restore user-defined words
-        for (name, _tokens) in self.session.user_words() {
-            // For now, we'll need to reconstruct word definitions
-            // This is a simplified approach - in practice we'd want to store
-            // full word definitions in the session
-            println!("Restored word: {}", name);
+        // Restore user-defined words, typed or not, then pending declarations
+        for (name, definition) in session.user_words() {
+            if session.signatures().contains_key(name) {
+                self.core.define_word(definition.clone())?;
+            } else {
+                self.core.define_untyped_word(definition.clone())?;
+            }
+        }
+        for (name, signature) in session.signatures() {
+            if !session.user_words().contains_key(name) {
+                self.core.declare_signature(name.clone(), signature.clone());
+            }
         }
 
+        self.session = session;
+        self.sync_user_words();
         Ok(())
     }
 
@@ -641,15 +812,29 @@ impl EnhancedRepl {
         &mut self.config.display
     }
 
-    /// Synchronize user-defined words from the VM to the session
+    /// Synchronize user-defined words and pending declarations from the VM to the session
     fn sync_user_words(&mut self) {
-        let user_words = self.core.get_user_words();
-        for word_name in user_words {
-            // For now, we'll store empty token sequences since we don't have access
-            // to the actual token sequence from the VM dictionary
-            // This is a simplified implementation - ideally we'd store the actual tokens
-            if !self.session.user_words().contains_key(&word_name) {
-                self.session.define_word(word_name, vec![]);
+        for name in self.core.get_user_words() {
+            let Some(definition) = self.core.get_word_definition(&name) else {
+                continue;
+            };
+            let signature = self.core.word_signature(&name);
+            let unchanged = self
+                .session
+                .user_words()
+                .get(&name)
+                .is_some_and(|saved| saved.body == definition.body)
+                && self.session.signatures().get(&name) == signature;
+            if !unchanged {
+                self.session
+                    .define_word(definition.clone(), signature.cloned());
+            }
+        }
+
+        for (name, signature) in self.core.pending_signatures() {
+            if self.session.signatures().get(name) != Some(signature) {
+                self.session
+                    .declare_signature(name.clone(), signature.clone());
             }
         }
     }
@@ -664,6 +849,7 @@ impl Default for EnhancedRepl {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use tempfile::NamedTempFile;
 
     #[test]
@@ -743,6 +929,103 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_session_restores_words() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        {
+            let mut repl = EnhancedRepl::new();
+            repl.eval(":: square ( Nat -> Nat ) ; : square dup * ;")
+                .unwrap();
+            repl.eval(": fourth square square ;").unwrap();
+            repl.eval(":: later ( Nat -> Bool ) ;").unwrap();
+            repl.save_session(path).unwrap();
+        }
+
+        let mut repl = EnhancedRepl::new();
+        repl.load_session(path).unwrap();
+        repl.eval("3 fourth").unwrap();
+        assert_eq!(repl.stack(), vec![Value::Nat(81)]);
+
+        // Signatures and pending declarations are restored too
+        assert!(repl.core.word_signature("square").is_some());
+        assert!(repl.eval(": later 1 + ;").is_err());
+
+        // Loading again is harmless, but a different definition conflicts
+        repl.load_session(path).unwrap();
+        let mut other = EnhancedRepl::new();
+        other.eval(": square dup + ;").unwrap();
+        let error = other.load_session(path).unwrap_err();
+        assert!(error.to_string().contains("square"));
+        assert!(other.stack().is_empty());
+    }
+
+    #[test]
+    fn test_debug_keeps_breakpoints() {
+        use std::io::{sink, Cursor};
+
+        let mut repl = EnhancedRepl::new();
+        let commands = Cursor::new("break dup\ncontinue\ncontinue\n");
+        repl.debug_with(
+            ": square dup * ; 3 square",
+            ConsoleDebugger::new(commands, sink()),
+        )
+        .unwrap();
+        assert_eq!(repl.stack(), vec![Value::Nat(9)]);
+        assert_eq!(repl.breakpoints, vec![Breakpoint::Word("dup".to_string())]);
+    }
+
+    #[test]
+    fn test_profile_and_export() {
+        let mut repl = EnhancedRepl::new();
+        assert!(repl.export_profile("unused.folded").is_err());
+
+        repl.eval(": square dup * ; : quad square square ;")
+            .unwrap();
+        let report = repl.profile("2 quad").unwrap();
+        assert_eq!(repl.stack(), vec![Value::Nat(16)]);
+        assert!(report
+            .words
+            .iter()
+            .any(|w| w.name == "square" && w.calls == 2));
+
+        let dir = tempfile::tempdir().unwrap();
+        let folded = dir.path().join("profile.folded");
+        repl.export_profile(&folded).unwrap();
+        assert!(fs::read_to_string(&folded)
+            .unwrap()
+            .contains("quad;square;dup "));
+
+        let json = dir.path().join("profile.json");
+        repl.export_profile(&json).unwrap();
+        let saved: ProfileReport =
+            serde_json::from_str(&fs::read_to_string(&json).unwrap()).unwrap();
+        assert_eq!(saved, report);
+    }
+
+    #[test]
+    fn test_load_file_and_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join("shapes.cao");
+        fs::write(&lib, "export square ;\n: square dup * ;").unwrap();
+        fs::write(
+            dir.path().join("main.cao"),
+            "import \"shapes.cao\"\n: cube dup square * ;",
+        )
+        .unwrap();
+
+        let mut repl = EnhancedRepl::new();
+        repl.load_file(dir.path().join("main.cao").to_str().unwrap())
+            .unwrap();
+        repl.eval("2 cube 3 shapes::square").unwrap();
+        assert_eq!(repl.stack(), vec![Value::Nat(8), Value::Nat(9)]);
+        assert!(repl.session.user_words().contains_key("shapes::square"));
+
+        let error = repl.load_file("missing.cao").unwrap_err();
+        assert!(error.to_string().contains("missing.cao"));
+    }
+
     #[test]
     fn test_command_handling() {
         let mut repl = EnhancedRepl::new();
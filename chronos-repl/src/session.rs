@@ -4,7 +4,7 @@
 //! words, types, stack state, and configuration settings.
 
 use crate::error::{Result, SessionError};
-use chronos_core::{Token, Value};
+use chronos_core::{TypeSignature, Value, WordDefinition};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -12,7 +12,7 @@ use std::path::Path;
 use uuid::Uuid;
 
 /// Current session format version
-pub const SESSION_VERSION: &str = "0.1.0";
+pub const SESSION_VERSION: &str = "0.2.0";
 
 /// Session data that can be serialized and persisted
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,8 +29,13 @@ pub struct SessionData {
     /// Current stack contents
     pub stack: Vec<Value>,
 
-    /// User-defined words (stored as token sequences)
-    pub user_words: HashMap<String, Vec<Token>>,
+    /// User-defined words, with their bodies and ordinal costs
+    pub user_words: HashMap<String, WordDefinition>,
+
+    /// Signatures of typed user words and `::` declarations awaiting a
+    /// definition; user words without an entry have an unknown stack effect
+    #[serde(default)]
+    pub signatures: HashMap<String, TypeSignature>,
 
     /// Session configuration
     pub config: SessionConfig,
@@ -42,6 +47,13 @@ pub struct SessionData {
     pub stats: SessionStats,
 }
 
+/// The version of a session file, read before the rest of it so that a file
+/// in another format is reported as a version mismatch
+#[derive(Deserialize)]
+struct SessionVersion {
+    version: String,
+}
+
 /// Session metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMetadata {
@@ -162,6 +174,7 @@ impl Default for SessionData {
             },
             stack: Vec::new(),
             user_words: HashMap::new(),
+            signatures: HashMap::new(),
             config: SessionConfig::default(),
             history: Vec::new(),
             stats: SessionStats::default(),
@@ -199,13 +212,14 @@ impl Session {
         let contents =
             fs::read_to_string(path).map_err(|e| SessionError::Serialization(e.to_string()))?;
 
-        let data: SessionData = serde_json::from_str(&contents)?;
-
         // Validate session version compatibility
-        if data.version != SESSION_VERSION {
-            return Err(SessionError::version_mismatch(SESSION_VERSION, &data.version).into());
+        let SessionVersion { version } = serde_json::from_str(&contents)?;
+        if version != SESSION_VERSION {
+            return Err(SessionError::version_mismatch(SESSION_VERSION, &version).into());
         }
 
+        let data: SessionData = serde_json::from_str(&contents)?;
+
         Ok(Self {
             data,
             file_path: Some(path.to_path_buf()),
@@ -291,12 +305,16 @@ impl Session {
             self.data.stats.max_stack_depth.max(self.data.stack.len());
     }
 
-    /// Add a user-defined word
-    pub fn define_word<S: Into<String>>(&mut self, name: S, tokens: Vec<Token>) {
-        let name = name.into();
+    /// Add a user-defined word, with its signature if the word is typed
+    pub fn define_word(&mut self, definition: WordDefinition, signature: Option<TypeSignature>) {
+        let name = definition.name.clone();
         let is_new = !self.data.user_words.contains_key(&name);
 
-        self.data.user_words.insert(name, tokens);
+        match signature {
+            Some(signature) => self.data.signatures.insert(name.clone(), signature),
+            None => self.data.signatures.remove(&name),
+        };
+        self.data.user_words.insert(name, definition);
         self.dirty = true;
 
         if is_new {
@@ -304,20 +322,32 @@ impl Session {
         }
     }
 
+    /// Record a `::` declaration for a word that is not defined yet
+    pub fn declare_signature<S: Into<String>>(&mut self, name: S, signature: TypeSignature) {
+        self.data.signatures.insert(name.into(), signature);
+        self.dirty = true;
+    }
+
     /// Remove a user-defined word
     pub fn undefine_word<S: AsRef<str>>(&mut self, name: S) -> bool {
         let removed = self.data.user_words.remove(name.as_ref()).is_some();
         if removed {
+            self.data.signatures.remove(name.as_ref());
             self.dirty = true;
         }
         removed
     }
 
     /// Get all user-defined words
-    pub fn user_words(&self) -> &HashMap<String, Vec<Token>> {
+    pub fn user_words(&self) -> &HashMap<String, WordDefinition> {
         &self.data.user_words
     }
 
+    /// Get the signatures of typed user words and pending declarations
+    pub fn signatures(&self) -> &HashMap<String, TypeSignature> {
+        &self.data.signatures
+    }
+
     /// Add command to history
     pub fn add_to_history<S: Into<String>>(&mut self, command: S) {
         let command = command.into();
@@ -440,8 +470,22 @@ impl Session {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chronos_core::{OrdinalValue, Token, Type};
     use tempfile::NamedTempFile;
 
+    fn word(name: &str, body: Vec<Token>) -> WordDefinition {
+        WordDefinition {
+            name: name.to_string(),
+            signature: TypeSignature {
+                inputs: vec![],
+                outputs: vec![Type::Nat],
+            },
+            body,
+            is_axiom: false,
+            ordinal_cost: OrdinalValue::Finite(1),
+        }
+    }
+
     #[test]
     fn test_session_creation() {
         let session = Session::new();
@@ -465,7 +509,16 @@ mod tests {
         // Create and save a session
         {
             let mut session = Session::new_with_name("Test Session");
-            session.define_word("test", vec![Token::Literal(Value::Nat(42))]);
+            let answer = word("answer", vec![Token::Literal(Value::Nat(42))]);
+            let signature = answer.signature.clone();
+            session.define_word(answer, Some(signature));
+            session.declare_signature(
+                "later",
+                TypeSignature {
+                    inputs: vec![Type::Nat],
+                    outputs: vec![],
+                },
+            );
             session.add_to_history("test command");
             session.save(path).unwrap();
         }
@@ -475,26 +528,65 @@ mod tests {
             let session = Session::load(path).unwrap();
             assert_eq!(session.data.metadata.name, Some("Test Session".to_string()));
             assert_eq!(session.data.user_words.len(), 1);
+            assert_eq!(session.data.user_words["answer"].body.len(), 1);
+            assert_eq!(
+                session.data.user_words["answer"].ordinal_cost,
+                OrdinalValue::Finite(1)
+            );
+            assert_eq!(session.signatures().len(), 2);
+            assert_eq!(session.signatures()["later"].inputs, vec![Type::Nat]);
             assert_eq!(session.data.history.len(), 1);
             assert!(!session.is_dirty());
         }
     }
 
+    #[test]
+    fn test_load_older_version() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        Session::new().save(path).unwrap();
+
+        // A 0.1.0 session file has no signatures
+        let mut json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        json["version"] = "0.1.0".into();
+        json.as_object_mut().unwrap().remove("signatures");
+        fs::write(path, json.to_string()).unwrap();
+
+        let Err(error) = Session::load(path) else {
+            panic!("Expected a version mismatch");
+        };
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Session error: Session version mismatch: expected {}, found 0.1.0",
+                SESSION_VERSION
+            )
+        );
+    }
+
     #[test]
     fn test_word_definition() {
         let mut session = Session::new();
         let tokens = vec![Token::Literal(Value::Nat(42))];
+        let definition = word("test", tokens.clone());
+        let signature = definition.signature.clone();
 
-        session.define_word("test", tokens.clone());
+        session.define_word(definition.clone(), Some(signature));
         assert!(session.is_dirty());
         assert_eq!(session.data.stats.words_defined, 1);
 
-        let stored_tokens = session.user_words().get("test").unwrap();
-        assert_eq!(stored_tokens, &tokens);
+        let stored = session.user_words().get("test").unwrap();
+        assert_eq!(stored.body, tokens);
+        assert!(session.signatures().contains_key("test"));
 
-        // Redefining doesn't increment count
-        session.define_word("test", tokens);
+        // Redefining doesn't increment count, and can drop the signature
+        session.define_word(definition, None);
         assert_eq!(session.data.stats.words_defined, 1);
+        assert!(!session.signatures().contains_key("test"));
+
+        assert!(session.undefine_word("test"));
+        assert!(session.user_words().is_empty());
     }
 
     #[test]
@@ -532,14 +624,14 @@ mod tests {
         assert!(!session.should_auto_save());
 
         // Auto-save if dirty and interval elapsed
-        session.define_word("test", vec![]);
+        session.define_word(word("test", vec![]), None);
         assert!(session.should_auto_save());
     }
 
     #[test]
     fn test_session_reset() {
         let mut session = Session::new_with_name("Test");
-        session.define_word("test", vec![]);
+        session.define_word(word("test", vec![]), None);
         session.add_to_history("test");
 
         let original_id = session.data.session_id.clone();
@@ -9,11 +9,8 @@
 
 pub mod goal_builders;
 pub mod hypervisor;
-pub mod ordinal;
-pub mod parser;
 pub mod shell;
 pub mod shell_manager;
-pub mod type_inference;
 
 // Re-export commonly used types and functions for convenience
 pub use goal_builders::{
@@ -24,3 +21,4 @@ pub use hypervisor::{Hypervisor, HypervisorConfig, ShellStatus, VirtualShell};
 pub use shell::{CompletionStatus, Goal, GoalType, Shell, ShellMode};
 pub use shell_manager::{CoordinationStrategy, ShellManager};
 pub use chronos_core::{Type, TypeSignature, Value};
+pub use chronos_core::{ordinal, parser, type_inference};
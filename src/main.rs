@@ -8,20 +8,47 @@
 
 mod goal_builders;
 mod hypervisor;
-mod ordinal;
-mod parser;
-
+mod runner;
 mod shell;
 mod shell_manager;
-mod type_inference;
-
-use std::io::{self, Write};
 
 use crate::hypervisor::Hypervisor;
+use crate::runner::Command;
 
-use chronos_repl::repl;
+use chronos_repl::{repl, LineEditor};
+use std::env;
+use std::io::{self, IsTerminal};
+use std::process;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match runner::parse_args(&args, io::stdin().is_terminal()) {
+        Ok(Command::Interactive) => {}
+        Ok(Command::Help) => {
+            println!("{}", runner::USAGE);
+            return Ok(());
+        }
+        Ok(Command::Test(paths)) => {
+            if let Err(e) = runner::test(&paths, &mut io::stdout().lock()) {
+                eprintln!("{}", e);
+                process::exit(e.exit_code());
+            }
+            return Ok(());
+        }
+        Ok(Command::Run(options)) => {
+            if let Err(e) = runner::run(&options, &mut io::stdout().lock()) {
+                eprintln!("{}", e);
+                process::exit(e.exit_code());
+            }
+            return Ok(());
+        }
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            eprintln!("Try 'chronos --help' for more information.");
+            process::exit(2);
+        }
+    }
+
     println!("C∀O (Kao) - Categorical ∀xiomatic Ordinal Language v0.1.0");
     println!("An Evolving Axiomatic Programming Language");
     println!("Type 'help' for available commands, 'quit' to exit");
@@ -30,18 +57,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut repl = repl::EnhancedRepl::new();    
 
-    loop {
-        print!("C∀O> ");
-        io::stdout().flush()?;
+    let mut editor = LineEditor::new()?;
 
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
+    loop {
+        editor.set_words(repl.words());
+        match editor.read_input("C∀O> ") {
+            Ok(Some(input)) => {
                 let input = input.trim();
 
                 if input.is_empty() {
                     continue;
                 }
+                editor.add_history(input)?;
 
                 if input == "quit" || input == "exit" {
                     println!("Farewell! May your axioms remain consistent.");
@@ -62,6 +89,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     eprintln!("Error: {}", e);
                 }
             }
+            Ok(None) => {
+                println!("Farewell! May your axioms remain consistent.");
+                break;
+            }
             Err(error) => {
                 eprintln!("Error reading input: {}", error);
                 break;
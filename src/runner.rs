@@ -0,0 +1,471 @@
+//! Non-interactive execution for the `chronos` binary
+//!
+//! `chronos run file.cao`, `chronos -e "3 4 +"` and `chronos < file.cao` run a
+//! program without the prompt and print whatever is left on the stack. A
+//! program that fails exits with status 1, a bad command line or unreadable
+//! file with status 2, so the binary can be used from shell scripts and CI.
+//! `chronos test` runs the `test:` definitions in `.cao` files the same way.
+
+use chronos_core::testing::{self, TestResult};
+use chronos_core::{ChronosCore, ChronosError};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Usage shown by `chronos --help`
+pub const USAGE: &str = "\
+USAGE:
+    chronos                      Start the interactive REPL
+    chronos run [OPTIONS] <FILE> Run a C∀O source file (`-` reads standard input)
+    chronos -e <CODE> [OPTIONS]  Run the given code
+    chronos [OPTIONS] < FILE     Run code piped to standard input
+    chronos test [PATH...]       Run the `test:` definitions in .cao files
+                                 under each PATH (default: the current directory)
+
+OPTIONS:
+    -e, --eval <CODE>  Run CODE instead of a file
+        --check        Parse and type-check only, without running
+        --json         Print the final stack as JSON values
+    -h, --help         Show this help message
+
+Exit status is 0 on success, 1 when the program fails and 2 for usage errors.";
+
+/// Where the program comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    File(PathBuf),
+    Code(String),
+    Stdin,
+}
+
+/// A non-interactive run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunOptions {
+    pub input: Input,
+    /// Only parse and type-check the program
+    pub check: bool,
+    /// Print the final stack as JSON
+    pub json: bool,
+}
+
+/// What the command line asks for
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Interactive,
+    Help,
+    Run(RunOptions),
+    /// Run the tests in these files and directories
+    Test(Vec<PathBuf>),
+}
+
+/// Why a run failed
+#[derive(Debug)]
+pub enum RunError {
+    /// The input could not be read or the output written
+    Io(String),
+    /// The program failed, rendered against its source
+    Program(String),
+}
+
+impl RunError {
+    /// The process exit status for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RunError::Program(_) => 1,
+            RunError::Io(_) => 2,
+        }
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Io(message) => write!(f, "Error: {}", message),
+            RunError::Program(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Parse the arguments after the program name
+///
+/// Without an input the REPL starts, unless standard input is piped in or
+/// `--check` or `--json` ask for a non-interactive run.
+pub fn parse_args(args: &[String], stdin_is_terminal: bool) -> Result<Command, String> {
+    let mut input = None;
+    let mut check = false;
+    let mut json = false;
+    let mut run = false;
+
+    if args.first().is_some_and(|arg| arg == "test") {
+        return match args[1..].iter().find(|arg| arg.starts_with('-')) {
+            Some(arg) if arg == "-h" || arg == "--help" => Ok(Command::Help),
+            Some(arg) => Err(format!("Unknown option: {}", arg)),
+            None => Ok(Command::Test(args[1..].iter().map(PathBuf::from).collect())),
+        };
+    }
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--check" => check = true,
+            "--json" => json = true,
+            "-e" | "--eval" => {
+                let code = args
+                    .get(i + 1)
+                    .ok_or_else(|| format!("{} requires code to run", args[i]))?;
+                set_input(&mut input, Input::Code(code.clone()))?;
+                i += 1;
+            }
+            "run" if !run && input.is_none() => run = true,
+            "-" => set_input(&mut input, Input::Stdin)?,
+            arg if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            file if run => set_input(&mut input, Input::File(PathBuf::from(file)))?,
+            other => return Err(format!("Unknown command: {}", other)),
+        }
+        i += 1;
+    }
+
+    let input = match input {
+        Some(input) => input,
+        None if run || check || json || !stdin_is_terminal => Input::Stdin,
+        None => return Ok(Command::Interactive),
+    };
+    Ok(Command::Run(RunOptions { input, check, json }))
+}
+
+fn set_input(input: &mut Option<Input>, value: Input) -> Result<(), String> {
+    if input.is_some() {
+        return Err("Only one program can be run at a time".to_string());
+    }
+    *input = Some(value);
+    Ok(())
+}
+
+/// Run a program, writing the final stack to `output`
+pub fn run(options: &RunOptions, output: &mut impl Write) -> Result<(), RunError> {
+    let mut core = ChronosCore::new();
+    let source = match &options.input {
+        Input::File(path) => {
+            // Let the file import its neighbours wherever it is run from
+            if let Some(dir) = path.parent() {
+                core.add_search_path(dir);
+            }
+            fs::read_to_string(path)
+                .map_err(|e| RunError::Io(format!("Cannot read {}: {}", path.display(), e)))?
+        }
+        Input::Code(code) => code.clone(),
+        Input::Stdin => {
+            let mut source = String::new();
+            io::stdin()
+                .read_to_string(&mut source)
+                .map_err(|e| RunError::Io(format!("Cannot read standard input: {}", e)))?;
+            source
+        }
+    };
+
+    match compile_and_run(&mut core, &source, !options.check) {
+        Ok(()) => {}
+        // The `quit` word ends the program early
+        Err(e) if e.to_string().contains("QUIT_REQUESTED") => {}
+        Err(e) => return Err(RunError::Program(e.render(&source))),
+    }
+    if options.check {
+        return Ok(());
+    }
+
+    let stack = core.get_stack();
+    let printed = if options.json {
+        writeln!(
+            output,
+            "{}",
+            serde_json::to_string(&stack).map_err(|e| RunError::Io(e.to_string()))?
+        )
+    } else if stack.is_empty() {
+        Ok(())
+    } else {
+        let values: Vec<String> = stack.iter().map(|value| value.to_string()).collect();
+        writeln!(output, "{}", values.join(" "))
+    };
+    printed.map_err(|e| RunError::Io(e.to_string()))
+}
+
+/// Run the tests in the `.cao` files under `paths`, writing a report to
+/// `output`
+///
+/// Every test runs in a fresh runtime with its file loaded. Files that
+/// define no tests are skipped.
+pub fn test(paths: &[PathBuf], output: &mut impl Write) -> Result<(), RunError> {
+    let mut files = Vec::new();
+    if paths.is_empty() {
+        find_sources(Path::new("."), &mut files)?;
+    }
+    for path in paths {
+        if path.is_dir() {
+            find_sources(path, &mut files)?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut failures = Vec::new();
+    let mut passed = 0;
+    let write_error = |e: io::Error| RunError::Io(e.to_string());
+    for file in &files {
+        let source = fs::read_to_string(file)
+            .map_err(|e| RunError::Io(format!("Cannot read {}: {}", file.display(), e)))?;
+        if !source.contains("test:") {
+            continue;
+        }
+
+        let dir = file.parent().map(Path::to_path_buf);
+        let results = testing::run_tests(&source, |core| {
+            if let Some(dir) = &dir {
+                core.add_search_path(dir);
+            }
+        });
+        let results = match results {
+            Ok(results) => results,
+            Err(e) => {
+                writeln!(output, "load {} ... FAILED", file.display()).map_err(write_error)?;
+                failures.push((file.display().to_string(), e.render(&source)));
+                continue;
+            }
+        };
+
+        for TestResult { name, error } in results {
+            let test = format!("{}::{}", file.display(), name);
+            match error {
+                None => {
+                    writeln!(output, "test {} ... ok", test).map_err(write_error)?;
+                    passed += 1;
+                }
+                Some(error) => {
+                    writeln!(output, "test {} ... FAILED", test).map_err(write_error)?;
+                    failures.push((test, error.to_string()));
+                }
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        writeln!(output, "\nfailures:").map_err(write_error)?;
+        for (test, error) in &failures {
+            writeln!(output, "\n---- {} ----\n{}", test, error).map_err(write_error)?;
+        }
+    }
+    let status = if failures.is_empty() { "ok" } else { "FAILED" };
+    writeln!(
+        output,
+        "\ntest result: {}. {} passed; {} failed",
+        status,
+        passed,
+        failures.len()
+    )
+    .map_err(write_error)?;
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(RunError::Program(format!(
+            "{} of {} tests failed",
+            failures.len(),
+            passed + failures.len()
+        )))
+    }
+}
+
+/// Collect the `.cao` files under `dir`, skipping hidden and build directories
+fn find_sources(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), RunError> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| RunError::Io(format!("Cannot read {}: {}", dir.display(), e)))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                find_sources(&path, files)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "cao") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Load the imports of a program and compile it, then run it if `execute`
+/// is set
+///
+/// `--check` and a normal run share this front end, so any program that
+/// `--check` rejects is also rejected before it starts running.
+fn compile_and_run(
+    core: &mut ChronosCore,
+    source: &str,
+    execute: bool,
+) -> Result<(), ChronosError> {
+    let source = core.load_modules(source)?;
+    let program = core.compile(&source)?;
+    if execute {
+        core.run(&program)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn run_code(code: &str, check: bool, json: bool) -> Result<String, RunError> {
+        let options = RunOptions {
+            input: Input::Code(code.to_string()),
+            check,
+            json,
+        };
+        let mut output = Vec::new();
+        run(&options, &mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&[], true), Ok(Command::Interactive));
+        assert_eq!(
+            parse_args(&[], false),
+            Ok(Command::Run(RunOptions {
+                input: Input::Stdin,
+                check: false,
+                json: false,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("run prog.cao --json"), true),
+            Ok(Command::Run(RunOptions {
+                input: Input::File(PathBuf::from("prog.cao")),
+                check: false,
+                json: true,
+            }))
+        );
+        assert_eq!(
+            parse_args(&["--check".into(), "-e".into(), "3 4 +".into()], true),
+            Ok(Command::Run(RunOptions {
+                input: Input::Code("3 4 +".to_string()),
+                check: true,
+                json: false,
+            }))
+        );
+        assert_eq!(parse_args(&args("run -h"), true), Ok(Command::Help));
+        assert!(parse_args(&args("prog.cao"), true).is_err());
+        assert!(parse_args(&args("-e"), true).is_err());
+        assert!(parse_args(&args("run a.cao b.cao"), true).is_err());
+        assert!(parse_args(&args("--verbose"), true).is_err());
+
+        assert_eq!(
+            parse_args(&args("test lib tests/math.cao"), true),
+            Ok(Command::Test(vec![
+                PathBuf::from("lib"),
+                PathBuf::from("tests/math.cao")
+            ]))
+        );
+        assert_eq!(parse_args(&args("test"), true), Ok(Command::Test(vec![])));
+        assert!(parse_args(&args("test --json"), true).is_err());
+    }
+
+    #[test]
+    fn test_run_prints_the_final_stack() {
+        assert_eq!(run_code("3 4 + 2", false, false).unwrap(), "7 2\n");
+        assert_eq!(run_code("1 drop", false, false).unwrap(), "");
+        assert_eq!(
+            run_code("3 4 + true", false, true).unwrap(),
+            "[{\"Nat\":7},{\"Bool\":true}]\n"
+        );
+        assert_eq!(run_code("1 quit 2", false, false).unwrap(), "1\n");
+    }
+
+    #[test]
+    fn test_failures_exit_with_status_one() {
+        let error = run_code("1 frob", false, false).unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+        assert!(error.to_string().contains("1 frob"));
+
+        let missing = RunOptions {
+            input: Input::File(PathBuf::from("no/such/file.cao")),
+            check: false,
+            json: false,
+        };
+        assert_eq!(run(&missing, &mut Vec::new()).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn test_check_does_not_run() {
+        assert_eq!(run_code("3 4 + .", true, false).unwrap(), "");
+        assert!(run_code(":: bad ( Nat -> Bool ) ; : bad 1 + ;", true, false).is_err());
+    }
+
+    #[test]
+    fn test_check_and_run_reject_the_same_programs() {
+        for code in [
+            ": spin spin ; 1",
+            ":: bad ( Nat -> Bool ) ; : bad 1 + ; 2",
+            "#[1, true]",
+        ] {
+            let checked = run_code(code, true, false).unwrap_err();
+            let ran = run_code(code, false, false).unwrap_err();
+            assert_eq!(checked.exit_code(), 1, "{}", code);
+            assert_eq!(ran.exit_code(), 1, "{}", code);
+            assert_eq!(checked.to_string(), ran.to_string(), "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_test_reports_each_test() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(
+            dir.path().join("nested/square.cao"),
+            ": square dup * ;\n\
+             test: squares 3 square 9 assert-eq ;\n\
+             test: wrong 3 square 6 assert-eq ;",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.cao"), "1 2 +").unwrap();
+
+        let mut output = Vec::new();
+        let error = test(&[dir.path().to_path_buf()], &mut output).unwrap_err();
+        assert_eq!(error.exit_code(), 1);
+        assert_eq!(error.to_string(), "1 of 2 tests failed");
+
+        let report = String::from_utf8(output).unwrap();
+        assert!(report.contains("square.cao::squares ... ok"));
+        assert!(report.contains("square.cao::wrong ... FAILED"));
+        assert!(report.contains("expected: [6]\n  actual:   [9]"));
+        assert!(report.contains("test result: FAILED. 1 passed; 1 failed"));
+        assert!(!report.contains("notes.cao"));
+    }
+
+    #[test]
+    fn test_run_file_with_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("lib.cao"), "export triple ; : triple 3 * ;").unwrap();
+        let main = dir.path().join("main.cao");
+        fs::write(&main, "import \"lib.cao\"\n5 triple").unwrap();
+
+        let options = RunOptions {
+            input: Input::File(main),
+            check: false,
+            json: false,
+        };
+        let mut output = Vec::new();
+        run(&options, &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "15\n");
+    }
+}